@@ -1,19 +1,24 @@
-use std::{fmt, ops};
+use std::{borrow::Cow, fmt, ops, sync::Arc};
 
 use BlockType::*;
 use indoc::indoc;
 use ratatui::{
-    style::Stylize,
+    style::{Color, Stylize},
     text::{Line, Span, Text},
 };
 
-use crate::board::Board;
+use crate::board::StandardBoard;
 
 /// Row-column coordinates for matrix access.
 pub type Position = (usize, usize);
 
+/// Row-column coordinates of the top-left corner of an [ActiveBlock]'s bounding box on the board.
+/// The column may be negative, since the bounding box may leave the left bounds of the board.
+pub type TopLeft = (usize, isize);
+
 /// The varieties of block that may be seen in a game.
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BlockType {
     I,
     J,
@@ -22,43 +27,116 @@ pub enum BlockType {
     S,
     T,
     Z,
+    /// A piece registered with a [BlockGenerator](crate::block_generator::BlockGenerator) at
+    /// runtime, identified by the order in which it was registered.
+    Custom(u8),
+}
+
+/// Groups of block types that share rotation behaviour, such as wall kicks.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum KickClass {
+    /// The I block, which has its own kick table.
+    I,
+    /// The O block, whose rotations are identical and never kick.
+    O,
+    /// The J, L, S, T and Z blocks, which share a kick table. Custom pieces also kick like these.
+    Jlstz,
 }
 
 impl BlockType {
-    /// The number of block types in the game.
+    /// The number of standard block types in the game.
     pub const COUNT: u8 = 7;
 
-    /// Returns all possible rotations of the block type.
-    fn rotations(&self) -> &'static Rotations {
+    /// The maximum number of custom pieces that may be registered. Custom pieces are sampled
+    /// alongside the standard block types from a `u8` range, so the combined count must fit.
+    pub const MAX_CUSTOM: u8 = u8::MAX - Self::COUNT;
+
+    /// Returns the class of block type that determines its rotation behaviour.
+    ///
+    /// ```
+    /// use tetrust::{BlockType, KickClass};
+    ///
+    /// let shares_jlstz_kicks = match BlockType::T.kick_class() {
+    ///     KickClass::Jlstz => true,
+    ///     KickClass::I | KickClass::O => false,
+    /// };
+    /// assert!(shares_jlstz_kicks);
+    /// ```
+    pub fn kick_class(&self) -> KickClass {
         match self {
-            I => I_ROTATIONS,
-            J => J_ROTATIONS,
-            L => L_ROTATIONS,
-            O => O_ROTATIONS,
-            S => S_ROTATIONS,
-            T => T_ROTATIONS,
-            Z => Z_ROTATIONS,
+            I => KickClass::I,
+            O => KickClass::O,
+            J | L | S | T | Z | Custom(_) => KickClass::Jlstz,
         }
     }
 
-    fn colorize(&self, s: &'static str) -> Span<'static> {
+    /// Returns the wall kicks for the block type.
+    fn kicks(&self) -> &'static KickTable {
+        match self.kick_class() {
+            KickClass::I => I_KICKS,
+            KickClass::O => O_KICKS,
+            KickClass::Jlstz => JLSTZ_KICKS,
+        }
+    }
+
+    /// Returns all possible rotations of the block type, or None for custom pieces, whose
+    /// rotations are held by the [CustomPieces] they were registered with.
+    fn rotations(&self) -> Option<&'static Rotations> {
         match self {
-            I => s.cyan(),
-            J => s.blue(),
-            L => s.light_red(),
-            O => s.yellow(),
-            S => s.green(),
-            T => s.magenta(),
-            Z => s.red(),
+            I => Some(I_ROTATIONS),
+            J => Some(J_ROTATIONS),
+            L => Some(L_ROTATIONS),
+            O => Some(O_ROTATIONS),
+            S => Some(S_ROTATIONS),
+            T => Some(T_ROTATIONS),
+            Z => Some(Z_ROTATIONS),
+            Custom(_) => None,
+        }
+    }
+
+    /// Returns the rotation of the block type at `index`, or None for custom pieces.
+    fn rotation(&self, index: RotationIndex) -> Option<&'static Rotation> {
+        self.rotations().map(|rotations| &rotations[index])
+    }
+
+    /// Returns the RGB colour of the block type: the Tetris guideline colour for standard blocks,
+    /// or grey for custom pieces, whose colours are known only to the generator that registered
+    /// them. See [Game::block_color](crate::Game::block_color).
+    pub fn color(&self) -> (u8, u8, u8) {
+        match self {
+            I => (0, 255, 255),
+            J => (0, 0, 255),
+            L => (255, 127, 0),
+            O => (255, 255, 0),
+            S => (0, 255, 0),
+            T => (128, 0, 128),
+            Z => (255, 0, 0),
+            Custom(_) => CUSTOM_FALLBACK_COLOR,
         }
     }
 
     /// Returns a coloured grid cell for rendering.
     pub fn grid_cell(&self) -> Span<'static> {
-        self.colorize("██")
+        colorize("██", self.color())
     }
 
-    /// Returns the schematic representation of the block type for rendering.
+    /// Returns a single-character symbol identifying the block type in plain-text output.
+    pub fn symbol(&self) -> char {
+        match self {
+            I => 'I',
+            J => 'J',
+            L => 'L',
+            O => 'O',
+            S => 'S',
+            T => 'T',
+            Z => 'Z',
+            Custom(_) => '#',
+        }
+    }
+
+    /// Returns the schematic representation of the block type for rendering. Custom pieces are
+    /// drawn as a single grey cell, since their shapes are known only to the generator that
+    /// registered them.
     pub fn schematic(&self) -> Text<'static> {
         let raw: &'static str = match self {
             I => indoc! {"
@@ -88,20 +166,24 @@ impl BlockType {
                 ████
                   ████
             "},
+            Custom(_) => "██",
         };
-        raw.lines()
-            .map(|line| Line::from(self.colorize(line)))
-            .collect::<Vec<_>>()
-            .into()
+        colorize_schematic(raw, self.color())
     }
 }
 
-impl ops::Index<RotationIndex> for BlockType {
-    type Output = Rotation;
+/// The colour of custom pieces drawn without the [CustomPieces] they were registered with.
+const CUSTOM_FALLBACK_COLOR: (u8, u8, u8) = (128, 128, 128);
 
-    fn index(&self, index: RotationIndex) -> &Self::Output {
-        &self.rotations()[index]
-    }
+fn colorize<'a>(s: impl Into<Cow<'a, str>>, (r, g, b): (u8, u8, u8)) -> Span<'a> {
+    Span::raw(s).fg(Color::Rgb(r, g, b))
+}
+
+fn colorize_schematic(raw: &str, color: (u8, u8, u8)) -> Text<'static> {
+    raw.lines()
+        .map(|line| Line::from(colorize(line.to_string(), color)))
+        .collect::<Vec<_>>()
+        .into()
 }
 
 impl fmt::Display for BlockType {
@@ -113,7 +195,7 @@ impl fmt::Display for BlockType {
 /// A single rotation of a block situated in a local coordinate space. Conceptually, this is a 2D
 /// matrix, but the matrix itself isn't necessary to implement the game.
 #[derive(Debug, Clone, PartialEq, Eq)]
-struct Rotation {
+pub struct Rotation {
     /// The positive vertical offset of the top of the block from the local coordinate space's
     /// origin.
     vertical_offset: usize,
@@ -129,10 +211,31 @@ struct Rotation {
     height: usize,
 
     /// The positions occupied by the block in its local coordinate space.
-    positions: [Position; 4],
+    positions: Cow<'static, [Position]>,
 }
 
 impl Rotation {
+    /// Builds a rotation from the positions it occupies in its local coordinate space, deriving
+    /// its offsets and dimensions. Returns [None] if there are no positions.
+    fn from_positions(positions: Vec<Position>) -> Option<Self> {
+        let top = positions.iter().map(|(r, _)| *r).min()?;
+        let bottom = positions.iter().map(|(r, _)| *r).max()?;
+        let left = positions.iter().map(|(_, c)| *c).min()?;
+        let right = positions.iter().map(|(_, c)| *c).max()?;
+
+        let mut positions = positions;
+        positions.sort_unstable();
+        positions.dedup();
+
+        Some(Self {
+            vertical_offset: top,
+            horizontal_offset: left,
+            width: right - left + 1,
+            height: bottom - top + 1,
+            positions: Cow::Owned(positions),
+        })
+    }
+
     fn vertical_offset(&self) -> usize {
         self.vertical_offset
     }
@@ -153,27 +256,211 @@ impl Rotation {
     fn positions(&self) -> impl Iterator<Item = &Position> {
         self.positions.iter()
     }
+
+    /// Draws the rotation's occupied cells, trimmed to its bounding box, for rendering.
+    fn schematic(&self) -> String {
+        (0..self.height)
+            .map(|r| {
+                (0..self.width)
+                    .map(|c| {
+                        let pos = (r + self.vertical_offset, c + self.horizontal_offset);
+                        if self.positions.contains(&pos) {
+                            "██"
+                        } else {
+                            "  "
+                        }
+                    })
+                    .collect::<String>()
+                    .trim_end()
+                    .to_string()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// The shape and colour of a custom piece, such as a pentomino.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct CustomPiece {
+    rotations: Rotations,
+    color: (u8, u8, u8),
+    schematic: String,
+}
+
+impl CustomPiece {
+    /// Builds a custom piece from the positions occupied by each of its rotations in its local
+    /// coordinate space. The first rotation must fit in the buffer zone and near the center of the
+    /// board, since this is how the piece spawns.
+    pub(crate) fn new(rotations: [Vec<Position>; 4], color: (u8, u8, u8)) -> Result<Self, String> {
+        let mut built = Vec::with_capacity(4);
+        for (i, positions) in rotations.into_iter().enumerate() {
+            let rotation = Rotation::from_positions(positions)
+                .ok_or_else(|| format!("rotation {i} of custom piece has no positions"))?;
+            if rotation.width() > StandardBoard::COLUMNS {
+                return Err(format!(
+                    "rotation {i} of custom piece is wider than the board: width={}, columns={}",
+                    rotation.width(),
+                    StandardBoard::COLUMNS
+                ));
+            }
+            built.push(rotation);
+        }
+
+        let spawn = &built[0];
+        if spawn.vertical_offset() + spawn.height() > StandardBoard::BUFFER_ZONE_ROWS {
+            return Err(format!(
+                "rotation 0 of custom piece must fit in the {}-row buffer zone to spawn",
+                StandardBoard::BUFFER_ZONE_ROWS
+            ));
+        }
+        if spawn.horizontal_offset() + spawn.width() / 2 > StandardBoard::COLUMNS / 2 {
+            return Err(
+                "rotation 0 of custom piece is too far right of its origin to spawn centrally"
+                    .to_string(),
+            );
+        }
+
+        let schematic = spawn.schematic();
+        Ok(Self {
+            rotations: Rotations(
+                built
+                    .try_into()
+                    .expect("custom piece should have exactly four rotations"),
+            ),
+            color,
+            schematic,
+        })
+    }
+}
+
+/// The custom pieces registered with a [BlockGenerator](crate::block_generator::BlockGenerator),
+/// indexed by the ID of their [BlockType::Custom]. A custom [BlockType] only refers to a piece
+/// within the registry that issued it.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct CustomPieces(Vec<Arc<CustomPiece>>);
+
+impl CustomPieces {
+    /// Adds `piece` to the registry, returning the [BlockType] that refers to it.
+    pub(crate) fn register(&mut self, piece: CustomPiece) -> Result<BlockType, String> {
+        let id = u8::try_from(self.0.len())
+            .ok()
+            .filter(|&id| id < BlockType::MAX_CUSTOM)
+            .ok_or_else(|| {
+                format!(
+                    "cannot register more than {} custom pieces",
+                    BlockType::MAX_CUSTOM
+                )
+            })?;
+        self.0.push(Arc::new(piece));
+        Ok(Custom(id))
+    }
+
+    /// Returns the number of registered pieces.
+    pub(crate) fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns the piece that `block_type` refers to, or None if it's a standard block or a custom
+    /// piece that wasn't registered here.
+    fn get(&self, block_type: BlockType) -> Option<&Arc<CustomPiece>> {
+        match block_type {
+            Custom(id) => self.0.get(usize::from(id)),
+            _ => None,
+        }
+    }
+
+    /// Returns the block type if it's a standard block or a custom piece registered here, or an
+    /// error if it refers to a custom piece that wasn't, such as one decoded from a save written
+    /// with another registry.
+    pub(crate) fn check(&self, block_type: BlockType) -> Result<BlockType, String> {
+        match block_type {
+            Custom(id) if self.get(block_type).is_none() => {
+                Err(format!("no custom piece was registered with ID {id}"))
+            }
+            _ => Ok(block_type),
+        }
+    }
+
+    /// Returns the RGB colour of the block type, including the colour a custom piece was
+    /// registered with.
+    pub(crate) fn color(&self, block_type: BlockType) -> (u8, u8, u8) {
+        self.get(block_type)
+            .map_or_else(|| block_type.color(), |piece| piece.color)
+    }
+
+    /// Returns a coloured grid cell for rendering the block type.
+    pub(crate) fn grid_cell(&self, block_type: BlockType) -> Span<'static> {
+        colorize("██", self.color(block_type))
+    }
+
+    /// Returns the schematic representation of the block type for rendering, including the shape
+    /// of a custom piece.
+    pub(crate) fn schematic(&self, block_type: BlockType) -> Text<'static> {
+        match self.get(block_type) {
+            Some(piece) => colorize_schematic(&piece.schematic, piece.color),
+            None => block_type.schematic(),
+        }
+    }
 }
 
 /// A complete set of four rotations for a [BlockType].
 #[derive(Debug, Clone, PartialEq, Eq)]
 struct Rotations([Rotation; 4]);
 
-/// Type-safe wrapping type for indexing [Rotations], constrained to the range 0..4.
+impl Rotations {
+    /// Returns the rotation at `index`, or None if the index is out of range.
+    fn get(&self, index: RotationIndex) -> Option<&Rotation> {
+        self.0.get(index.0)
+    }
+}
+
+/// Type-safe wrapping type for indexing a block's four rotations, constrained to the range 0..4.
+/// Counts the clockwise quarter turns of a block from its spawn rotation.
 #[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
-struct RotationIndex(usize);
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "usize", into = "usize")
+)]
+pub struct RotationIndex(usize);
 
 impl RotationIndex {
     fn new() -> Self {
         Self::default()
     }
 
+    /// Instantiates the index of any number of clockwise quarter turns from rotation 0, wrapping
+    /// into the range 0..4. Negative values count counter-clockwise turns.
+    fn wrapping(value: isize) -> Self {
+        Self(value.rem_euclid(4) as usize)
+    }
+
     fn inc(&mut self) {
-        self.0 = self.0.wrapping_add(1) % 4
+        self.0 = (self.0 + 1) % 4
     }
 
     fn dec(&mut self) {
-        self.0 = self.0.wrapping_sub(1) % 4
+        // Adding 3 is equivalent to subtracting 1 modulo 4, without underflowing at 0.
+        self.0 = (self.0 + 3) % 4
+    }
+}
+
+impl TryFrom<usize> for RotationIndex {
+    type Error = String;
+
+    fn try_from(value: usize) -> Result<Self, Self::Error> {
+        if value >= 4 {
+            return Err(format!(
+                "rotation index must be in the range 0..4: index={value}"
+            ));
+        }
+        Ok(Self(value))
+    }
+}
+
+impl From<RotationIndex> for usize {
+    fn from(value: RotationIndex) -> Self {
+        value.0
     }
 }
 
@@ -181,7 +468,8 @@ impl ops::Index<RotationIndex> for Rotations {
     type Output = Rotation;
 
     fn index(&self, index: RotationIndex) -> &Self::Output {
-        &self.0[index.0]
+        self.get(index)
+            .unwrap_or_else(|| panic!("rotation index out of range: {index:?}"))
     }
 }
 
@@ -198,28 +486,28 @@ const I_ROTATIONS: &Rotations = &Rotations([
         horizontal_offset: 0,
         width: 4,
         height: 1,
-        positions: [(1, 0), (1, 1), (1, 2), (1, 3)],
+        positions: Cow::Borrowed(&[(1, 0), (1, 1), (1, 2), (1, 3)]),
     },
     Rotation {
         vertical_offset: 0,
         horizontal_offset: 2,
         width: 1,
         height: 4,
-        positions: [(0, 2), (1, 2), (2, 2), (3, 2)],
+        positions: Cow::Borrowed(&[(0, 2), (1, 2), (2, 2), (3, 2)]),
     },
     Rotation {
         vertical_offset: 2,
         horizontal_offset: 0,
         width: 4,
         height: 1,
-        positions: [(2, 0), (2, 1), (2, 2), (2, 3)],
+        positions: Cow::Borrowed(&[(2, 0), (2, 1), (2, 2), (2, 3)]),
     },
     Rotation {
         vertical_offset: 0,
         horizontal_offset: 1,
         width: 1,
         height: 4,
-        positions: [(0, 1), (1, 1), (2, 1), (3, 1)],
+        positions: Cow::Borrowed(&[(0, 1), (1, 1), (2, 1), (3, 1)]),
     },
 ]);
 
@@ -235,28 +523,28 @@ const J_ROTATIONS: &Rotations = &Rotations([
         horizontal_offset: 0,
         width: 3,
         height: 2,
-        positions: [(0, 0), (1, 0), (1, 1), (1, 2)],
+        positions: Cow::Borrowed(&[(0, 0), (1, 0), (1, 1), (1, 2)]),
     },
     Rotation {
         vertical_offset: 0,
         horizontal_offset: 1,
         width: 2,
         height: 3,
-        positions: [(0, 1), (0, 2), (1, 1), (2, 1)],
+        positions: Cow::Borrowed(&[(0, 1), (0, 2), (1, 1), (2, 1)]),
     },
     Rotation {
         vertical_offset: 1,
         horizontal_offset: 0,
         width: 3,
         height: 2,
-        positions: [(1, 0), (1, 1), (1, 2), (2, 2)],
+        positions: Cow::Borrowed(&[(1, 0), (1, 1), (1, 2), (2, 2)]),
     },
     Rotation {
         vertical_offset: 0,
         horizontal_offset: 0,
         width: 2,
         height: 3,
-        positions: [(0, 1), (1, 1), (2, 0), (2, 1)],
+        positions: Cow::Borrowed(&[(0, 1), (1, 1), (2, 0), (2, 1)]),
     },
 ]);
 
@@ -271,28 +559,28 @@ const O_ROTATIONS: &Rotations = &Rotations([
         horizontal_offset: 0,
         width: 2,
         height: 2,
-        positions: [(0, 0), (0, 1), (1, 0), (1, 1)],
+        positions: Cow::Borrowed(&[(0, 0), (0, 1), (1, 0), (1, 1)]),
     },
     Rotation {
         vertical_offset: 0,
         horizontal_offset: 0,
         width: 2,
         height: 2,
-        positions: [(0, 0), (0, 1), (1, 0), (1, 1)],
+        positions: Cow::Borrowed(&[(0, 0), (0, 1), (1, 0), (1, 1)]),
     },
     Rotation {
         vertical_offset: 0,
         horizontal_offset: 0,
         width: 2,
         height: 2,
-        positions: [(0, 0), (0, 1), (1, 0), (1, 1)],
+        positions: Cow::Borrowed(&[(0, 0), (0, 1), (1, 0), (1, 1)]),
     },
     Rotation {
         vertical_offset: 0,
         horizontal_offset: 0,
         width: 2,
         height: 2,
-        positions: [(0, 0), (0, 1), (1, 0), (1, 1)],
+        positions: Cow::Borrowed(&[(0, 0), (0, 1), (1, 0), (1, 1)]),
     },
 ]);
 
@@ -308,28 +596,28 @@ const L_ROTATIONS: &Rotations = &Rotations([
         horizontal_offset: 0,
         width: 3,
         height: 2,
-        positions: [(0, 2), (1, 0), (1, 1), (1, 2)],
+        positions: Cow::Borrowed(&[(0, 2), (1, 0), (1, 1), (1, 2)]),
     },
     Rotation {
         vertical_offset: 0,
         horizontal_offset: 1,
         width: 2,
         height: 3,
-        positions: [(0, 1), (1, 1), (2, 1), (2, 2)],
+        positions: Cow::Borrowed(&[(0, 1), (1, 1), (2, 1), (2, 2)]),
     },
     Rotation {
         vertical_offset: 1,
         horizontal_offset: 0,
         width: 3,
         height: 2,
-        positions: [(1, 0), (1, 1), (1, 2), (2, 0)],
+        positions: Cow::Borrowed(&[(1, 0), (1, 1), (1, 2), (2, 0)]),
     },
     Rotation {
         vertical_offset: 0,
         horizontal_offset: 0,
         width: 2,
         height: 3,
-        positions: [(0, 0), (0, 1), (1, 1), (2, 1)],
+        positions: Cow::Borrowed(&[(0, 0), (0, 1), (1, 1), (2, 1)]),
     },
 ]);
 
@@ -345,28 +633,28 @@ const S_ROTATIONS: &Rotations = &Rotations([
         horizontal_offset: 0,
         width: 3,
         height: 2,
-        positions: [(0, 1), (0, 2), (1, 0), (1, 1)],
+        positions: Cow::Borrowed(&[(0, 1), (0, 2), (1, 0), (1, 1)]),
     },
     Rotation {
         vertical_offset: 0,
         horizontal_offset: 1,
         width: 2,
         height: 3,
-        positions: [(0, 1), (1, 1), (1, 2), (2, 2)],
+        positions: Cow::Borrowed(&[(0, 1), (1, 1), (1, 2), (2, 2)]),
     },
     Rotation {
         vertical_offset: 1,
         horizontal_offset: 0,
         width: 3,
         height: 2,
-        positions: [(1, 1), (1, 2), (2, 0), (2, 1)],
+        positions: Cow::Borrowed(&[(1, 1), (1, 2), (2, 0), (2, 1)]),
     },
     Rotation {
         vertical_offset: 0,
         horizontal_offset: 0,
         width: 2,
         height: 3,
-        positions: [(0, 0), (1, 0), (1, 1), (2, 1)],
+        positions: Cow::Borrowed(&[(0, 0), (1, 0), (1, 1), (2, 1)]),
     },
 ]);
 
@@ -382,28 +670,28 @@ const T_ROTATIONS: &Rotations = &Rotations([
         horizontal_offset: 0,
         width: 3,
         height: 2,
-        positions: [(0, 1), (1, 0), (1, 1), (1, 2)],
+        positions: Cow::Borrowed(&[(0, 1), (1, 0), (1, 1), (1, 2)]),
     },
     Rotation {
         vertical_offset: 0,
         horizontal_offset: 1,
         width: 2,
         height: 3,
-        positions: [(0, 1), (1, 1), (1, 2), (2, 1)],
+        positions: Cow::Borrowed(&[(0, 1), (1, 1), (1, 2), (2, 1)]),
     },
     Rotation {
         vertical_offset: 1,
         horizontal_offset: 0,
         width: 3,
         height: 2,
-        positions: [(1, 0), (1, 1), (1, 2), (2, 1)],
+        positions: Cow::Borrowed(&[(1, 0), (1, 1), (1, 2), (2, 1)]),
     },
     Rotation {
         vertical_offset: 0,
         horizontal_offset: 0,
         width: 2,
         height: 3,
-        positions: [(0, 1), (1, 0), (1, 1), (2, 1)],
+        positions: Cow::Borrowed(&[(0, 1), (1, 0), (1, 1), (2, 1)]),
     },
 ]);
 
@@ -419,33 +707,60 @@ const Z_ROTATIONS: &Rotations = &Rotations([
         horizontal_offset: 0,
         width: 3,
         height: 2,
-        positions: [(0, 0), (0, 1), (1, 1), (1, 2)],
+        positions: Cow::Borrowed(&[(0, 0), (0, 1), (1, 1), (1, 2)]),
     },
     Rotation {
         vertical_offset: 0,
         horizontal_offset: 1,
         width: 2,
         height: 3,
-        positions: [(0, 2), (1, 1), (1, 2), (2, 1)],
+        positions: Cow::Borrowed(&[(0, 2), (1, 1), (1, 2), (2, 1)]),
     },
     Rotation {
         vertical_offset: 1,
         horizontal_offset: 0,
         width: 3,
         height: 2,
-        positions: [(1, 0), (1, 1), (2, 1), (2, 2)],
+        positions: Cow::Borrowed(&[(1, 0), (1, 1), (2, 1), (2, 2)]),
     },
     Rotation {
         vertical_offset: 0,
         horizontal_offset: 0,
         width: 2,
         height: 3,
-        positions: [(0, 1), (1, 0), (1, 1), (2, 0)],
+        positions: Cow::Borrowed(&[(0, 1), (1, 0), (1, 1), (2, 0)]),
     },
 ]);
 
+/// The (row, column) offsets tried in order when rotating a block clockwise out of each rotation,
+/// following the Super Rotation System. Rows increase downwards. Counter-clockwise kicks out of
+/// rotation `n` are the negated clockwise kicks out of rotation `n - 1`.
+type KickTable = [[(isize, isize); 5]; 4];
+
+// J, L, S, T and Z kicks.
+#[rustfmt::skip]
+const JLSTZ_KICKS: &KickTable = &[
+    [(0, 0), (0, -1), (-1, -1), (2, 0), (2, -1)],
+    [(0, 0), (0, 1), (1, 1), (-2, 0), (-2, 1)],
+    [(0, 0), (0, 1), (-1, 1), (2, 0), (2, 1)],
+    [(0, 0), (0, -1), (1, -1), (-2, 0), (-2, -1)],
+];
+
+// I kicks.
+#[rustfmt::skip]
+const I_KICKS: &KickTable = &[
+    [(0, 0), (0, -2), (0, 1), (1, -2), (-2, 1)],
+    [(0, 0), (0, -1), (0, 2), (-2, -1), (1, 2)],
+    [(0, 0), (0, 2), (0, -1), (-1, 2), (2, -1)],
+    [(0, 0), (0, 1), (0, -2), (2, 1), (-1, -2)],
+];
+
+// O blocks never kick.
+const O_KICKS: &KickTable = &[[(0, 0); 5]; 4];
+
 /// The block currently under the player's control.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ActiveBlock {
     // The row-column coordinates of the top-left corner of the block's virtual bounding box on the
     // board.
@@ -453,16 +768,52 @@ pub struct ActiveBlock {
     // The column coordinate of the box is allowed to be negative since it may leave the left bounds
     // of the board while all of the block's cells remain inbounds (either vertical alignment of an
     // I block, for example).
-    top_left: (usize, isize),
+    top_left: TopLeft,
     block_type: BlockType,
     rotation_idx: RotationIndex,
+
+    // The shape of a custom piece, which isn't saved with the block and must be restored from the
+    // registry it came from with [ActiveBlock::registered_in].
+    #[cfg_attr(feature = "serde", serde(skip))]
+    custom: Option<Arc<CustomPiece>>,
 }
 
 impl ActiveBlock {
-    pub fn new(block_type: BlockType) -> Self {
+    /// Instantiates a standard block in its spawn row and rotation. Blocks of any type, including
+    /// custom pieces, are spawned with [Self::new_in].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `block_type` is a custom piece, whose shape is known only to the generator that
+    /// registered it.
+    pub(crate) fn new(block_type: BlockType) -> Self {
+        Self::spawned(block_type, None)
+    }
+
+    /// Instantiates a block of any type registered in `custom_pieces` in its spawn row and
+    /// rotation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `block_type` is a custom piece that wasn't registered in `custom_pieces`.
+    pub(crate) fn new_in(block_type: BlockType, custom_pieces: &CustomPieces) -> Self {
+        match custom_pieces.get(block_type) {
+            Some(piece) => Self::spawned(block_type, Some(Arc::clone(piece))),
+            None => Self::new(block_type),
+        }
+    }
+
+    fn spawned(block_type: BlockType, custom: Option<Arc<CustomPiece>>) -> Self {
         let rotation_idx = RotationIndex::new();
-        let rotation = &block_type[rotation_idx];
+        let rotation = match &custom {
+            Some(piece) => &piece.rotations[rotation_idx],
+            None => block_type
+                .rotation(rotation_idx)
+                .unwrap_or_else(|| panic!("{block_type:?} has no built-in rotations")),
+        };
 
+        // Spawning isn't generic over board size: blocks only ever spawn onto a Game's
+        // StandardBoard, and custom pieces are checked against its width when they're built.
         let height = rotation.height();
         debug_assert!(
             height <= 2,
@@ -472,40 +823,81 @@ impl ActiveBlock {
 
         let width = rotation.width();
         debug_assert!(
-            width <= Board::COLUMNS,
+            width <= StandardBoard::COLUMNS,
             "Block width {} exceeds board width {}",
             width,
-            Board::COLUMNS,
+            StandardBoard::COLUMNS,
         );
 
         // Place the bounding box so that the block lands at the bottom of the buffer zone.
-        let r = Board::BUFFER_ZONE_ROWS - rotation.vertical_offset() - height;
+        let r = StandardBoard::BUFFER_ZONE_ROWS - rotation.vertical_offset() - height;
 
         // The initial column coordinate places the block approximately in the center of the board.
         //
         // For example, on a standard 10-column board, the I block's leftmost cell falls in row[3],
         // while the O and S blocks' fall in row[4]. This gives a one-cell rightwards bias to
         // three-cell-wide blocks.
-        let c = Board::COLUMNS / 2 - rotation.horizontal_offset() - width / 2;
+        let c = StandardBoard::COLUMNS / 2 - rotation.horizontal_offset() - width / 2;
 
         Self {
             top_left: (r, c as isize),
             block_type,
             rotation_idx,
+            custom,
         }
     }
 
+    /// Returns the block with the shape of its custom piece restored from `custom_pieces`, as
+    /// required for deserialized blocks. Returns an error if the block is a custom piece that
+    /// wasn't registered there.
+    pub(crate) fn registered_in(self, custom_pieces: &CustomPieces) -> Result<Self, String> {
+        let block_type = custom_pieces.check(self.block_type)?;
+        Ok(Self {
+            custom: custom_pieces.get(block_type).cloned(),
+            ..self
+        })
+    }
+
+    /// Instantiates a block of the given type with its bounding box at `top_left` and the given
+    /// number of clockwise quarter turns from its spawn rotation, in the range 0..4.
+    #[cfg(test)]
+    pub(crate) fn from_parts(
+        block_type: BlockType,
+        top_left: TopLeft,
+        rotation_index: usize,
+    ) -> Result<Self, String> {
+        Ok(Self {
+            top_left,
+            block_type,
+            rotation_idx: RotationIndex::try_from(rotation_index)?,
+            custom: None,
+        })
+    }
+
+    /// Returns the number of clockwise quarter turns of the block from its spawn rotation.
+    pub fn rotation_index(&self) -> RotationIndex {
+        self.rotation_idx
+    }
+
     pub(crate) fn block_type(&self) -> BlockType {
         self.block_type
     }
 
-    // Returns the board-space coordinates of the top-left cell of the ActiveBlock.
-    fn top_left(&self) -> (usize, isize) {
+    /// Returns the board-space coordinates of the top-left cell of the ActiveBlock.
+    pub fn top_left(&self) -> TopLeft {
         self.top_left
     }
 
     fn rotation(&self) -> &Rotation {
-        &self.block_type[self.rotation_idx]
+        match &self.custom {
+            Some(piece) => &piece.rotations[self.rotation_idx],
+            // Custom blocks are only without their piece when deserialized, and are restored with
+            // registered_in before use.
+            None => self
+                .block_type
+                .rotation(self.rotation_idx)
+                .unwrap_or_else(|| panic!("{:?} has no built-in rotations", self.block_type)),
+        }
     }
 
     /// Returns an iterator of the positions of the block's cells in board space in order of
@@ -546,9 +938,42 @@ impl ActiveBlock {
         self.rotation_idx.dec();
     }
 
+    /// Sets the block's rotation to `rotation` clockwise quarter turns from rotation 0, wrapping
+    /// values outside the range 0..4.
+    pub fn set_rotation(&mut self, rotation: isize) {
+        self.rotation_idx = RotationIndex::wrapping(rotation);
+    }
+
+    /// Returns the (row, column) offsets to try, in order, when rotating the block clockwise from
+    /// its current rotation.
+    pub(crate) fn clockwise_kicks(&self) -> [(isize, isize); 5] {
+        self.block_type.kicks()[self.rotation_idx.0]
+    }
+
+    /// Returns the (row, column) offsets to try, in order, when rotating the block
+    /// counter-clockwise from its current rotation.
+    pub(crate) fn counter_clockwise_kicks(&self) -> [(isize, isize); 5] {
+        let mut from = self.rotation_idx;
+        from.dec();
+        self.block_type.kicks()[from.0].map(|(r, c)| (-r, -c))
+    }
+
+    /// Returns a copy of the block translated by the given (row, column) offset, or None if the
+    /// block would be moved above row 0.
+    pub(crate) fn translated(&self, (dr, dc): (isize, isize)) -> Option<Self> {
+        let r = self.top_left.0.checked_add_signed(dr)?;
+        Some(Self {
+            top_left: (r, self.top_left.1.saturating_add(dc)),
+            ..self.clone()
+        })
+    }
+
     /// Returns a grid cell coloured according to the [BlockType].
     pub fn grid_cell(&self) -> Span<'static> {
-        self.block_type.grid_cell()
+        match &self.custom {
+            Some(piece) => colorize("██", piece.color),
+            None => self.block_type.grid_cell(),
+        }
     }
 }
 
@@ -556,20 +981,67 @@ impl ActiveBlock {
 mod block_type_tests {
     use super::*;
 
+    mod kick_class_tests {
+        use super::*;
+
+        #[test]
+        fn returns_class_for_each_block_type() {
+            assert_eq!(I.kick_class(), KickClass::I);
+            assert_eq!(O.kick_class(), KickClass::O);
+            for block_type in [J, L, S, T, Z, Custom(0)] {
+                assert_eq!(
+                    block_type.kick_class(),
+                    KickClass::Jlstz,
+                    "{:?}",
+                    block_type
+                );
+            }
+        }
+    }
+
+    mod color_tests {
+        use super::*;
+
+        #[test]
+        fn standard_block_types_use_guideline_colors() {
+            let colors = [I, J, L, O, S, T, Z].map(|block_type| block_type.color());
+            assert_eq!(
+                colors,
+                [
+                    (0, 255, 255),
+                    (0, 0, 255),
+                    (255, 127, 0),
+                    (255, 255, 0),
+                    (0, 255, 0),
+                    (128, 0, 128),
+                    (255, 0, 0),
+                ]
+            );
+        }
+
+        #[test]
+        fn standard_block_types_have_distinct_colors() {
+            let colors = [I, J, L, O, S, T, Z].map(|block_type| block_type.color());
+            for (i, color) in colors.iter().enumerate() {
+                assert!(!colors[i + 1..].contains(color), "{color:?} is repeated");
+            }
+        }
+    }
+
     mod index_tests {
         use super::*;
 
         #[test]
         fn when_rotation_index_is_0_returns_rotation_0() {
             let idx = RotationIndex::new();
-            assert_eq!(I[idx], I_ROTATIONS.0[0]);
+            assert_eq!(I.rotation(idx), Some(&I_ROTATIONS.0[0]));
         }
 
         #[test]
         fn when_rotation_index_is_1_returns_rotation_1() {
             let mut idx = RotationIndex::new();
             idx.inc();
-            assert_eq!(I[idx], I_ROTATIONS.0[1]);
+            assert_eq!(I.rotation(idx), Some(&I_ROTATIONS.0[1]));
         }
 
         #[test]
@@ -577,7 +1049,7 @@ mod block_type_tests {
             let mut idx = RotationIndex::new();
             idx.inc();
             idx.inc();
-            assert_eq!(I[idx], I_ROTATIONS.0[2]);
+            assert_eq!(I.rotation(idx), Some(&I_ROTATIONS.0[2]));
         }
 
         #[test]
@@ -586,46 +1058,251 @@ mod block_type_tests {
             idx.inc();
             idx.inc();
             idx.inc();
-            assert_eq!(I[idx], I_ROTATIONS.0[3]);
+            assert_eq!(I.rotation(idx), Some(&I_ROTATIONS.0[3]));
         }
     }
 
     mod rotations_tests {
         use super::*;
 
+        #[test]
+        fn o_rotations_are_identical() {
+            let mut idx = RotationIndex::new();
+            for _ in 0..4 {
+                idx.inc();
+                assert_eq!(O.rotation(idx), O.rotation(RotationIndex::new()));
+            }
+        }
+
         #[test]
         fn i_returns_i_rotations() {
-            assert_eq!(I.rotations(), I_ROTATIONS);
+            assert_eq!(I.rotations(), Some(I_ROTATIONS));
         }
 
         #[test]
         fn j_returns_j_rotations() {
-            assert_eq!(J.rotations(), J_ROTATIONS);
+            assert_eq!(J.rotations(), Some(J_ROTATIONS));
         }
 
         #[test]
         fn l_returns_l_rotations() {
-            assert_eq!(L.rotations(), L_ROTATIONS);
+            assert_eq!(L.rotations(), Some(L_ROTATIONS));
         }
 
         #[test]
         fn o_returns_o_rotations() {
-            assert_eq!(O.rotations(), O_ROTATIONS);
+            assert_eq!(O.rotations(), Some(O_ROTATIONS));
         }
 
         #[test]
         fn s_returns_s_rotations() {
-            assert_eq!(S.rotations(), S_ROTATIONS);
+            assert_eq!(S.rotations(), Some(S_ROTATIONS));
         }
 
         #[test]
         fn t_returns_t_rotations() {
-            assert_eq!(T.rotations(), T_ROTATIONS);
+            assert_eq!(T.rotations(), Some(T_ROTATIONS));
         }
 
         #[test]
         fn z_returns_z_rotations() {
-            assert_eq!(Z.rotations(), Z_ROTATIONS);
+            assert_eq!(Z.rotations(), Some(Z_ROTATIONS));
+        }
+
+        #[test]
+        fn when_block_type_is_custom_returns_none() {
+            assert_eq!(Custom(0).rotations(), None);
+            assert_eq!(Custom(0).rotation(RotationIndex::new()), None);
+        }
+    }
+
+    mod rotations_get_tests {
+        use super::*;
+
+        #[test]
+        fn when_index_is_valid_returns_some() {
+            let index = RotationIndex::try_from(2).unwrap();
+            assert_eq!(T.rotations().unwrap().get(index), Some(&T_ROTATIONS.0[2]));
+        }
+
+        #[test]
+        fn when_index_is_out_of_range_returns_none() {
+            assert_eq!(T.rotations().unwrap().get(RotationIndex(4)), None);
+        }
+    }
+}
+
+#[cfg(test)]
+mod custom_piece_tests {
+    use super::*;
+
+    mod new_tests {
+        use super::*;
+
+        fn rotations() -> [Vec<Position>; 4] {
+            [
+                vec![(1, 0), (1, 1), (1, 2)],
+                vec![(0, 1), (1, 1), (2, 1)],
+                vec![(1, 0), (1, 1), (1, 2)],
+                vec![(0, 1), (1, 1), (2, 1)],
+            ]
+        }
+
+        #[test]
+        fn when_piece_is_valid_returns_ok() {
+            let piece = CustomPiece::new(rotations(), (1, 2, 3)).unwrap();
+            assert_eq!(piece.color, (1, 2, 3));
+            assert_eq!(piece.schematic, "██████");
+        }
+
+        #[test]
+        fn when_a_rotation_is_empty_returns_err() {
+            let mut rotations = rotations();
+            rotations[3] = vec![];
+            assert!(CustomPiece::new(rotations, (1, 2, 3)).is_err());
+        }
+
+        #[test]
+        fn when_spawn_rotation_is_too_far_right_returns_err() {
+            let mut rotations = rotations();
+            rotations[0] = vec![(1, 6), (1, 7), (1, 8)];
+            assert!(CustomPiece::new(rotations, (1, 2, 3)).is_err());
+        }
+    }
+}
+
+#[cfg(test)]
+mod custom_pieces_tests {
+    use super::*;
+
+    fn domino() -> CustomPiece {
+        CustomPiece::new(
+            [
+                vec![(1, 0), (1, 1)],
+                vec![(0, 1), (1, 1)],
+                vec![(1, 0), (1, 1)],
+                vec![(0, 1), (1, 1)],
+            ],
+            (1, 2, 3),
+        )
+        .unwrap()
+    }
+
+    mod register_tests {
+        use super::*;
+
+        #[test]
+        fn issues_ids_in_registration_order() {
+            let mut pieces = CustomPieces::default();
+            assert_eq!(pieces.register(domino()), Ok(Custom(0)));
+            assert_eq!(pieces.register(domino()), Ok(Custom(1)));
+        }
+
+        #[test]
+        fn when_registry_is_full_returns_err() {
+            let mut pieces = CustomPieces::default();
+            for _ in 0..BlockType::MAX_CUSTOM {
+                pieces.register(domino()).unwrap();
+            }
+            assert!(pieces.register(domino()).is_err());
+        }
+    }
+
+    mod check_tests {
+        use super::*;
+
+        #[test]
+        fn when_block_type_is_standard_returns_it() {
+            assert_eq!(CustomPieces::default().check(T), Ok(T));
+        }
+
+        #[test]
+        fn when_custom_piece_is_registered_returns_it() {
+            let mut pieces = CustomPieces::default();
+            let block_type = pieces.register(domino()).unwrap();
+            assert_eq!(pieces.check(block_type), Ok(block_type));
+        }
+
+        #[test]
+        fn when_custom_piece_is_registered_elsewhere_returns_err() {
+            let mut elsewhere = CustomPieces::default();
+            let block_type = elsewhere.register(domino()).unwrap();
+            assert!(CustomPieces::default().check(block_type).is_err());
+        }
+    }
+
+    mod color_tests {
+        use super::*;
+
+        #[test]
+        fn when_block_type_is_standard_returns_its_guideline_color() {
+            assert_eq!(CustomPieces::default().color(T), T.color());
+        }
+
+        #[test]
+        fn when_custom_piece_is_registered_returns_its_color() {
+            let mut pieces = CustomPieces::default();
+            let block_type = pieces.register(domino()).unwrap();
+            assert_eq!(pieces.color(block_type), (1, 2, 3));
+        }
+    }
+}
+
+#[cfg(test)]
+mod rotation_tests {
+    use super::*;
+
+    mod from_positions_tests {
+        use super::*;
+
+        #[test]
+        fn when_positions_are_empty_returns_none() {
+            assert_eq!(Rotation::from_positions(vec![]), None);
+        }
+
+        #[test]
+        fn derives_the_same_rotations_as_the_standard_block_types() {
+            for block_type in [I, J, L, O, S, T, Z] {
+                for rotation in &block_type.rotations().unwrap().0 {
+                    let derived = Rotation::from_positions(rotation.positions().copied().collect());
+                    assert_eq!(derived.as_ref(), Some(rotation), "{:?}", block_type);
+                }
+            }
+        }
+    }
+
+    mod dimensions_tests {
+        use super::*;
+
+        /// Returns the (height, width) of each rotation of the block type.
+        fn dimensions(block_type: BlockType) -> Vec<(usize, usize)> {
+            block_type
+                .rotations()
+                .unwrap()
+                .0
+                .iter()
+                .map(|rotation| (rotation.height(), rotation.width()))
+                .collect()
+        }
+
+        #[test]
+        fn when_block_is_i_alternates_between_horizontal_and_vertical() {
+            assert_eq!(dimensions(I), vec![(1, 4), (4, 1), (1, 4), (4, 1)]);
+        }
+
+        #[test]
+        fn when_block_is_j_alternates_between_wide_and_tall() {
+            assert_eq!(dimensions(J), vec![(2, 3), (3, 2), (2, 3), (3, 2)]);
+        }
+    }
+
+    mod schematic_tests {
+        use super::*;
+
+        #[test]
+        fn draws_occupied_cells_within_bounding_box() {
+            let rotation = T.rotation(RotationIndex::new()).unwrap();
+            assert_eq!(rotation.schematic(), "  ██\n██████");
         }
     }
 }
@@ -645,6 +1322,7 @@ mod active_block_tests {
                     top_left: (0, 3),
                     block_type: BlockType::I,
                     rotation_idx: RotationIndex::new(),
+                    custom: None,
                 }
             );
         }
@@ -657,6 +1335,7 @@ mod active_block_tests {
                     top_left: (0, 4),
                     block_type: BlockType::J,
                     rotation_idx: RotationIndex::new(),
+                    custom: None,
                 }
             );
         }
@@ -669,6 +1348,7 @@ mod active_block_tests {
                     top_left: (0, 4),
                     block_type: BlockType::L,
                     rotation_idx: RotationIndex::new(),
+                    custom: None,
                 }
             );
         }
@@ -681,6 +1361,7 @@ mod active_block_tests {
                     top_left: (0, 4),
                     block_type: BlockType::O,
                     rotation_idx: RotationIndex::new(),
+                    custom: None,
                 }
             );
         }
@@ -693,6 +1374,7 @@ mod active_block_tests {
                     top_left: (0, 4),
                     block_type: BlockType::S,
                     rotation_idx: RotationIndex::new(),
+                    custom: None,
                 }
             );
         }
@@ -705,6 +1387,7 @@ mod active_block_tests {
                     top_left: (0, 4),
                     block_type: BlockType::T,
                     rotation_idx: RotationIndex::new(),
+                    custom: None,
                 }
             );
         }
@@ -717,6 +1400,7 @@ mod active_block_tests {
                     top_left: (0, 4),
                     block_type: BlockType::Z,
                     rotation_idx: RotationIndex::new(),
+                    custom: None,
                 }
             );
         }
@@ -738,12 +1422,148 @@ mod active_block_tests {
                 top_left: (0, -1),
                 block_type: BlockType::I,
                 rotation_idx: RotationIndex::new(),
+                custom: None,
             };
             let positions: Vec<Position> = block.board_positions().collect();
-            assert_eq!(
-                positions,
-                vec![(1, usize::MAX), (1, 0), (1, 1), (1, 2)]
-            );
+            assert_eq!(positions, vec![(1, usize::MAX), (1, 0), (1, 1), (1, 2)]);
+        }
+    }
+
+    mod rotation_tests {
+        use super::*;
+
+        #[test]
+        fn returns_the_block_types_rotation_at_the_current_index() {
+            for block_type in [I, J, L, O, S, T, Z] {
+                let mut block = ActiveBlock::new(block_type);
+                for i in 0..4 {
+                    assert_eq!(
+                        block.rotation(),
+                        &block_type.rotations().unwrap().0[i],
+                        "{block_type:?} rotation {i}"
+                    );
+                    block.rotate_clockwise();
+                }
+            }
+        }
+
+        #[test]
+        fn board_positions_span_the_rotations_width_and_height() {
+            for block_type in [I, J, L, O, S, T, Z] {
+                let mut block = ActiveBlock::new(block_type);
+                for i in 0..4 {
+                    let rows: Vec<usize> = block.board_positions().map(|(r, _)| r).collect();
+                    let cols: Vec<usize> = block.board_positions().map(|(_, c)| c).collect();
+                    let height = rows.iter().max().unwrap() - rows.iter().min().unwrap() + 1;
+                    let width = cols.iter().max().unwrap() - cols.iter().min().unwrap() + 1;
+                    assert_eq!(
+                        (height, width),
+                        (block.rotation().height(), block.rotation().width()),
+                        "{block_type:?} rotation {i}"
+                    );
+                    block.rotate_clockwise();
+                }
+            }
+        }
+    }
+
+    mod rotate_clockwise_tests {
+        use super::*;
+
+        #[test]
+        fn when_block_is_o_board_positions_are_unchanged() {
+            let mut block = ActiveBlock::new(BlockType::O);
+            let before: Vec<Position> = block.board_positions().collect();
+            for _ in 0..4 {
+                block.rotate_clockwise();
+                assert_eq!(block.board_positions().collect::<Vec<_>>(), before);
+            }
+        }
+
+        fn assert_four_distinct_orientations(block_type: BlockType) {
+            let mut block = ActiveBlock::new(block_type);
+            let mut orientations: Vec<Vec<Position>> = Vec::new();
+            for _ in 0..4 {
+                orientations.push(block.board_positions().collect());
+                block.rotate_clockwise();
+            }
+
+            for (i, orientation) in orientations.iter().enumerate() {
+                assert!(
+                    !orientations[i + 1..].contains(orientation),
+                    "{block_type:?} rotation {i} repeats a later rotation"
+                );
+            }
+            assert_eq!(block.board_positions().collect::<Vec<_>>(), orientations[0]);
+        }
+
+        #[test]
+        fn when_block_is_s_cycles_through_four_distinct_orientations() {
+            assert_four_distinct_orientations(BlockType::S);
+        }
+
+        #[test]
+        fn when_block_is_z_cycles_through_four_distinct_orientations() {
+            assert_four_distinct_orientations(BlockType::Z);
+        }
+
+        #[test]
+        fn when_block_is_l_cycles_through_four_distinct_orientations() {
+            assert_four_distinct_orientations(BlockType::L);
+        }
+
+        #[test]
+        fn when_block_is_t_cycles_through_four_distinct_orientations() {
+            assert_four_distinct_orientations(BlockType::T);
+        }
+    }
+
+    mod rotation_index_tests {
+        use super::*;
+
+        #[test]
+        fn when_block_is_new_returns_0() {
+            assert_eq!(usize::from(ActiveBlock::new(T).rotation_index()), 0);
+        }
+
+        #[test]
+        fn when_rotated_clockwise_four_times_returns_to_starting_index() {
+            let mut block = ActiveBlock::new(T);
+            let start = block.rotation_index();
+            for expected in [1, 2, 3, 0] {
+                block.rotate_clockwise();
+                assert_eq!(usize::from(block.rotation_index()), expected);
+            }
+            assert_eq!(block.rotation_index(), start);
+        }
+
+        #[test]
+        fn when_rotated_counter_clockwise_four_times_returns_to_starting_index() {
+            let mut block = ActiveBlock::new(T);
+            let start = block.rotation_index();
+            for expected in [3, 2, 1, 0] {
+                block.rotate_counter_clockwise();
+                assert_eq!(usize::from(block.rotation_index()), expected);
+            }
+            assert_eq!(block.rotation_index(), start);
+        }
+    }
+
+    mod set_rotation_tests {
+        use super::*;
+
+        #[test]
+        fn when_rotation_is_negative_wraps_counter_clockwise() {
+            let mut block = ActiveBlock::new(BlockType::T);
+            block.set_rotation(-1);
+            assert_eq!(block.rotation_idx, RotationIndex(3));
+        }
+
+        #[test]
+        fn when_rotation_exceeds_3_wraps_clockwise() {
+            let mut block = ActiveBlock::new(BlockType::T);
+            block.set_rotation(5);
+            assert_eq!(block.rotation_idx, RotationIndex(1));
         }
     }
 
@@ -756,6 +1576,7 @@ mod active_block_tests {
                 top_left: (0, 0),
                 block_type: BlockType::I,
                 rotation_idx: RotationIndex::new(),
+                custom: None,
             };
             block.move_down();
             assert_eq!(block.top_left, (1, 0));
@@ -767,6 +1588,7 @@ mod active_block_tests {
                 top_left: (usize::MAX, 0),
                 block_type: BlockType::I,
                 rotation_idx: RotationIndex::new(),
+                custom: None,
             };
             block.move_down();
             assert_eq!(block.top_left, (usize::MAX, 0));
@@ -782,6 +1604,7 @@ mod active_block_tests {
                 top_left: (1, 0),
                 block_type: BlockType::I,
                 rotation_idx: RotationIndex::new(),
+                custom: None,
             };
             block.move_up();
             assert_eq!(block.top_left, (0, 0));
@@ -793,6 +1616,7 @@ mod active_block_tests {
                 top_left: (0, 0),
                 block_type: BlockType::I,
                 rotation_idx: RotationIndex::new(),
+                custom: None,
             };
             block.move_up();
             assert_eq!(block.top_left, (0, 0));
@@ -808,6 +1632,7 @@ mod active_block_tests {
                 top_left: (0, 0),
                 block_type: BlockType::I,
                 rotation_idx: RotationIndex::new(),
+                custom: None,
             };
             block.move_left();
             assert_eq!(block.top_left, (0, -1));
@@ -819,6 +1644,7 @@ mod active_block_tests {
                 top_left: (0, isize::MIN),
                 block_type: BlockType::I,
                 rotation_idx: RotationIndex::new(),
+                custom: None,
             };
             block.move_left();
             assert_eq!(block.top_left, (0, isize::MIN));
@@ -834,6 +1660,7 @@ mod active_block_tests {
                 top_left: (0, 0),
                 block_type: BlockType::I,
                 rotation_idx: RotationIndex::new(),
+                custom: None,
             };
             block.move_right();
             assert_eq!(block.top_left, (0, 1));
@@ -845,6 +1672,7 @@ mod active_block_tests {
                 top_left: (0, isize::MAX),
                 block_type: BlockType::I,
                 rotation_idx: RotationIndex::new(),
+                custom: None,
             };
             block.move_right();
             assert_eq!(block.top_left, (0, isize::MAX));
@@ -852,6 +1680,45 @@ mod active_block_tests {
     }
 }
 
+#[cfg(test)]
+mod kick_tests {
+    use super::*;
+
+    mod counter_clockwise_kicks_tests {
+        use super::*;
+
+        #[test]
+        fn reverses_clockwise_kicks_into_the_same_rotation() {
+            for block_type in [I, J, T] {
+                let mut block = ActiveBlock::new(block_type);
+                for _ in 0..4 {
+                    let clockwise = block.clockwise_kicks();
+                    block.rotate_clockwise();
+                    let reversed = block.counter_clockwise_kicks().map(|(r, c)| (-r, -c));
+                    assert_eq!(clockwise, reversed, "{:?}", block_type);
+                }
+            }
+        }
+    }
+
+    mod translated_tests {
+        use super::*;
+
+        #[test]
+        fn offsets_top_left() {
+            let block = ActiveBlock::new(T);
+            let translated = block.translated((2, -1)).unwrap();
+            assert_eq!(translated.top_left(), (2, 3));
+        }
+
+        #[test]
+        fn when_offset_moves_block_above_row_0_returns_none() {
+            let block = ActiveBlock::new(T);
+            assert_eq!(block.translated((-1, 0)), None);
+        }
+    }
+}
+
 #[cfg(test)]
 mod rotation_index_tests {
     use super::*;
@@ -874,6 +1741,43 @@ mod rotation_index_tests {
         }
     }
 
+    mod wrapping_tests {
+        use super::*;
+
+        #[test]
+        fn when_value_is_in_range_returns_value() {
+            for i in 0..4 {
+                assert_eq!(RotationIndex::wrapping(i), RotationIndex(i as usize));
+            }
+        }
+
+        #[test]
+        fn when_value_is_negative_wraps_from_3() {
+            assert_eq!(RotationIndex::wrapping(-1), RotationIndex(3));
+            assert_eq!(RotationIndex::wrapping(-6), RotationIndex(2));
+        }
+
+        #[test]
+        fn when_value_is_4_or_more_wraps_from_0() {
+            assert_eq!(RotationIndex::wrapping(5), RotationIndex(1));
+            assert_eq!(RotationIndex::wrapping(isize::MAX), RotationIndex(3));
+        }
+    }
+
+    mod try_from_tests {
+        use super::*;
+
+        #[test]
+        fn when_value_is_less_than_4_returns_ok() {
+            assert_eq!(RotationIndex::try_from(3), Ok(RotationIndex(3)));
+        }
+
+        #[test]
+        fn when_value_is_4_or_more_returns_err() {
+            assert!(RotationIndex::try_from(4).is_err());
+        }
+    }
+
     mod dec_tests {
         use super::*;
 
@@ -890,5 +1794,30 @@ mod rotation_index_tests {
             idx.dec();
             assert_eq!(idx, RotationIndex(3));
         }
+
+        #[test]
+        fn when_decremented_repeatedly_stays_in_range_and_cycles() {
+            let mut idx = RotationIndex::new();
+            for expected in [3, 2, 1, 0, 3, 2, 1, 0] {
+                idx.dec();
+                assert_eq!(idx, RotationIndex(expected));
+            }
+        }
+
+        #[test]
+        fn when_incremented_across_the_boundary_dec_undoes_inc() {
+            for start in 0..4 {
+                let mut idx = RotationIndex(start);
+                idx.inc();
+                assert!(idx.0 < 4);
+                idx.dec();
+                assert_eq!(idx, RotationIndex(start));
+
+                idx.dec();
+                assert!(idx.0 < 4);
+                idx.inc();
+                assert_eq!(idx, RotationIndex(start));
+            }
+        }
     }
 }
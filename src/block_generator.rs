@@ -1,26 +1,147 @@
-use rand::rngs::ThreadRng;
+use std::cell::RefCell;
+use std::collections::VecDeque;
+
+use rand::{Rng, SeedableRng, rngs::StdRng, seq::SliceRandom};
 use rand_distr::{Distribution, Uniform};
 
-use crate::block::BlockType;
+use crate::block::{BlockType, CustomPiece, CustomPieces, Position};
+
+/// The definition of a custom piece, such as a pentomino, that can be registered with a
+/// [BlockGenerator].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PieceDef {
+    /// The positions occupied by each of the piece's four rotations in its local coordinate
+    /// space, in clockwise order, starting from the spawn rotation.
+    pub rotations: [Vec<Position>; 4],
+
+    /// The RGB color of the piece.
+    pub color: (u8, u8, u8),
+}
 
 /// Randomly generates new blocks based on the supplied RNG.
+///
+/// Generators created with the same seed, e.g. by [BlockGenerator::seeded], produce identical
+/// sequences of blocks.
 #[derive(Debug, Clone)]
 pub struct BlockGenerator<S> {
-    rng: ThreadRng,
+    rng: StdRng,
     sampler: S,
+
+    // Custom pieces that may be generated in addition to the standard block types.
+    custom: CustomPieces,
+
+    // Blocks generated ahead of time by peeking, which are dealt before any new blocks.
+    peeked: VecDeque<BlockType>,
 }
 
 impl BlockGenerator<Uniform<u8>> {
     pub fn new() -> Self {
-        let sampler = Uniform::new_inclusive(1, BlockType::COUNT)
-            .unwrap_or_else(|_| panic!("uniform sampler was invalid for 1..={}", BlockType::COUNT));
-        Self { rng: rand::rng(), sampler }
+        Self::with_rng(StdRng::from_rng(&mut rand::rng()), Self::uniform_sampler(0))
+    }
+
+    /// Instantiates a generator whose sequence of blocks is determined entirely by `seed`, for
+    /// reproducible games and replays.
+    pub fn seeded(seed: u64) -> Self {
+        Self::with_rng(StdRng::seed_from_u64(seed), Self::uniform_sampler(0))
+    }
+
+    /// Registers a custom piece, which will be generated with the same likelihood as each of the
+    /// standard block types. Returns the [BlockType] that refers to the piece.
+    ///
+    /// Registered pieces belong to this generator, and to clones of it, so a custom [BlockType]
+    /// only refers to the same piece in games played with them.
+    pub fn register(&mut self, def: PieceDef) -> Result<BlockType, String> {
+        let block_type = self
+            .custom
+            .register(CustomPiece::new(def.rotations, def.color)?)?;
+        self.sampler = Self::uniform_sampler(self.custom.len());
+        Ok(block_type)
+    }
+
+    fn uniform_sampler(custom_count: usize) -> Uniform<u8> {
+        let max = BlockType::COUNT + custom_count as u8;
+        Uniform::new_inclusive(1, max)
+            .unwrap_or_else(|_| panic!("uniform sampler was invalid for 1..={}", max))
+    }
+}
+
+impl Default for BlockGenerator<Uniform<u8>> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A sampler that deals each of the seven standard block types once, in a random order, before
+/// reshuffling. This bounds droughts: no block type is ever more than 12 blocks apart.
+#[derive(Debug, Default, Clone)]
+pub struct Bag(RefCell<Vec<u8>>);
+
+impl Distribution<u8> for Bag {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> u8 {
+        let mut bag = self.0.borrow_mut();
+        if bag.is_empty() {
+            bag.extend(1..=BlockType::COUNT);
+            bag.shuffle(rng);
+        }
+        bag.pop().expect("bag should have been refilled")
+    }
+}
+
+impl BlockGenerator<Bag> {
+    /// Instantiates a generator that deals the standard block types from a shuffled 7-bag.
+    pub fn new_bag() -> Self {
+        Self::with_rng(StdRng::from_rng(&mut rand::rng()), Bag::default())
+    }
+
+    /// Instantiates a 7-bag generator whose shuffles are determined entirely by `seed`.
+    pub fn seeded_bag(seed: u64) -> Self {
+        Self::with_rng(StdRng::seed_from_u64(seed), Bag::default())
+    }
+}
+
+impl<S> BlockGenerator<S> {
+    fn with_rng(rng: StdRng, sampler: S) -> Self {
+        Self {
+            rng,
+            sampler,
+            custom: CustomPieces::default(),
+            peeked: VecDeque::new(),
+        }
+    }
+
+    /// Returns the custom pieces registered with the generator.
+    pub(crate) fn custom_pieces(&self) -> &CustomPieces {
+        &self.custom
+    }
+
+    /// Returns a random column index in the range `0..columns`, drawn from the generator's RNG so
+    /// that seeded games receive the same garbage every time they're played. Blocks generated
+    /// afterwards differ from those that would have been generated had the column not been drawn.
+    pub(crate) fn random_column(&mut self, columns: usize) -> usize {
+        self.rng.random_range(0..columns)
     }
 }
 
 impl<S: Distribution<u8>> BlockGenerator<S> {
     /// Generate a new block.
     pub fn block(&mut self) -> BlockType {
+        self.peeked.pop_front().unwrap_or_else(|| self.generate())
+    }
+
+    /// Returns the next `n` blocks without dealing them, so that previews exactly match the blocks
+    /// later returned by [Self::block], even across the boundary between two bags. Blocks are
+    /// generated as needed and kept until dealt, so pieces registered afterwards can only appear
+    /// after them.
+    pub fn peek(&mut self, n: usize) -> &[BlockType] {
+        while self.peeked.len() < n {
+            let block_type = self.generate();
+            self.peeked.push_back(block_type);
+        }
+        &self.peeked.make_contiguous()[..n]
+    }
+
+    /// Samples a new block from the generator's RNG.
+    fn generate(&mut self) -> BlockType {
         match self.sampler.sample(&mut self.rng) {
             1 => BlockType::I,
             2 => BlockType::J,
@@ -29,17 +150,25 @@ impl<S: Distribution<u8>> BlockGenerator<S> {
             5 => BlockType::S,
             6 => BlockType::T,
             7 => BlockType::Z,
-            i => unreachable!(
-                "Only {} block types are implemented, but sampler returned {}",
-                BlockType::COUNT,
-                i
-            ),
+            i => i
+                .checked_sub(BlockType::COUNT + 1)
+                .filter(|&j| usize::from(j) < self.custom.len())
+                .map(BlockType::Custom)
+                .unwrap_or_else(|| {
+                    unreachable!(
+                        "Only {} block types are registered, but sampler returned {}",
+                        BlockType::COUNT as usize + self.custom.len(),
+                        i
+                    )
+                }),
         }
     }
 }
 
 #[cfg(test)]
 pub(crate) mod test_helpers {
+    use std::cell::Cell;
+
     use rand::Rng;
     use rand_distr::Distribution;
 
@@ -53,9 +182,35 @@ pub(crate) mod test_helpers {
         }
     }
 
+    /// A sampler that cycles through a fixed sequence of values.
+    pub(crate) struct SequenceSampler {
+        values: Vec<u8>,
+        next: Cell<usize>,
+    }
+
+    impl Distribution<u8> for SequenceSampler {
+        fn sample<R: Rng + ?Sized>(&self, _rng: &mut R) -> u8 {
+            let i = self.next.get();
+            self.next.set((i + 1) % self.values.len());
+            self.values[i]
+        }
+    }
+
+    impl BlockGenerator<SequenceSampler> {
+        pub(crate) fn with_sequence_sampler(values: impl IntoIterator<Item = u8>) -> Self {
+            Self::with_rng(
+                StdRng::seed_from_u64(0),
+                SequenceSampler {
+                    values: values.into_iter().collect(),
+                    next: Cell::new(0),
+                },
+            )
+        }
+    }
+
     impl BlockGenerator<MockSampler> {
         pub(crate) fn with_mock_sampler(value: u8) -> Self {
-            Self { rng: rand::rng(), sampler: MockSampler(value) }
+            Self::with_rng(StdRng::seed_from_u64(0), MockSampler(value))
         }
     }
 }
@@ -115,5 +270,174 @@ mod block_generator_tests {
             let mut generator = BlockGenerator::with_mock_sampler(7);
             assert_eq!(generator.block(), BlockType::Z);
         }
+
+        fn domino() -> CustomPiece {
+            CustomPiece::new(
+                [
+                    vec![(1, 0), (1, 1)],
+                    vec![(0, 1), (1, 1)],
+                    vec![(1, 0), (1, 1)],
+                    vec![(0, 1), (1, 1)],
+                ],
+                (1, 2, 3),
+            )
+            .unwrap()
+        }
+
+        #[test]
+        fn when_sampler_produces_value_above_7_returns_custom_piece() {
+            let mut generator = BlockGenerator::with_mock_sampler(9);
+            generator.custom.register(domino()).unwrap();
+            generator.custom.register(domino()).unwrap();
+            assert_eq!(generator.block(), BlockType::Custom(1));
+        }
+
+        #[test]
+        #[should_panic]
+        fn when_sampler_produces_value_above_registered_custom_pieces_panics() {
+            let mut generator = BlockGenerator::with_mock_sampler(9);
+            generator.custom.register(domino()).unwrap();
+            generator.block();
+        }
+    }
+
+    mod seeded_tests {
+        use super::*;
+
+        #[test]
+        fn when_seeds_match_generates_the_same_blocks() {
+            let mut a = BlockGenerator::seeded(1234);
+            let mut b = BlockGenerator::seeded(1234);
+            for _ in 0..50 {
+                assert_eq!(a.block(), b.block());
+            }
+        }
+
+        #[test]
+        fn when_seeds_match_bags_deal_the_same_blocks() {
+            let mut a = BlockGenerator::seeded_bag(1234);
+            let mut b = BlockGenerator::seeded_bag(1234);
+            for _ in 0..50 {
+                assert_eq!(a.block(), b.block());
+            }
+        }
+    }
+
+    mod peek_tests {
+        use super::*;
+
+        #[test]
+        fn when_peeked_then_drawn_yields_identical_blocks() {
+            let mut generator = BlockGenerator::seeded(1234);
+            let peeked = generator.peek(5).to_vec();
+            let drawn: Vec<BlockType> = (0..5).map(|_| generator.block()).collect();
+            assert_eq!(peeked, drawn);
+        }
+
+        #[test]
+        fn when_peeking_across_a_bag_boundary_yields_identical_blocks() {
+            let mut generator = BlockGenerator::seeded_bag(7);
+            generator.block();
+            let peeked = generator.peek(10).to_vec();
+            let drawn: Vec<BlockType> = (0..10).map(|_| generator.block()).collect();
+            assert_eq!(peeked, drawn);
+        }
+
+        #[test]
+        fn does_not_change_the_sequence_of_blocks() {
+            let mut peeking = BlockGenerator::seeded_bag(7);
+            let mut drawing = BlockGenerator::seeded_bag(7);
+
+            peeking.peek(3);
+            peeking.peek(9);
+            for _ in 0..20 {
+                assert_eq!(peeking.block(), drawing.block());
+                peeking.peek(2);
+            }
+        }
+    }
+
+    mod bag_tests {
+        use std::collections::HashMap;
+
+        use super::*;
+
+        #[test]
+        fn deals_each_block_type_once_per_bag() {
+            let mut generator = BlockGenerator::seeded_bag(7);
+            let mut counts: HashMap<BlockType, usize> = HashMap::new();
+            for _ in 0..14 {
+                *counts.entry(generator.block()).or_default() += 1;
+            }
+
+            let expected = HashMap::from([
+                (BlockType::I, 2),
+                (BlockType::J, 2),
+                (BlockType::L, 2),
+                (BlockType::O, 2),
+                (BlockType::S, 2),
+                (BlockType::T, 2),
+                (BlockType::Z, 2),
+            ]);
+            assert_eq!(counts, expected);
+        }
+    }
+
+    mod register_tests {
+        use super::*;
+
+        fn tromino() -> PieceDef {
+            PieceDef {
+                rotations: [
+                    vec![(1, 0), (1, 1), (1, 2)],
+                    vec![(0, 1), (1, 1), (2, 1)],
+                    vec![(1, 0), (1, 1), (1, 2)],
+                    vec![(0, 1), (1, 1), (2, 1)],
+                ],
+                color: (128, 128, 128),
+            }
+        }
+
+        #[test]
+        fn when_piece_is_valid_widens_sampler_to_include_it() {
+            let mut generator = BlockGenerator::new();
+            let block_type = generator.register(tromino()).unwrap();
+            assert_eq!(generator.custom.len(), 1);
+            assert_eq!(block_type, BlockType::Custom(0));
+            assert_eq!(generator.sampler, BlockGenerator::uniform_sampler(1));
+        }
+
+        #[test]
+        fn does_not_register_the_piece_with_other_generators() {
+            let mut generator = BlockGenerator::new();
+            let block_type = generator.register(tromino()).unwrap();
+            assert!(
+                BlockGenerator::new()
+                    .custom_pieces()
+                    .check(block_type)
+                    .is_err()
+            );
+        }
+
+        #[test]
+        fn when_a_rotation_is_empty_returns_err() {
+            let mut def = tromino();
+            def.rotations[2] = vec![];
+            assert!(BlockGenerator::new().register(def).is_err());
+        }
+
+        #[test]
+        fn when_spawn_rotation_is_taller_than_buffer_zone_returns_err() {
+            let mut def = tromino();
+            def.rotations[0] = vec![(0, 1), (1, 1), (2, 1)];
+            assert!(BlockGenerator::new().register(def).is_err());
+        }
+
+        #[test]
+        fn when_a_rotation_is_wider_than_board_returns_err() {
+            let mut def = tromino();
+            def.rotations[1] = vec![(0, 0), (0, 11)];
+            assert!(BlockGenerator::new().register(def).is_err());
+        }
     }
 }
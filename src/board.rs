@@ -1,24 +1,86 @@
-use std::fmt;
+use std::{fmt, str::FromStr};
 
-use crate::block::{ActiveBlock, BlockType};
+use rand::Rng;
+use ratatui::{style::Stylize, text::Span};
 
-/// The play space. A 2D matrix where a square is Some with the occupying [BlockType] if occupied
-/// and None otherwise.
-#[derive(Default, Debug, Clone, PartialEq, Eq)]
-pub struct Board([[Option<BlockType>; Self::COLUMNS]; Self::ROWS]);
+use crate::{
+    block::{ActiveBlock, BlockType, CustomPieces, Position},
+    config::GravityMode,
+};
 
-impl Board {
+/// The contents of an occupied square of the board.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Cell {
+    /// Part of a block fixed to the board by the player.
+    Block(BlockType),
+
+    /// Part of a garbage line added to the bottom of the board.
+    Garbage,
+}
+
+impl Cell {
+    /// Returns a coloured grid cell for rendering.
+    pub fn grid_cell(&self) -> Span<'static> {
+        match self {
+            Cell::Block(block_type) => block_type.grid_cell(),
+            Cell::Garbage => "██".dark_gray(),
+        }
+    }
+
+    /// Returns a coloured grid cell for rendering, drawing custom pieces in the colours they were
+    /// registered with in `custom_pieces`.
+    pub(crate) fn grid_cell_in(&self, custom_pieces: &CustomPieces) -> Span<'static> {
+        match self {
+            Cell::Block(block_type) => custom_pieces.grid_cell(*block_type),
+            Cell::Garbage => self.grid_cell(),
+        }
+    }
+}
+
+impl fmt::Display for Cell {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Cell::Block(block_type) => write!(f, "{}", block_type.symbol()),
+            Cell::Garbage => write!(f, "▒"),
+        }
+    }
+}
+
+/// A grid of optional values, one per square of the board.
+type Grid<T, const ROWS: usize, const COLUMNS: usize> = [[Option<T>; COLUMNS]; ROWS];
+
+/// The play space. A 2D matrix where a square is Some with the occupying [Cell] if occupied and
+/// None otherwise.
+///
+/// `ROWS` counts every row of the board, including the hidden buffer zone, so a board must be at
+/// least [Self::BUFFER_ZONE_ROWS] tall. [Self::checksum] supports boards up to 64 columns wide.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Board<const ROWS: usize, const COLUMNS: usize>([[Option<Cell>; COLUMNS]; ROWS]);
+
+/// The standard 10-column board with 20 playable rows above a two-row buffer zone. `ROWS` counts
+/// the buffer zone, so the 20x10 playfield is a `Board<22, 10>`; a `Board<20, 10>` would leave
+/// only 18 playable rows.
+pub type StandardBoard = Board<22, 10>;
+
+impl<const ROWS: usize, const COLUMNS: usize> Board<ROWS, COLUMNS> {
     /// The number of columns on the board.
-    pub const COLUMNS: usize = 10;
+    pub const COLUMNS: usize = COLUMNS;
 
     /// The height of the invisible buffer zone used for spawning blocks.
     pub const BUFFER_ZONE_ROWS: usize = 2;
 
     /// The number of rows rendered to the player.
-    pub const PLAYABLE_ROWS: usize = 20;
+    pub const PLAYABLE_ROWS: usize = ROWS - Self::BUFFER_ZONE_ROWS;
 
     /// The total number of rows on the board.
-    pub const ROWS: usize = Self::BUFFER_ZONE_ROWS + Self::PLAYABLE_ROWS;
+    pub const ROWS: usize = ROWS;
+
+    /// The bumpiness at which the bumpiness term of [Self::roughness] saturates: an average step of
+    /// four rows between adjacent columns.
+    const ROUGHNESS_BUMPINESS_CAP: usize = 4 * (Self::COLUMNS - 1);
+
+    /// The hole count at which the holes term of [Self::roughness] saturates: one hole per column.
+    const ROUGHNESS_HOLES_CAP: usize = Self::COLUMNS;
 
     /// Instantiates an empty board.
     pub fn new() -> Self {
@@ -28,18 +90,87 @@ impl Board {
     /// Instatiates a full board.
     #[cfg(test)]
     fn new_filled() -> Self {
-        Self([[Some(BlockType::I); Self::COLUMNS]; Self::ROWS])
+        Self([[Some(Cell::Block(BlockType::I)); COLUMNS]; ROWS])
     }
 
-    /// Clear continguous rows of occupied squares and consolidate the board, returning the number
-    /// of lines cleared.
+    /// Clears every complete row of occupied squares, whether or not the complete rows are
+    /// contiguous, and consolidates the board by shifting the surviving rows down in order.
+    /// Returns the number of lines cleared.
     pub fn clear_lines(&mut self) -> u8 {
+        self.clear_lines_detailed().len() as u8
+    }
+
+    /// Clears lines as [Self::clear_lines] does, returning the indices of the rows that were full
+    /// before the board was consolidated, from top to bottom, so that renderers can animate them.
+    pub fn clear_lines_detailed(&mut self) -> Vec<usize> {
+        let full_rows = self
+            .0
+            .iter()
+            .enumerate()
+            .filter(|(_, row)| row.iter().all(|v| v.is_some()))
+            .map(|(r, _)| r)
+            .collect();
+        Self::clear_lines_in(&mut self.0);
+        full_rows
+    }
+
+    /// Clears complete rows according to the given [GravityMode], returning the total number of
+    /// lines cleared.
+    pub fn clear_lines_with(&mut self, mode: GravityMode) -> u8 {
+        match mode {
+            GravityMode::Naive => self.clear_lines(),
+            GravityMode::Cascade => self.clear_lines_cascade(),
+        }
+    }
+
+    /// Clears complete rows without shifting the rows above them, then lets each connected group of
+    /// cells fall until it comes to rest. Repeats until no further lines are completed, returning
+    /// the total number of lines cleared.
+    pub fn clear_lines_cascade(&mut self) -> u8 {
+        Self::clear_lines_cascade_in(&mut self.0)
+    }
+
+    /// Returns the (old, new) position of every cell that would survive clearing lines in the
+    /// given [GravityMode] but finish in a different row, without modifying the board.
+    pub fn consolidation_moves(&self, mode: GravityMode) -> Vec<(Position, Position)> {
+        // Clearing depends only on which cells are occupied, so replaying the clear on a grid in
+        // which each cell holds its own starting position reveals where every cell ends up.
+        let mut origins = [[None; COLUMNS]; ROWS];
+        for (r, row) in self.0.iter().enumerate() {
+            for (c, cell) in row.iter().enumerate() {
+                origins[r][c] = cell.map(|_| (r, c));
+            }
+        }
+        Self::clear_lines_in_with(&mut origins, mode);
+
+        origins
+            .iter()
+            .enumerate()
+            .flat_map(|(r, row)| {
+                row.iter()
+                    .enumerate()
+                    .filter_map(move |(c, origin)| origin.map(|origin| (origin, (r, c))))
+            })
+            .filter(|&(from, to)| from != to)
+            .collect()
+    }
+
+    /// Clears complete rows of any grid according to the given [GravityMode].
+    fn clear_lines_in_with<T: Copy>(grid: &mut Grid<T, ROWS, COLUMNS>, mode: GravityMode) -> u8 {
+        match mode {
+            GravityMode::Naive => Self::clear_lines_in(grid),
+            GravityMode::Cascade => Self::clear_lines_cascade_in(grid),
+        }
+    }
+
+    /// Clears complete rows of any grid, shifting the rows above down. See [Self::clear_lines].
+    fn clear_lines_in<T: Copy>(grid: &mut Grid<T, ROWS, COLUMNS>) -> u8 {
         let mut cleared_row_count = 0;
 
         // First, work down the board to find the highest currently occupied row. This tells us
         // when to stop swapping cleared lines upwards.
         let mut highest_occupied_row = 0isize; // isize is simpler to compare in the loop condition below
-        for (i, row) in self.0.iter().enumerate() {
+        for (i, row) in grid.iter().enumerate() {
             if row.iter().any(|v| v.is_some()) {
                 highest_occupied_row = i as isize;
                 break;
@@ -50,19 +181,19 @@ impl Board {
         let mut i = (Self::ROWS - 1) as isize; // isize avoids a wrapping sub when highest_occupied_row is 0
         while i >= highest_occupied_row {
             // Skip incomplete rows.
-            if self.0[i as usize].iter().any(|v| v.is_none()) {
+            if grid[i as usize].iter().any(|v| v.is_none()) {
                 i -= 1;
                 continue;
             }
 
             // Clear completed rows.
-            self.0[i as usize].fill(None);
+            grid[i as usize].fill(None);
             cleared_row_count += 1;
 
             // Consolidate the board by bubbling cleared rows upwards.
             let rows_to_swap = (highest_occupied_row + 1) as usize..=i as usize;
             for j in rows_to_swap.rev() {
-                self.0.swap(j, j - 1)
+                grid.swap(j, j - 1)
             }
             highest_occupied_row += 1;
         }
@@ -70,6 +201,92 @@ impl Board {
         cleared_row_count
     }
 
+    /// Clears complete rows of any grid, letting connected groups fall. See
+    /// [Self::clear_lines_cascade].
+    fn clear_lines_cascade_in<T: Copy>(grid: &mut Grid<T, ROWS, COLUMNS>) -> u8 {
+        let mut cleared_row_count = 0;
+        loop {
+            let mut cleared = 0;
+            for row in grid
+                .iter_mut()
+                .filter(|row| row.iter().all(|v| v.is_some()))
+            {
+                row.fill(None);
+                cleared += 1;
+            }
+            if cleared == 0 {
+                return cleared_row_count;
+            }
+            cleared_row_count += cleared;
+            Self::settle_groups(grid);
+        }
+    }
+
+    /// Drops each 4-connected group of occupied cells until no group can fall any further.
+    fn settle_groups<T: Copy>(grid: &mut Grid<T, ROWS, COLUMNS>) {
+        let mut moved = true;
+        while moved {
+            moved = false;
+            let mut groups = Self::connected_groups(grid);
+            // Settle the lowest groups first so that groups above them can land on them.
+            groups.sort_by_key(|group| std::cmp::Reverse(group.iter().map(|(r, _)| *r).max()));
+            for group in groups {
+                let cells: Vec<Option<T>> = group.iter().map(|&(r, c)| grid[r][c]).collect();
+                group.iter().for_each(|&(r, c)| grid[r][c] = None);
+
+                let mut distance = 0;
+                while group.iter().all(|&(r, c)| {
+                    r + distance + 1 < Self::ROWS && grid[r + distance + 1][c].is_none()
+                }) {
+                    distance += 1;
+                }
+
+                for (&(r, c), cell) in group.iter().zip(cells) {
+                    grid[r + distance][c] = cell;
+                }
+                moved |= distance > 0;
+            }
+        }
+    }
+
+    /// Returns the positions of each 4-connected group of occupied cells on the board.
+    fn connected_groups<T>(grid: &Grid<T, ROWS, COLUMNS>) -> Vec<Vec<Position>> {
+        let mut visited = [[false; COLUMNS]; ROWS];
+        let mut groups = Vec::new();
+        for r in 0..Self::ROWS {
+            for c in 0..Self::COLUMNS {
+                if visited[r][c] || grid[r][c].is_none() {
+                    continue;
+                }
+
+                let mut group = Vec::new();
+                let mut stack = vec![(r, c)];
+                visited[r][c] = true;
+                while let Some((r, c)) = stack.pop() {
+                    group.push((r, c));
+                    let neighbours = [
+                        (r.wrapping_sub(1), c),
+                        (r + 1, c),
+                        (r, c.wrapping_sub(1)),
+                        (r, c + 1),
+                    ];
+                    for (nr, nc) in neighbours {
+                        if nr < Self::ROWS
+                            && nc < Self::COLUMNS
+                            && !visited[nr][nc]
+                            && grid[nr][nc].is_some()
+                        {
+                            visited[nr][nc] = true;
+                            stack.push((nr, nc));
+                        }
+                    }
+                }
+                groups.push(group);
+            }
+        }
+        groups
+    }
+
     /// Returns true if the active block overlaps a non-empty cell of the board.
     pub fn collides(&self, active_block: &ActiveBlock) -> bool {
         active_block
@@ -80,12 +297,85 @@ impl Board {
             })
     }
 
+    /// Returns true if no square of the board is occupied.
+    pub fn is_empty(&self) -> bool {
+        self.0.iter().flatten().all(Option::is_none)
+    }
+
+    /// Returns true if the square at row `r` and column `c` is occupied or lies outside the board.
+    pub fn is_blocked(&self, r: usize, c: isize) -> bool {
+        usize::try_from(c)
+            .ok()
+            .filter(|&c| r < Self::ROWS && c < Self::COLUMNS)
+            .is_none_or(|c| self.0[r][c].is_some())
+    }
+
+    /// Moves the active block down until it rests on the stack or the floor, returning the number
+    /// of rows it fell.
+    pub fn drop_block(&self, active_block: &mut ActiveBlock) -> usize {
+        let mut rows = 0;
+        loop {
+            active_block.move_down();
+            if self.collides(active_block) {
+                active_block.move_up();
+                return rows;
+            }
+            rows += 1;
+        }
+    }
+
     /// Fills the board cells corresponding to the final position of the active block, fixing the
     /// the block to the board.
     pub fn fix_active_block(&mut self, active_block: &ActiveBlock) {
         active_block
             .board_positions()
-            .for_each(|(r, c)| self.0[r][c] = Some(active_block.block_type()));
+            .for_each(|(r, c)| self.0[r][c] = Some(Cell::Block(active_block.block_type())));
+    }
+
+    /// Returns a copy of the board with the active block's cells filled in, e.g. for rendering,
+    /// without modifying the board. Cells outside the board are skipped.
+    pub fn with_block(&self, active_block: &ActiveBlock) -> Self {
+        let mut board = self.clone();
+        active_block
+            .board_positions()
+            .filter(|&(r, c)| r < Self::ROWS && c < Self::COLUMNS)
+            .for_each(|(r, c)| board.0[r][c] = Some(Cell::Block(active_block.block_type())));
+        board
+    }
+
+    /// Pushes the contents of the board upwards to make room for `count` garbage lines at the
+    /// bottom of the board, each with a single hole in `hole_column`. Any cells pushed off the top
+    /// of the board are lost.
+    pub fn add_garbage_lines(&mut self, count: usize, hole_column: usize) {
+        debug_assert!(
+            hole_column < Self::COLUMNS,
+            "Garbage hole column {} is outside the board's {} columns",
+            hole_column,
+            Self::COLUMNS,
+        );
+
+        let count = count.min(Self::ROWS);
+        self.0.rotate_left(count);
+        for row in &mut self.0[Self::ROWS - count..] {
+            *row = [Some(Cell::Garbage); COLUMNS];
+            row[hole_column] = None;
+        }
+    }
+
+    /// Pushes `count` rows of garbage onto the bottom of the board, each with a hole in a column
+    /// chosen by `rng`.
+    pub fn add_random_garbage_lines(&mut self, count: usize, rng: &mut impl Rng) {
+        for _ in 0..count {
+            self.add_garbage_lines(1, rng.random_range(0..Self::COLUMNS));
+        }
+    }
+
+    /// Returns true if the row at `index` contains any garbage, even if the player has since
+    /// filled its hole. Returns false if `index` is beyond the bottom of the board.
+    pub fn is_garbage_row(&self, index: usize) -> bool {
+        self.0
+            .get(index)
+            .is_some_and(|row| row.contains(&Some(Cell::Garbage)))
     }
 
     /// Returns true if the two-row buffer zone at the top of the board is occupied, which can be
@@ -104,42 +394,435 @@ impl Board {
         occupied
     }
 
+    /// Returns true if the stack in any column rises higher than `threshold_rows` below the top of
+    /// the visible board, which can be used to warn the player that they're close to topping out.
+    pub fn is_in_danger(&self, threshold_rows: usize) -> bool {
+        let safe_height = Self::PLAYABLE_ROWS.saturating_sub(threshold_rows);
+        (0..Self::COLUMNS).any(|c| self.column_height(c) > safe_height)
+    }
+
+    /// Returns a score in the range 0..=1 describing how messy the stack is, where 0 is perfectly
+    /// flat with no holes.
+    ///
+    /// The score is the mean of two terms, each capped at 1:
+    ///
+    /// - bumpiness, the sum of height differences between adjacent columns, divided by
+    ///   [Self::ROUGHNESS_BUMPINESS_CAP];
+    /// - holes, the number of empty cells beneath the top of their column's stack, divided by
+    ///   [Self::ROUGHNESS_HOLES_CAP].
+    pub fn roughness(&self) -> f32 {
+        let bumpiness = (self.bumpiness() as f32 / Self::ROUGHNESS_BUMPINESS_CAP as f32).min(1.0);
+        let holes = (self.count_holes() as f32 / Self::ROUGHNESS_HOLES_CAP as f32).min(1.0);
+        (bumpiness + holes) / 2.0
+    }
+
+    /// Returns the column at the bottom of the deepest well, where a well's depth is the distance
+    /// from the top of the column's stack to the top of its shorter neighbour. The walls count as
+    /// infinitely tall neighbours. Ties are broken in favour of the rightmost column.
+    pub fn deepest_well_column(&self) -> usize {
+        let heights = self.column_heights();
+        (0..Self::COLUMNS)
+            .max_by_key(|&c| {
+                let left = c.checked_sub(1).map_or(usize::MAX, |l| heights[l]);
+                let right = heights.get(c + 1).copied().unwrap_or(usize::MAX);
+                left.min(right).saturating_sub(heights[c])
+            })
+            .expect("board should have at least one column")
+    }
+
+    /// Returns the population variance of the column heights in the half-open range
+    /// `start_col..end_col`. A flat region has a variance of 0.
+    ///
+    /// Returns [None] if the range is empty or extends beyond the board's columns.
+    pub fn surface_variance(&self, start_col: usize, end_col: usize) -> Option<f32> {
+        if start_col >= end_col || end_col > Self::COLUMNS {
+            return None;
+        }
+
+        let heights: Vec<f32> = (start_col..end_col)
+            .map(|c| self.column_height(c) as f32)
+            .collect();
+        let n = heights.len() as f32;
+        let mean = heights.iter().sum::<f32>() / n;
+        Some(heights.iter().map(|h| (h - mean).powi(2)).sum::<f32>() / n)
+    }
+
+    /// Returns the sum of the absolute differences between adjacent [Self::column_heights].
+    pub fn bumpiness(&self) -> usize {
+        self.column_heights()
+            .windows(2)
+            .map(|pair| pair[0].abs_diff(pair[1]))
+            .sum()
+    }
+
+    /// Returns the number of holes: empty cells with an occupied cell somewhere above them in the
+    /// same column, whether directly above or under an overhang.
+    pub fn count_holes(&self) -> usize {
+        (0..Self::COLUMNS)
+            .map(|c| {
+                let height = self.column_height(c);
+                self.0[Self::ROWS - height..]
+                    .iter()
+                    .filter(|row| row[c].is_none())
+                    .count()
+            })
+            .sum()
+    }
+
+    /// Returns the height of the stack in each column, measured from the floor to the column's
+    /// highest occupied cell, regardless of any gaps beneath it. Empty columns have height 0.
+    pub fn column_heights(&self) -> [usize; COLUMNS] {
+        std::array::from_fn(|c| self.column_height(c))
+    }
+
+    /// Returns the height of the stack in the given column, measured from the floor to its
+    /// highest occupied cell. Empty columns have height 0.
+    fn column_height(&self, col: usize) -> usize {
+        self.0
+            .iter()
+            .position(|row| row[col].is_some())
+            .map_or(0, |r| Self::ROWS - r)
+    }
+
+    /// Returns a grid the size of the board marking the empty cells reachable from the open top of
+    /// the board by 4-connected paths through empty cells. Empty cells marked false are sealed off
+    /// by the stack and can't be filled by dropping blocks.
+    pub fn flood_fill_reachable_from_top(&self) -> Vec<Vec<bool>> {
+        let mut reachable = vec![vec![false; Self::COLUMNS]; Self::ROWS];
+        let mut stack: Vec<Position> = (0..Self::COLUMNS)
+            .filter(|&c| self.0[0][c].is_none())
+            .map(|c| (0, c))
+            .collect();
+        stack.iter().for_each(|&(r, c)| reachable[r][c] = true);
+
+        while let Some((r, c)) = stack.pop() {
+            let neighbours = [
+                (r.wrapping_sub(1), c),
+                (r + 1, c),
+                (r, c.wrapping_sub(1)),
+                (r, c + 1),
+            ];
+            for (nr, nc) in neighbours {
+                if nr < Self::ROWS
+                    && nc < Self::COLUMNS
+                    && !reachable[nr][nc]
+                    && self.0[nr][nc].is_none()
+                {
+                    reachable[nr][nc] = true;
+                    stack.push((nr, nc));
+                }
+            }
+        }
+        reachable
+    }
+
+    /// Returns a 64-bit FNV-1a hash of the board's occupancy, for cheaply detecting when two
+    /// copies of a board that should be identical have diverged. Each row is hashed as a bitboard
+    /// in which bit `c` is set if column `c` is occupied.
+    pub fn checksum(&self) -> u64 {
+        const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+        const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+        self.0
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .enumerate()
+                    .filter(|(_, cell)| cell.is_some())
+                    .fold(0u64, |bits, (c, _)| bits | 1 << c)
+            })
+            .flat_map(|bits| bits.to_le_bytes().into_iter().take(COLUMNS.div_ceil(8)))
+            .fold(FNV_OFFSET_BASIS, |hash, byte| {
+                (hash ^ byte as u64).wrapping_mul(FNV_PRIME)
+            })
+    }
+
+    /// Renders the board as text, including the hidden buffer zone rows above a separator line.
+    pub fn render_with_buffer(&self) -> String {
+        let mut rendered = String::new();
+        self.write_rows(&mut rendered, true, &[])
+            .expect("writing to a String should never fail");
+        rendered
+    }
+
+    /// Writes the visible rows of the board as text, drawing the cells at `highlighted` positions
+    /// as `@`, e.g. to overlay the active block.
+    pub(crate) fn write_highlighted(
+        &self,
+        w: &mut impl fmt::Write,
+        highlighted: &[Position],
+    ) -> fmt::Result {
+        self.write_rows(w, false, highlighted)
+    }
+
+    /// Writes the board's rows as text between horizontal borders, optionally including the buffer
+    /// zone above a separator line. Cells at `highlighted` positions are drawn as `@`.
+    fn write_rows(
+        &self,
+        w: &mut impl fmt::Write,
+        include_buffer_zone: bool,
+        highlighted: &[Position],
+    ) -> fmt::Result {
+        let border = "—".repeat(Self::COLUMNS);
+        let write_row = |w: &mut dyn fmt::Write, r: usize| {
+            write!(w, "|")?;
+            for (c, cell) in self.0[r].iter().enumerate() {
+                match cell {
+                    _ if highlighted.contains(&(r, c)) => write!(w, "@")?,
+                    Some(cell) => write!(w, "{cell}")?,
+                    None => write!(w, " ")?,
+                }
+            }
+            writeln!(w, "|")
+        };
+
+        writeln!(w, "*{border}*")?;
+        if include_buffer_zone {
+            for r in 0..Self::BUFFER_ZONE_ROWS {
+                write_row(w, r)?;
+            }
+            writeln!(w, "|{border}|")?;
+        }
+        for r in Self::BUFFER_ZONE_ROWS..Self::ROWS {
+            write_row(w, r)?;
+        }
+        writeln!(w, "*{border}*")
+    }
+
+    /// Returns a copy of the board with its row order reversed, buffer zone included.
+    ///
+    /// This is purely presentational, for variants that draw the stack hanging from the ceiling:
+    /// gravity, collision and line clears always operate on the unflipped board.
+    pub fn flip_vertical(&self) -> Self {
+        let mut flipped = self.clone();
+        flipped.0.reverse();
+        flipped
+    }
+
+    /// Returns each row of the board as a string, with one character per square as encoded by
+    /// [encode_square].
+    #[cfg(feature = "serde")]
+    pub(crate) fn encoded_rows(&self) -> impl Iterator<Item = String> + '_ {
+        self.0
+            .iter()
+            .map(|row| row.iter().map(encode_square).collect())
+    }
+
+    /// Decodes a board from rows encoded by [Self::encoded_rows], returning an error if the
+    /// dimensions don't match the board's or a square is unrecognized.
+    #[cfg(feature = "serde")]
+    pub(crate) fn from_encoded_rows(rows: &[impl AsRef<str>]) -> Result<Self, String> {
+        if rows.len() != ROWS {
+            return Err(format!("board must have {ROWS} rows: rows={}", rows.len()));
+        }
+
+        let mut board = Self::new();
+        for (r, row) in rows.iter().enumerate() {
+            let squares = row
+                .as_ref()
+                .chars()
+                .map(decode_square)
+                .collect::<Result<Vec<_>, String>>()?;
+            board.0[r] = squares.try_into().map_err(|squares: Vec<_>| {
+                format!(
+                    "row {r} must have {COLUMNS} squares: squares={}",
+                    squares.len()
+                )
+            })?;
+        }
+        Ok(board)
+    }
+
+    /// Returns an iterator over the positions of the board's occupied squares, in order of
+    /// increasing row then column.
+    pub fn occupied_cells(&self) -> impl Iterator<Item = Position> + '_ {
+        self.0.iter().enumerate().flat_map(|(r, row)| {
+            row.iter()
+                .enumerate()
+                .filter(|(_, cell)| cell.is_some())
+                .map(move |(c, _)| (r, c))
+        })
+    }
+
     /// Returns an iterator over the board's rows.
-    pub fn iter(&self) -> impl Iterator<Item = &[Option<BlockType>; Self::COLUMNS]> {
+    pub fn iter(&self) -> impl Iterator<Item = &[Option<Cell>; COLUMNS]> {
         self.0.iter()
     }
 }
 
-impl From<[[Option<BlockType>; Board::COLUMNS]; Board::ROWS]> for Board {
-    fn from(value: [[Option<BlockType>; Board::COLUMNS]; Board::ROWS]) -> Self {
-        Board(value)
+impl<const ROWS: usize, const COLUMNS: usize> Default for Board<ROWS, COLUMNS> {
+    fn default() -> Self {
+        Self([[None; COLUMNS]; ROWS])
     }
 }
 
-impl fmt::Display for Board {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        writeln!(f, "*{}*", "—".repeat(Board::COLUMNS))?;
+impl<const ROWS: usize, const COLUMNS: usize> From<[[Option<Cell>; COLUMNS]; ROWS]>
+    for Board<ROWS, COLUMNS>
+{
+    fn from(value: [[Option<Cell>; COLUMNS]; ROWS]) -> Self {
+        Self(value)
+    }
+}
+
+/// The reasons a board may fail to parse with [Board::from_str].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseBoardError {
+    /// The string didn't have one line per playable row.
+    WrongRowCount { expected: usize, found: usize },
+
+    /// A row didn't have one character per column.
+    RaggedRow {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+
+    /// A square was neither filled (`#` or `1`) nor empty (`.` or `0`).
+    UnrecognizedSquare {
+        row: usize,
+        column: usize,
+        square: char,
+    },
+}
 
-        let print_row = |f: &mut fmt::Formatter<'_>, row: &[Option<BlockType>; 10]| {
-            let row = row.map(|o| o.map_or(" ".into(), |bt| bt.to_string()));
-            writeln!(
+impl fmt::Display for ParseBoardError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseBoardError::WrongRowCount { expected, found } => {
+                write!(f, "board must have {expected} rows, found {found}")
+            }
+            ParseBoardError::RaggedRow {
+                row,
+                expected,
+                found,
+            } => write!(f, "row {row} must have {expected} squares, found {found}"),
+            ParseBoardError::UnrecognizedSquare {
+                row,
+                column,
+                square,
+            } => write!(
                 f,
-                "|{}{}{}{}{}{}{}{}{}{}|",
-                row[0], row[1], row[2], row[3], row[4], row[5], row[6], row[7], row[8], row[9]
-            )
-        };
+                "unrecognized square {square:?} at row {row}, column {column}"
+            ),
+        }
+    }
+}
 
-        self.0[..Board::BUFFER_ZONE_ROWS]
-            .iter()
-            .try_for_each(|row| print_row(f, row))?;
+impl<const ROWS: usize, const COLUMNS: usize> FromStr for Board<ROWS, COLUMNS> {
+    type Err = ParseBoardError;
+
+    /// Parses the playable rows of a board from a multi-line string with one character per square,
+    /// where `#` or `1` is filled and `.` or `0` is empty. Filled squares become [Cell::Garbage]
+    /// and the buffer zone is left empty, which makes this convenient for test fixtures and puzzle
+    /// setups. Leading and trailing whitespace around the string is ignored.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let rows: Vec<&str> = s.trim().lines().collect();
+        if rows.len() != Self::PLAYABLE_ROWS {
+            return Err(ParseBoardError::WrongRowCount {
+                expected: Self::PLAYABLE_ROWS,
+                found: rows.len(),
+            });
+        }
 
-        writeln!(f, "|{}|", "—".repeat(Board::COLUMNS))?;
+        let mut board = Self::new();
+        for (row, line) in rows.into_iter().enumerate() {
+            let found = line.chars().count();
+            if found != COLUMNS {
+                return Err(ParseBoardError::RaggedRow {
+                    row,
+                    expected: COLUMNS,
+                    found,
+                });
+            }
 
-        self.0[Board::BUFFER_ZONE_ROWS..]
-            .iter()
-            .try_for_each(|row| print_row(f, row))?;
+            let squares = &mut board.0[row + Self::BUFFER_ZONE_ROWS];
+            for (column, square) in line.chars().enumerate() {
+                squares[column] = match square {
+                    '#' | '1' => Some(Cell::Garbage),
+                    '.' | '0' => None,
+                    _ => {
+                        return Err(ParseBoardError::UnrecognizedSquare {
+                            row,
+                            column,
+                            square,
+                        });
+                    }
+                };
+            }
+        }
+        Ok(board)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<const ROWS: usize, const COLUMNS: usize> serde::Serialize for Board<ROWS, COLUMNS> {
+    /// Serializes the board compactly, as one string per row with one character per square.
+    fn serialize<Ser: serde::Serializer>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error> {
+        serializer.collect_seq(self.encoded_rows())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, const ROWS: usize, const COLUMNS: usize> serde::Deserialize<'de>
+    for Board<ROWS, COLUMNS>
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let rows = Vec::<String>::deserialize(deserializer)?;
+        Self::from_encoded_rows(&rows).map_err(serde::de::Error::custom)
+    }
+}
+
+/// The first character used to encode squares occupied by custom pieces, which are offset from
+/// here by their ID in the Unicode Private Use Area.
+#[cfg(feature = "serde")]
+const CUSTOM_SQUARE_BASE: u32 = 0xE000;
+
+/// Encodes a square of the board as a single character: '.' if empty, the block's symbol for
+/// standard blocks and '▒' for garbage.
+#[cfg(feature = "serde")]
+pub(crate) fn encode_square(square: &Option<Cell>) -> char {
+    match square {
+        None => '.',
+        Some(Cell::Garbage) => '▒',
+        Some(Cell::Block(BlockType::Custom(id))) => {
+            char::from_u32(CUSTOM_SQUARE_BASE + u32::from(*id))
+                .expect("custom square should be a valid character")
+        }
+        Some(Cell::Block(block_type)) => block_type.symbol(),
+    }
+}
 
-        writeln!(f, "*{}*", "—".repeat(Board::COLUMNS))
+/// Decodes a square of the board encoded by [encode_square].
+#[cfg(feature = "serde")]
+pub(crate) fn decode_square(c: char) -> Result<Option<Cell>, String> {
+    let block_type = match c {
+        '.' => return Ok(None),
+        '▒' => return Ok(Some(Cell::Garbage)),
+        'I' => BlockType::I,
+        'J' => BlockType::J,
+        'L' => BlockType::L,
+        'O' => BlockType::O,
+        'S' => BlockType::S,
+        'T' => BlockType::T,
+        'Z' => BlockType::Z,
+        _ => u8::try_from(u32::from(c).wrapping_sub(CUSTOM_SQUARE_BASE))
+            .map(BlockType::Custom)
+            .map_err(|_| format!("unrecognized board square: {c:?}"))?,
+    };
+    Ok(Some(Cell::Block(block_type)))
+}
+
+impl<const ROWS: usize, const COLUMNS: usize> fmt::Display for Board<ROWS, COLUMNS> {
+    /// Renders the visible rows of the board as text.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_rows(f, false, &[])
+    }
+}
+
+impl<const ROWS: usize, const COLUMNS: usize> fmt::Debug for Board<ROWS, COLUMNS> {
+    /// Renders every row of the board as text, including the hidden buffer zone.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "\n{}", self.render_with_buffer())
     }
 }
 
@@ -151,9 +834,9 @@ mod board_tests {
 
         #[test]
         fn when_board_is_empty_clears_no_lines() {
-            let mut board = Board::new();
+            let mut board = StandardBoard::new();
             let expected_lines_cleared = 0;
-            let expected_board = Board::new();
+            let expected_board = StandardBoard::new();
 
             let lines_cleared = board.clear_lines();
 
@@ -172,9 +855,9 @@ mod board_tests {
 
         #[test]
         fn when_board_is_full_clears_all_lines() {
-            let mut board = Board::new_filled();
-            let expected_lines_cleared = Board::ROWS as u8;
-            let expected_board = Board::new();
+            let mut board = StandardBoard::new_filled();
+            let expected_lines_cleared = StandardBoard::ROWS as u8;
+            let expected_board = StandardBoard::new();
 
             let lines_cleared = board.clear_lines();
 
@@ -193,11 +876,12 @@ mod board_tests {
 
         #[test]
         fn when_one_complete_line_clears_one_line() {
-            let mut board = Board::new();
-            board.0[Board::ROWS - 1] = [Some(BlockType::I); Board::COLUMNS];
+            let mut board = StandardBoard::new();
+            board.0[StandardBoard::ROWS - 1] =
+                [Some(Cell::Block(BlockType::I)); StandardBoard::COLUMNS];
 
             let expected_lines_cleared = 1;
-            let expected_board = Board::new();
+            let expected_board = StandardBoard::new();
 
             let lines_cleared = board.clear_lines();
 
@@ -216,12 +900,14 @@ mod board_tests {
 
         #[test]
         fn when_multiple_complete_lines_clears_all_complete_lines() {
-            let mut board = Board::new();
-            board.0[Board::ROWS - 2] = [Some(BlockType::I); Board::COLUMNS];
-            board.0[Board::ROWS - 1] = [Some(BlockType::I); Board::COLUMNS];
+            let mut board = StandardBoard::new();
+            board.0[StandardBoard::ROWS - 2] =
+                [Some(Cell::Block(BlockType::I)); StandardBoard::COLUMNS];
+            board.0[StandardBoard::ROWS - 1] =
+                [Some(Cell::Block(BlockType::I)); StandardBoard::COLUMNS];
 
             let expected_lines_cleared = 2;
-            let expected_board = Board::new();
+            let expected_board = StandardBoard::new();
 
             let lines_cleared = board.clear_lines();
 
@@ -240,57 +926,58 @@ mod board_tests {
 
         #[test]
         fn when_complete_line_has_rows_above_it_consolidates_board() {
-            let mut board = Board::new();
-            board.0[Board::ROWS - 3] = [
+            let mut board = StandardBoard::new();
+            board.0[StandardBoard::ROWS - 3] = [
                 None,
-                Some(BlockType::I),
+                Some(Cell::Block(BlockType::I)),
                 None,
-                Some(BlockType::I),
+                Some(Cell::Block(BlockType::I)),
                 None,
-                Some(BlockType::I),
+                Some(Cell::Block(BlockType::I)),
                 None,
-                Some(BlockType::I),
+                Some(Cell::Block(BlockType::I)),
                 None,
-                Some(BlockType::I),
+                Some(Cell::Block(BlockType::I)),
             ];
-            board.0[Board::ROWS - 2] = [
-                Some(BlockType::I),
+            board.0[StandardBoard::ROWS - 2] = [
+                Some(Cell::Block(BlockType::I)),
                 None,
-                Some(BlockType::I),
+                Some(Cell::Block(BlockType::I)),
                 None,
-                Some(BlockType::I),
+                Some(Cell::Block(BlockType::I)),
                 None,
-                Some(BlockType::I),
+                Some(Cell::Block(BlockType::I)),
                 None,
-                Some(BlockType::I),
+                Some(Cell::Block(BlockType::I)),
                 None,
             ];
-            board.0[Board::ROWS - 1] = [Some(BlockType::I); Board::COLUMNS];
+            board.0[StandardBoard::ROWS - 1] =
+                [Some(Cell::Block(BlockType::I)); StandardBoard::COLUMNS];
 
             let expected_lines_cleared = 1;
-            let mut expected_board = Board::new();
-            expected_board.0[Board::ROWS - 2] = [
+            let mut expected_board = StandardBoard::new();
+            expected_board.0[StandardBoard::ROWS - 2] = [
                 None,
-                Some(BlockType::I),
+                Some(Cell::Block(BlockType::I)),
                 None,
-                Some(BlockType::I),
+                Some(Cell::Block(BlockType::I)),
                 None,
-                Some(BlockType::I),
+                Some(Cell::Block(BlockType::I)),
                 None,
-                Some(BlockType::I),
+                Some(Cell::Block(BlockType::I)),
                 None,
-                Some(BlockType::I),
+                Some(Cell::Block(BlockType::I)),
             ];
-            expected_board.0[Board::ROWS - 1] = [
-                Some(BlockType::I),
+            expected_board.0[StandardBoard::ROWS - 1] = [
+                Some(Cell::Block(BlockType::I)),
                 None,
-                Some(BlockType::I),
+                Some(Cell::Block(BlockType::I)),
                 None,
-                Some(BlockType::I),
+                Some(Cell::Block(BlockType::I)),
                 None,
-                Some(BlockType::I),
+                Some(Cell::Block(BlockType::I)),
                 None,
-                Some(BlockType::I),
+                Some(Cell::Block(BlockType::I)),
                 None,
             ];
 
@@ -311,58 +998,60 @@ mod board_tests {
 
         #[test]
         fn when_multiple_complete_lines_have_rows_above_them_consolidates_board() {
-            let mut board = Board::new();
-            board.0[Board::ROWS - 4] = [
+            let mut board = StandardBoard::new();
+            board.0[StandardBoard::ROWS - 4] = [
                 None,
-                Some(BlockType::I),
+                Some(Cell::Block(BlockType::I)),
                 None,
-                Some(BlockType::I),
+                Some(Cell::Block(BlockType::I)),
                 None,
-                Some(BlockType::I),
+                Some(Cell::Block(BlockType::I)),
                 None,
-                Some(BlockType::I),
+                Some(Cell::Block(BlockType::I)),
                 None,
-                Some(BlockType::I),
+                Some(Cell::Block(BlockType::I)),
             ];
-            board.0[Board::ROWS - 3] = [Some(BlockType::I); Board::COLUMNS];
-            board.0[Board::ROWS - 2] = [
-                Some(BlockType::I),
+            board.0[StandardBoard::ROWS - 3] =
+                [Some(Cell::Block(BlockType::I)); StandardBoard::COLUMNS];
+            board.0[StandardBoard::ROWS - 2] = [
+                Some(Cell::Block(BlockType::I)),
                 None,
-                Some(BlockType::I),
+                Some(Cell::Block(BlockType::I)),
                 None,
-                Some(BlockType::I),
+                Some(Cell::Block(BlockType::I)),
                 None,
-                Some(BlockType::I),
+                Some(Cell::Block(BlockType::I)),
                 None,
-                Some(BlockType::I),
+                Some(Cell::Block(BlockType::I)),
                 None,
             ];
-            board.0[Board::ROWS - 1] = [Some(BlockType::I); Board::COLUMNS];
+            board.0[StandardBoard::ROWS - 1] =
+                [Some(Cell::Block(BlockType::I)); StandardBoard::COLUMNS];
 
             let expected_lines_cleared = 2;
-            let mut expected_board = Board::new();
-            expected_board.0[Board::ROWS - 2] = [
+            let mut expected_board = StandardBoard::new();
+            expected_board.0[StandardBoard::ROWS - 2] = [
                 None,
-                Some(BlockType::I),
+                Some(Cell::Block(BlockType::I)),
                 None,
-                Some(BlockType::I),
+                Some(Cell::Block(BlockType::I)),
                 None,
-                Some(BlockType::I),
+                Some(Cell::Block(BlockType::I)),
                 None,
-                Some(BlockType::I),
+                Some(Cell::Block(BlockType::I)),
                 None,
-                Some(BlockType::I),
+                Some(Cell::Block(BlockType::I)),
             ];
-            expected_board.0[Board::ROWS - 1] = [
-                Some(BlockType::I),
+            expected_board.0[StandardBoard::ROWS - 1] = [
+                Some(Cell::Block(BlockType::I)),
                 None,
-                Some(BlockType::I),
+                Some(Cell::Block(BlockType::I)),
                 None,
-                Some(BlockType::I),
+                Some(Cell::Block(BlockType::I)),
                 None,
-                Some(BlockType::I),
+                Some(Cell::Block(BlockType::I)),
                 None,
-                Some(BlockType::I),
+                Some(Cell::Block(BlockType::I)),
                 None,
             ];
 
@@ -380,94 +1069,1279 @@ mod board_tests {
                 expected_board, board
             )
         }
+
+        #[test]
+        fn when_complete_lines_are_separated_by_partial_rows_consolidates_survivors_in_order() {
+            use BlockType::*;
+            let block_types = [I, J, L, O, S, T, Z];
+            let full_rows = [5, 10, 15];
+            let mut board = StandardBoard::new();
+            let mut survivors = Vec::new();
+            for r in 3..StandardBoard::ROWS {
+                let mut row =
+                    [Some(Cell::Block(block_types[r % block_types.len()])); StandardBoard::COLUMNS];
+                if !full_rows.contains(&r) {
+                    // Scatter the holes so that no two partial rows are alike.
+                    row[r % StandardBoard::COLUMNS] = None;
+                    row[r * 3 % StandardBoard::COLUMNS] = None;
+                    survivors.push(row);
+                }
+                board.0[r] = row;
+            }
+
+            let mut expected_board = StandardBoard::new();
+            let first_survivor_row = StandardBoard::ROWS - survivors.len();
+            expected_board.0[first_survivor_row..].copy_from_slice(&survivors);
+
+            let lines_cleared = board.clear_lines();
+
+            assert_eq!(lines_cleared, full_rows.len() as u8);
+            assert_eq!(
+                expected_board, board,
+                "Cleared board did not match expected board:\nExpected:\n{}\nActual:\n{}",
+                expected_board, board
+            )
+        }
     }
 
-    mod collides_tests {
+    mod clear_lines_detailed_tests {
         use super::*;
 
         #[test]
-        fn when_block_is_within_bounds_and_board_is_empty_returns_false() {
-            let board = Board::new();
-            let block = ActiveBlock::new(BlockType::I);
-            assert!(!board.collides(&block));
+        fn when_board_is_empty_returns_no_rows() {
+            let mut board = StandardBoard::new();
+            assert_eq!(board.clear_lines_detailed(), Vec::<usize>::new());
         }
 
         #[test]
-        fn when_block_row_exceeds_board_rows_returns_true() {
-            let board = Board::new();
-            let mut block = ActiveBlock::new(BlockType::I);
-            for _ in 0..Board::ROWS - 1 {
-                block.move_down();
-            }
-            assert!(board.collides(&block));
+        fn when_one_line_is_complete_returns_its_row() {
+            let mut board = StandardBoard::new();
+            board.0[StandardBoard::ROWS - 2] =
+                [Some(Cell::Block(BlockType::I)); StandardBoard::COLUMNS];
+            board.0[StandardBoard::ROWS - 1][0] = Some(Cell::Garbage);
+
+            assert_eq!(board.clear_lines_detailed(), vec![StandardBoard::ROWS - 2]);
+            assert_eq!(board.occupied_cells().count(), 1);
         }
 
         #[test]
-        fn when_block_column_exceeds_board_columns_returns_true() {
-            let board = Board::new();
-            let mut block = ActiveBlock::new(BlockType::I);
-            // I starts at left=3; move right 5 times to left=8 so local col 2 maps to board col 10.
-            for _ in 0..5 {
-                block.move_right();
+        fn when_several_lines_are_complete_returns_their_rows_before_consolidation() {
+            let mut board = StandardBoard::new();
+            for r in [
+                StandardBoard::ROWS - 4,
+                StandardBoard::ROWS - 3,
+                StandardBoard::ROWS - 1,
+            ] {
+                board.0[r] = [Some(Cell::Block(BlockType::I)); StandardBoard::COLUMNS];
             }
-            assert!(board.collides(&block));
+            board.0[StandardBoard::ROWS - 2][0] = Some(Cell::Garbage);
+
+            assert_eq!(
+                board.clear_lines_detailed(),
+                vec![
+                    StandardBoard::ROWS - 4,
+                    StandardBoard::ROWS - 3,
+                    StandardBoard::ROWS - 1
+                ]
+            );
+            assert_eq!(
+                board.occupied_cells().collect::<Vec<_>>(),
+                vec![(StandardBoard::ROWS - 1, 0)]
+            );
         }
 
         #[test]
-        fn when_block_is_past_left_boundary_returns_true() {
-            let board = Board::new();
-            let mut block = ActiveBlock::new(BlockType::I);
-            // I starts at left=3; move left 4 times to left=-1 so local col 0 overflows to usize::MAX.
-            for _ in 0..4 {
-                block.move_left();
+        fn when_complete_lines_are_separated_by_partial_rows_returns_each_row() {
+            let mut board = StandardBoard::new();
+            for r in 3..StandardBoard::ROWS {
+                board.0[r] = [Some(Cell::Garbage); StandardBoard::COLUMNS];
+                if ![5, 10, 15].contains(&r) {
+                    board.0[r][r % StandardBoard::COLUMNS] = None;
+                }
             }
-            assert!(board.collides(&block));
+
+            assert_eq!(board.clear_lines_detailed(), vec![5, 10, 15]);
+        }
+    }
+
+    mod from_str_tests {
+        use super::*;
+
+        use indoc::indoc;
+
+        const STACK: &str = indoc! {"
+            ..........
+            ..........
+            ..........
+            ..........
+            ..........
+            ..........
+            ..........
+            ..........
+            ..........
+            ..........
+            ..........
+            ..........
+            ..........
+            ..........
+            ..........
+            ..........
+            ..........
+            ....#.....
+            0011000000
+            ##########
+        "};
+
+        #[test]
+        fn when_string_is_valid_fills_playable_rows() {
+            let board: StandardBoard = STACK.parse().unwrap();
+            assert_eq!(
+                board.occupied_cells().collect::<Vec<_>>(),
+                [(19, 4), (20, 2), (20, 3)]
+                    .into_iter()
+                    .chain((0..StandardBoard::COLUMNS).map(|c| (21, c)))
+                    .collect::<Vec<_>>()
+            );
+            assert_eq!(board.0[21][0], Some(Cell::Garbage));
         }
 
         #[test]
-        fn when_block_overlaps_occupied_cell_returns_true() {
-            let mut board = Board::new();
-            // I at its initial position occupies board cell (1, 3).
-            board.0[1][3] = Some(BlockType::I);
-            let block = ActiveBlock::new(BlockType::I);
-            assert!(board.collides(&block));
+        fn when_row_count_is_wrong_returns_err() {
+            let result = "..........\n##########".parse::<StandardBoard>();
+            assert_eq!(
+                result,
+                Err(ParseBoardError::WrongRowCount {
+                    expected: 20,
+                    found: 2
+                })
+            );
+        }
+
+        #[test]
+        fn when_rows_are_ragged_returns_err() {
+            let ragged = STACK.replacen("....#.....", "....#....", 1);
+            assert_eq!(
+                ragged.parse::<StandardBoard>(),
+                Err(ParseBoardError::RaggedRow {
+                    row: 17,
+                    expected: 10,
+                    found: 9
+                })
+            );
+        }
+
+        #[test]
+        fn when_square_is_unrecognized_returns_err() {
+            let bad = STACK.replacen("....#.....", "....x.....", 1);
+            assert_eq!(
+                bad.parse::<StandardBoard>(),
+                Err(ParseBoardError::UnrecognizedSquare {
+                    row: 17,
+                    column: 4,
+                    square: 'x'
+                })
+            );
+        }
+
+        #[test]
+        fn error_describes_the_problem() {
+            let err = "#".parse::<StandardBoard>().unwrap_err();
+            assert_eq!(err.to_string(), "board must have 20 rows, found 1");
         }
     }
 
-    mod fix_active_block_tests {
+    mod clear_lines_cascade_tests {
         use super::*;
 
         #[test]
-        fn sets_block_positions_to_block_type() {
-            let mut board = Board::new();
-            let block = ActiveBlock::new(BlockType::I);
+        fn when_groups_float_after_clear_drops_them_to_rest() {
+            let mut board = StandardBoard::new();
+            board.0[StandardBoard::ROWS - 1] = [Some(Cell::Garbage); StandardBoard::COLUMNS];
+            board.0[StandardBoard::ROWS - 2][0] = Some(Cell::Block(BlockType::I));
+            for c in 5..8 {
+                board.0[StandardBoard::ROWS - 3][c] = Some(Cell::Block(BlockType::T));
+            }
+            let mut naive = board.clone();
 
-            board.fix_active_block(&block);
+            let lines_cleared = board.clear_lines_cascade();
+            naive.clear_lines();
 
-            // I at its initial position occupies (1, 3..=6).
-            let mut expected = Board::new();
-            expected.0[1][3] = Some(BlockType::I);
-            expected.0[1][4] = Some(BlockType::I);
-            expected.0[1][5] = Some(BlockType::I);
-            expected.0[1][6] = Some(BlockType::I);
+            let mut expected = StandardBoard::new();
+            expected.0[StandardBoard::ROWS - 1][0] = Some(Cell::Block(BlockType::I));
+            for c in 5..8 {
+                expected.0[StandardBoard::ROWS - 1][c] = Some(Cell::Block(BlockType::T));
+            }
+            assert_eq!(lines_cleared, 1);
             assert_eq!(board, expected);
+            assert_ne!(naive, expected);
         }
-    }
 
-    mod buffer_zone_occupied_tests {
-        use super::*;
+        #[test]
+        fn when_falling_group_completes_a_line_clears_it_in_a_chain() {
+            let mut board = StandardBoard::new();
+            board.0[StandardBoard::ROWS - 1] = [Some(Cell::Garbage); StandardBoard::COLUMNS];
+            board.0[StandardBoard::ROWS - 1][9] = None;
+            board.0[StandardBoard::ROWS - 2] =
+                [Some(Cell::Block(BlockType::I)); StandardBoard::COLUMNS];
+            board.0[StandardBoard::ROWS - 3][9] = Some(Cell::Block(BlockType::O));
+            let mut naive = board.clone();
+
+            let lines_cleared = board.clear_lines_cascade();
+            let naive_lines_cleared = naive.clear_lines();
+
+            assert_eq!(lines_cleared, 2);
+            assert_eq!(board, StandardBoard::new());
+            assert_eq!(naive_lines_cleared, 1);
+            assert_eq!(
+                naive.0[StandardBoard::ROWS - 2][9],
+                Some(Cell::Block(BlockType::O))
+            );
+        }
 
         #[test]
-        fn when_buffer_zone_row_1_is_occupied_returns_true() {
-            let mut board = Board::new();
-            board.0[1][0] = Some(BlockType::I);
-            assert!(board.buffer_zone_occupied());
+        fn when_no_lines_are_complete_leaves_floating_cells_in_place() {
+            let mut board = StandardBoard::new();
+            board.0[StandardBoard::ROWS - 5][4] = Some(Cell::Block(BlockType::S));
+            let expected = board.clone();
+
+            assert_eq!(board.clear_lines_cascade(), 0);
+            assert_eq!(board, expected);
+        }
+    }
+
+    mod consolidation_moves_tests {
+        use super::*;
+
+        #[test]
+        fn when_line_is_cleared_below_partial_row_reports_shift_down_one_row() {
+            let mut board = StandardBoard::new();
+            board.0[StandardBoard::ROWS - 1].fill(Some(Cell::Block(BlockType::I)));
+            board.0[StandardBoard::ROWS - 2][0] = Some(Cell::Block(BlockType::T));
+            board.0[StandardBoard::ROWS - 2][3] = Some(Cell::Block(BlockType::T));
+
+            let moves = board.consolidation_moves(GravityMode::Naive);
+
+            assert_eq!(
+                moves,
+                vec![
+                    ((StandardBoard::ROWS - 2, 0), (StandardBoard::ROWS - 1, 0)),
+                    ((StandardBoard::ROWS - 2, 3), (StandardBoard::ROWS - 1, 3)),
+                ]
+            );
+        }
+
+        #[test]
+        fn when_no_line_is_cleared_returns_no_moves() {
+            let mut board = StandardBoard::new();
+            board.0[StandardBoard::ROWS - 1][0] = Some(Cell::Block(BlockType::I));
+            assert!(board.consolidation_moves(GravityMode::Naive).is_empty());
+        }
+
+        #[test]
+        fn does_not_modify_board() {
+            let mut board = StandardBoard::new();
+            board.0[StandardBoard::ROWS - 1].fill(Some(Cell::Block(BlockType::I)));
+            let before = board.clone();
+
+            board.consolidation_moves(GravityMode::Cascade);
+
+            assert_eq!(board, before);
+        }
+
+        #[test]
+        fn when_gravity_mode_is_cascade_reports_groups_falling_to_rest() {
+            let mut board = StandardBoard::new();
+            board.0[StandardBoard::ROWS - 2].fill(Some(Cell::Block(BlockType::I)));
+            board.0[StandardBoard::ROWS - 3][0] = Some(Cell::Block(BlockType::T));
+
+            let moves = board.consolidation_moves(GravityMode::Cascade);
+
+            assert_eq!(
+                moves,
+                vec![((StandardBoard::ROWS - 3, 0), (StandardBoard::ROWS - 1, 0))]
+            );
+        }
+    }
+
+    mod collides_tests {
+        use super::*;
+
+        #[test]
+        fn when_block_is_within_bounds_and_board_is_empty_returns_false() {
+            let board = StandardBoard::new();
+            let block = ActiveBlock::new(BlockType::I);
+            assert!(!board.collides(&block));
+        }
+
+        #[test]
+        fn when_block_row_exceeds_board_rows_returns_true() {
+            let board = StandardBoard::new();
+            let mut block = ActiveBlock::new(BlockType::I);
+            for _ in 0..StandardBoard::ROWS - 1 {
+                block.move_down();
+            }
+            assert!(board.collides(&block));
+        }
+
+        #[test]
+        fn when_block_column_exceeds_board_columns_returns_true() {
+            let board = StandardBoard::new();
+            let mut block = ActiveBlock::new(BlockType::I);
+            // I starts at left=3; move right 5 times to left=8 so local col 2 maps to board col 10.
+            for _ in 0..5 {
+                block.move_right();
+            }
+            assert!(board.collides(&block));
+        }
+
+        #[test]
+        fn when_block_is_past_left_boundary_returns_true() {
+            let board = StandardBoard::new();
+            let mut block = ActiveBlock::new(BlockType::I);
+            // I starts at left=3; move left 4 times to left=-1 so local col 0 overflows to usize::MAX.
+            for _ in 0..4 {
+                block.move_left();
+            }
+            assert!(board.collides(&block));
+        }
+
+        #[test]
+        fn when_block_overlaps_occupied_cell_returns_true() {
+            let mut board = StandardBoard::new();
+            // I at its initial position occupies board cell (1, 3).
+            board.0[1][3] = Some(Cell::Block(BlockType::I));
+            let block = ActiveBlock::new(BlockType::I);
+            assert!(board.collides(&block));
+        }
+
+        #[test]
+        fn when_block_straddles_buffer_zone_and_overlaps_cell_below_returns_true() {
+            let mut board = StandardBoard::new();
+            // J moved down once occupies (1, 4), (2, 4), (2, 5) and (2, 6).
+            board.0[StandardBoard::BUFFER_ZONE_ROWS][6] = Some(Cell::Block(BlockType::I));
+            let mut block = ActiveBlock::new(BlockType::J);
+            block.move_down();
+            assert!(board.collides(&block));
+        }
+
+        #[test]
+        fn when_block_straddles_buffer_zone_beside_occupied_cells_returns_false() {
+            let mut board = StandardBoard::new();
+            board.0[0][5] = Some(Cell::Block(BlockType::I));
+            board.0[StandardBoard::BUFFER_ZONE_ROWS][7] = Some(Cell::Block(BlockType::I));
+            let mut block = ActiveBlock::new(BlockType::J);
+            block.move_down();
+            assert!(!board.collides(&block));
+        }
+
+        #[test]
+        fn when_block_rests_on_bottom_row_returns_false() {
+            let board = StandardBoard::new();
+            let mut block = ActiveBlock::new(BlockType::O);
+            for _ in 0..StandardBoard::ROWS - 2 {
+                block.move_down();
+            }
+            assert!(!board.collides(&block));
+        }
+
+        #[test]
+        fn when_bounding_box_overhangs_left_wall_but_cells_do_not_returns_false() {
+            let board = StandardBoard::new();
+            // J's clockwise rotation leaves the left column of its bounding box empty.
+            let mut block = ActiveBlock::new(BlockType::J);
+            block.rotate_clockwise();
+            for _ in 0..5 {
+                block.move_left();
+            }
+            assert_eq!(block.top_left().1, -1);
+            assert!(!board.collides(&block));
+
+            block.move_left();
+            assert!(board.collides(&block));
+        }
+
+        #[test]
+        fn when_bounding_box_overhangs_right_wall_but_cells_do_not_returns_false() {
+            let board = StandardBoard::new();
+            // J's counter-clockwise rotation leaves the right column of its bounding box empty.
+            let mut block = ActiveBlock::new(BlockType::J);
+            block.rotate_counter_clockwise();
+            for _ in 0..4 {
+                block.move_right();
+            }
+            assert!(!board.collides(&block));
+
+            block.move_right();
+            assert!(board.collides(&block));
+        }
+    }
+
+    mod drop_block_tests {
+        use super::*;
+
+        #[test]
+        fn when_board_is_empty_drops_block_to_floor() {
+            let board = StandardBoard::new();
+            let mut block = ActiveBlock::new(BlockType::I);
+
+            let rows = board.drop_block(&mut block);
+
+            // I at its initial position occupies row 1.
+            assert_eq!(rows, StandardBoard::ROWS - 2);
+            assert!(
+                block
+                    .board_positions()
+                    .all(|(r, _)| r == StandardBoard::ROWS - 1)
+            );
+        }
+
+        #[test]
+        fn when_stack_is_below_block_drops_block_onto_stack() {
+            let mut board = StandardBoard::new();
+            board.0[StandardBoard::ROWS - 1][5] = Some(Cell::Block(BlockType::O));
+            let mut block = ActiveBlock::new(BlockType::I);
+
+            board.drop_block(&mut block);
+
+            assert!(
+                block
+                    .board_positions()
+                    .all(|(r, _)| r == StandardBoard::ROWS - 2)
+            );
+        }
+
+        #[test]
+        fn when_block_is_resting_does_not_move_block() {
+            let mut board = StandardBoard::new();
+            board.0[2] = [Some(Cell::Block(BlockType::O)); StandardBoard::COLUMNS];
+            let mut block = ActiveBlock::new(BlockType::I);
+            let before = block.clone();
+
+            assert_eq!(board.drop_block(&mut block), 0);
+            assert_eq!(block, before);
+        }
+    }
+
+    mod fix_active_block_tests {
+        use super::*;
+
+        #[test]
+        fn sets_block_positions_to_block_type() {
+            let mut board = StandardBoard::new();
+            let block = ActiveBlock::new(BlockType::I);
+
+            board.fix_active_block(&block);
+
+            // I at its initial position occupies (1, 3..=6).
+            let mut expected = StandardBoard::new();
+            expected.0[1][3] = Some(Cell::Block(BlockType::I));
+            expected.0[1][4] = Some(Cell::Block(BlockType::I));
+            expected.0[1][5] = Some(Cell::Block(BlockType::I));
+            expected.0[1][6] = Some(Cell::Block(BlockType::I));
+            assert_eq!(board, expected);
+        }
+
+        #[test]
+        fn locked_blocks_keep_their_types_through_line_clears() {
+            let mut board = StandardBoard::new();
+            for block_type in [BlockType::I, BlockType::J] {
+                let mut block = ActiveBlock::new(block_type);
+                board.drop_block(&mut block);
+                board.fix_active_block(&block);
+            }
+            let bottom = StandardBoard::ROWS - 1;
+            let i = Some(Cell::Block(BlockType::I));
+            let j = Some(Cell::Block(BlockType::J));
+            assert_eq!(board.0[bottom][3..7], [i; 4]);
+            assert_eq!(board.0[bottom - 1][4..7], [j; 3]);
+            assert_eq!(board.0[bottom - 2][4], j);
+
+            for c in (0..3).chain(7..StandardBoard::COLUMNS) {
+                board.0[bottom][c] = Some(Cell::Garbage);
+            }
+            board.clear_lines();
+
+            let mut expected = StandardBoard::new();
+            expected.0[bottom][4..7].fill(j);
+            expected.0[bottom - 1][4] = j;
+            assert_eq!(board, expected);
+        }
+    }
+
+    mod roughness_tests {
+        use super::*;
+
+        #[test]
+        fn when_board_is_empty_returns_0() {
+            assert_eq!(StandardBoard::new().roughness(), 0.0);
+        }
+
+        #[test]
+        fn when_stack_is_flat_without_holes_returns_0() {
+            let mut board = StandardBoard::new();
+            for row in &mut board.0[StandardBoard::ROWS - 3..] {
+                *row = [Some(Cell::Garbage); StandardBoard::COLUMNS];
+            }
+
+            assert_eq!(board.roughness(), 0.0);
+        }
+
+        #[test]
+        fn when_stack_is_jagged_with_holes_returns_1() {
+            let mut board = StandardBoard::new();
+            // Alternate empty columns with 8-high columns, each with two holes.
+            for c in (0..StandardBoard::COLUMNS).step_by(2) {
+                for row in &mut board.0[StandardBoard::ROWS - 8..StandardBoard::ROWS - 2] {
+                    row[c] = Some(Cell::Garbage);
+                }
+            }
+
+            assert_eq!(board.bumpiness(), 72);
+            assert_eq!(board.count_holes(), 10);
+            assert_eq!(board.roughness(), 1.0);
+        }
+
+        #[test]
+        fn when_stack_is_moderately_rough_returns_intermediate_score() {
+            let mut board = StandardBoard::new();
+            board.0[StandardBoard::ROWS - 1][0] = Some(Cell::Garbage);
+            board.0[StandardBoard::ROWS - 3][5] = Some(Cell::Garbage);
+
+            let roughness = board.roughness();
+
+            assert!(roughness > 0.0 && roughness < 1.0, "{roughness}");
+        }
+    }
+
+    mod surface_variance_tests {
+        use super::*;
+
+        #[test]
+        fn when_region_is_flat_returns_0() {
+            let mut board = StandardBoard::new();
+            for row in &mut board.0[StandardBoard::ROWS - 3..] {
+                row[2..6].fill(Some(Cell::Garbage));
+            }
+            // The region excludes the step down to the empty column 6.
+            assert_eq!(board.surface_variance(2, 6), Some(0.0));
+        }
+
+        #[test]
+        fn when_region_is_stepped_returns_variance_of_heights() {
+            let mut board = StandardBoard::new();
+            // Heights 0, 1, 2, 3 in columns 0 to 3.
+            for c in 1..4 {
+                for row in &mut board.0[StandardBoard::ROWS - c..] {
+                    row[c] = Some(Cell::Garbage);
+                }
+            }
+            assert_eq!(board.surface_variance(0, 4), Some(1.25));
+        }
+
+        #[test]
+        fn when_range_is_empty_returns_none() {
+            let board = StandardBoard::new();
+            assert_eq!(board.surface_variance(3, 3), None);
+            assert_eq!(board.surface_variance(4, 3), None);
+        }
+
+        #[test]
+        fn when_range_extends_beyond_the_board_returns_none() {
+            let board = StandardBoard::new();
+            assert_eq!(board.surface_variance(0, StandardBoard::COLUMNS + 1), None);
+        }
+
+        #[test]
+        fn when_range_spans_every_column_returns_some() {
+            let board = StandardBoard::new();
+            assert_eq!(board.surface_variance(0, StandardBoard::COLUMNS), Some(0.0));
+        }
+    }
+
+    mod deepest_well_column_tests {
+        use super::*;
+
+        #[test]
+        fn when_rightmost_column_is_left_open_returns_it() {
+            let mut board = StandardBoard::new();
+            for row in &mut board.0[StandardBoard::ROWS - 4..] {
+                *row = [Some(Cell::Garbage); StandardBoard::COLUMNS];
+                row[9] = None;
+            }
+            assert_eq!(board.deepest_well_column(), 9);
+        }
+
+        #[test]
+        fn when_interior_well_is_deepest_returns_it() {
+            let mut board = StandardBoard::new();
+            for row in &mut board.0[StandardBoard::ROWS - 4..] {
+                *row = [Some(Cell::Garbage); StandardBoard::COLUMNS];
+                row[3] = None;
+            }
+            board.0[StandardBoard::ROWS - 1][9] = None;
+            assert_eq!(board.deepest_well_column(), 3);
+        }
+
+        #[test]
+        fn when_board_is_flat_returns_rightmost_column() {
+            assert_eq!(
+                StandardBoard::new().deepest_well_column(),
+                StandardBoard::COLUMNS - 1
+            );
+        }
+    }
+
+    mod is_in_danger_tests {
+        use super::*;
+
+        #[test]
+        fn when_stack_is_below_threshold_returns_false() {
+            let mut board = StandardBoard::new();
+            board.0[StandardBoard::ROWS - 1] =
+                [Some(Cell::Block(BlockType::I)); StandardBoard::COLUMNS];
+            board.0[StandardBoard::ROWS - 2][0] = Some(Cell::Block(BlockType::I));
+            assert!(!board.is_in_danger(4));
+        }
+
+        #[test]
+        fn when_stack_reaches_threshold_returns_false() {
+            let mut board = StandardBoard::new();
+            // A stack 16 rows high leaves exactly 4 visible rows free.
+            for row in &mut board.0[StandardBoard::ROWS - 16..] {
+                row[3] = Some(Cell::Block(BlockType::I));
+            }
+            assert!(!board.is_in_danger(4));
+        }
+
+        #[test]
+        fn when_stack_crosses_threshold_returns_true() {
+            let mut board = StandardBoard::new();
+            for row in &mut board.0[StandardBoard::ROWS - 17..] {
+                row[3] = Some(Cell::Block(BlockType::I));
+            }
+            assert!(board.is_in_danger(4));
+        }
+    }
+
+    mod add_garbage_lines_tests {
+        use super::*;
+
+        #[test]
+        fn adds_garbage_lines_with_hole_to_bottom_of_board() {
+            let mut board = StandardBoard::new();
+
+            board.add_garbage_lines(2, 3);
+
+            let mut expected = StandardBoard::new();
+            for row in &mut expected.0[StandardBoard::ROWS - 2..] {
+                *row = [Some(Cell::Garbage); StandardBoard::COLUMNS];
+                row[3] = None;
+            }
+            assert_eq!(board, expected);
+        }
+
+        #[test]
+        fn pushes_existing_cells_upwards() {
+            let mut board = StandardBoard::new();
+            board.0[StandardBoard::ROWS - 1][0] = Some(Cell::Block(BlockType::T));
+
+            board.add_garbage_lines(1, 0);
+
+            assert_eq!(
+                board.0[StandardBoard::ROWS - 2][0],
+                Some(Cell::Block(BlockType::T))
+            );
+            assert_eq!(board.0[StandardBoard::ROWS - 1][0], None);
+        }
+
+        #[test]
+        fn when_count_exceeds_board_rows_fills_board_with_garbage() {
+            let mut board = StandardBoard::new();
+
+            board.add_garbage_lines(StandardBoard::ROWS + 1, 9);
+
+            assert!(
+                board
+                    .iter()
+                    .all(|row| row[9].is_none() && row[0] == Some(Cell::Garbage))
+            );
+        }
+    }
+
+    mod flood_fill_reachable_from_top_tests {
+        use super::*;
+
+        #[test]
+        fn when_board_is_empty_marks_every_cell_reachable() {
+            let reachable = StandardBoard::new().flood_fill_reachable_from_top();
+            assert!(reachable.iter().flatten().all(|&r| r));
+        }
+
+        #[test]
+        fn when_surface_is_open_marks_overhung_cells_reachable() {
+            let mut board = StandardBoard::new();
+            // An overhang with an open side.
+            board.0[StandardBoard::ROWS - 2][0] = Some(Cell::Garbage);
+            board.0[StandardBoard::ROWS - 2][1] = Some(Cell::Garbage);
+
+            let reachable = board.flood_fill_reachable_from_top();
+
+            assert!(reachable[StandardBoard::ROWS - 1][0]);
+            assert!(!reachable[StandardBoard::ROWS - 2][0]);
+        }
+
+        #[test]
+        fn when_cavity_is_sealed_marks_its_cells_unreachable() {
+            let mut board = StandardBoard::new();
+            board.0[StandardBoard::ROWS - 3] = [Some(Cell::Garbage); StandardBoard::COLUMNS];
+            board.0[StandardBoard::ROWS - 1][0] = Some(Cell::Garbage);
+
+            let reachable = board.flood_fill_reachable_from_top();
+
+            assert!(reachable[StandardBoard::ROWS - 4].iter().all(|&r| r));
+            assert!(reachable[StandardBoard::ROWS - 2].iter().all(|&r| !r));
+            assert!(reachable[StandardBoard::ROWS - 1][1..].iter().all(|&r| !r));
+        }
+    }
+
+    mod checksum_tests {
+        use super::*;
+
+        #[test]
+        fn when_boards_are_identical_returns_equal_checksums() {
+            let mut a = StandardBoard::new();
+            a.add_garbage_lines(3, 4);
+            let b = a.clone();
+
+            assert_eq!(a.checksum(), b.checksum());
+        }
+
+        #[test]
+        fn when_one_cell_differs_returns_different_checksums() {
+            let mut a = StandardBoard::new();
+            a.add_garbage_lines(3, 4);
+            let mut b = a.clone();
+            b.0[StandardBoard::ROWS - 1][4] = Some(Cell::Garbage);
+
+            assert_ne!(a.checksum(), b.checksum());
+        }
+
+        #[test]
+        fn when_occupied_cell_moves_returns_different_checksums() {
+            let mut a = StandardBoard::new();
+            a.0[StandardBoard::ROWS - 1][0] = Some(Cell::Garbage);
+            let mut b = StandardBoard::new();
+            b.0[StandardBoard::ROWS - 2][0] = Some(Cell::Garbage);
+
+            assert_ne!(a.checksum(), b.checksum());
+        }
+    }
+
+    mod render_with_buffer_tests {
+        use super::*;
+
+        #[test]
+        fn shows_block_in_buffer_zone_above_separator() {
+            let mut board = StandardBoard::new();
+            board.fix_active_block(&ActiveBlock::new(BlockType::O));
+            board.0[StandardBoard::ROWS - 1][0] = Some(Cell::Garbage);
+
+            let rendered = board.render_with_buffer();
+
+            let lines: Vec<&str> = rendered.lines().collect();
+            assert_eq!(lines.len(), StandardBoard::ROWS + 3);
+            assert_eq!(lines[0], "*——————————*");
+            assert_eq!(lines[1], "|    OO    |");
+            assert_eq!(lines[2], "|    OO    |");
+            assert_eq!(lines[3], "|——————————|");
+            assert_eq!(lines[StandardBoard::ROWS + 1], "|▒         |");
+        }
+
+        #[test]
+        fn display_omits_buffer_zone() {
+            let mut board = StandardBoard::new();
+            board.fix_active_block(&ActiveBlock::new(BlockType::O));
+
+            let rendered = board.to_string();
+
+            assert_eq!(rendered.lines().count(), StandardBoard::PLAYABLE_ROWS + 2);
+            assert!(!rendered.contains('O'));
+        }
+    }
+
+    mod with_block_tests {
+        use super::*;
+
+        fn occupied_count(board: &StandardBoard) -> usize {
+            board.iter().flatten().filter(|cell| cell.is_some()).count()
+        }
+
+        #[test]
+        fn when_block_is_inside_the_board_adds_four_cells() {
+            let mut board = StandardBoard::new();
+            board.0[StandardBoard::ROWS - 1][0] = Some(Cell::Garbage);
+            let block = ActiveBlock::new(BlockType::T);
+
+            let with_block = board.with_block(&block);
+
+            assert_eq!(occupied_count(&with_block), 5);
+            for (r, c) in block.board_positions() {
+                assert_eq!(with_block.0[r][c], Some(Cell::Block(BlockType::T)));
+            }
+            assert_eq!(occupied_count(&board), 1);
+        }
+
+        #[test]
+        fn when_block_overhangs_the_board_skips_cells_outside_it() {
+            let board = StandardBoard::new();
+            // The I block spawns at left=3; moved 4 right, its rightmost cell is past the wall.
+            let mut block = ActiveBlock::new(BlockType::I);
+            (0..4).for_each(|_| block.move_right());
+
+            assert_eq!(occupied_count(&board.with_block(&block)), 3);
+        }
+    }
+
+    mod add_random_garbage_lines_tests {
+        use rand::{SeedableRng, rngs::StdRng};
+
+        use super::*;
+
+        #[test]
+        fn adds_rows_with_exactly_one_hole() {
+            let mut board = StandardBoard::new();
+
+            board.add_random_garbage_lines(5, &mut StdRng::seed_from_u64(3));
+
+            for row in &board.0[StandardBoard::ROWS - 5..] {
+                assert_eq!(row.iter().filter(|cell| cell.is_none()).count(), 1);
+            }
+            assert!(!board.is_garbage_row(StandardBoard::ROWS - 6));
+        }
+
+        #[test]
+        fn when_seed_is_the_same_produces_the_same_board() {
+            let mut a = StandardBoard::new();
+            let mut b = StandardBoard::new();
+
+            a.add_random_garbage_lines(8, &mut StdRng::seed_from_u64(42));
+            b.add_random_garbage_lines(8, &mut StdRng::seed_from_u64(42));
+
+            assert_eq!(a, b);
+        }
+    }
+
+    mod occupied_cells_tests {
+        use super::*;
+
+        #[test]
+        fn when_board_is_empty_yields_nothing() {
+            assert_eq!(StandardBoard::new().occupied_cells().count(), 0);
+        }
+
+        #[test]
+        fn yields_the_position_of_every_occupied_square() {
+            let mut board = StandardBoard::new();
+            board.0[3][7] = Some(Cell::Garbage);
+            board.0[StandardBoard::ROWS - 1][0] = Some(Cell::Block(BlockType::I));
+            board.0[StandardBoard::ROWS - 1][9] = Some(Cell::Block(BlockType::Z));
+
+            let cells: Vec<Position> = board.occupied_cells().collect();
+
+            assert_eq!(
+                cells,
+                [
+                    (3, 7),
+                    (StandardBoard::ROWS - 1, 0),
+                    (StandardBoard::ROWS - 1, 9)
+                ]
+            );
+        }
+    }
+
+    mod bumpiness_tests {
+        use super::*;
+
+        #[test]
+        fn when_surface_is_flat_returns_zero() {
+            let mut board = StandardBoard::new();
+            board.0[StandardBoard::ROWS - 1] = [Some(Cell::Garbage); StandardBoard::COLUMNS];
+            assert_eq!(board.bumpiness(), 0);
+        }
+
+        #[test]
+        fn when_surface_is_a_staircase_returns_one_per_step() {
+            let mut board = StandardBoard::new();
+            for c in 0..StandardBoard::COLUMNS {
+                board.0[StandardBoard::ROWS - 1 - c][c] = Some(Cell::Garbage);
+            }
+            assert_eq!(board.bumpiness(), StandardBoard::COLUMNS - 1);
+        }
+
+        #[test]
+        fn when_one_column_spikes_counts_both_of_its_sides() {
+            let mut board = StandardBoard::new();
+            board.0[StandardBoard::ROWS - 6][4] = Some(Cell::Garbage);
+            assert_eq!(board.bumpiness(), 12);
+        }
+    }
+
+    mod count_holes_tests {
+        use super::*;
+
+        #[test]
+        fn when_column_is_cleanly_filled_returns_zero() {
+            let mut board = StandardBoard::new();
+            for row in &mut board.0[StandardBoard::ROWS - 4..] {
+                row[2] = Some(Cell::Garbage);
+            }
+            assert_eq!(board.count_holes(), 0);
+        }
+
+        #[test]
+        fn when_two_holes_are_stacked_in_one_column_counts_both() {
+            let mut board = StandardBoard::new();
+            board.0[StandardBoard::ROWS - 1][2] = Some(Cell::Garbage);
+            board.0[StandardBoard::ROWS - 4][2] = Some(Cell::Garbage);
+            assert_eq!(board.count_holes(), 2);
+        }
+
+        #[test]
+        fn counts_holes_under_an_overhang() {
+            let mut board = StandardBoard::new();
+            board.0[StandardBoard::ROWS - 3][5] = Some(Cell::Garbage);
+            board.0[StandardBoard::ROWS - 1][4] = Some(Cell::Garbage);
+            // Column 5 has two empty cells beneath its overhanging cell.
+            assert_eq!(board.count_holes(), 2);
+        }
+    }
+
+    mod column_heights_tests {
+        use super::*;
+
+        #[test]
+        fn when_board_is_empty_returns_zeroes() {
+            assert_eq!(
+                StandardBoard::new().column_heights(),
+                [0; StandardBoard::COLUMNS]
+            );
+        }
+
+        #[test]
+        fn returns_the_height_of_each_column_stack() {
+            let mut board = StandardBoard::new();
+            board.add_garbage_lines(2, 9);
+            board.0[StandardBoard::ROWS - 3][0] = Some(Cell::Garbage);
+            board.0[StandardBoard::ROWS - 5][4] = Some(Cell::Garbage);
+
+            let heights = board.column_heights();
+
+            assert_eq!(heights, [3, 2, 2, 2, 5, 2, 2, 2, 2, 0]);
+        }
+
+        #[test]
+        fn when_column_has_a_gap_measures_to_its_topmost_cell() {
+            let mut board = StandardBoard::new();
+            board.0[StandardBoard::ROWS - 1][3] = Some(Cell::Garbage);
+            board.0[StandardBoard::ROWS - 4][3] = Some(Cell::Garbage);
+
+            assert_eq!(board.column_heights()[3], 4);
+        }
+    }
+
+    mod is_empty_tests {
+        use super::*;
+
+        #[test]
+        fn when_board_is_new_returns_true() {
+            assert!(StandardBoard::new().is_empty());
+        }
+
+        #[test]
+        fn when_one_cell_is_occupied_returns_false() {
+            let mut board = StandardBoard::new();
+            board.0[0][StandardBoard::COLUMNS - 1] = Some(Cell::Garbage);
+            assert!(!board.is_empty());
+        }
+    }
+
+    mod is_blocked_tests {
+        use super::*;
+
+        #[test]
+        fn when_square_is_empty_returns_false() {
+            assert!(!StandardBoard::new().is_blocked(5, 5));
+        }
+
+        #[test]
+        fn when_square_is_occupied_returns_true() {
+            let mut board = StandardBoard::new();
+            board.0[5][5] = Some(Cell::Garbage);
+            assert!(board.is_blocked(5, 5));
+        }
+
+        #[test]
+        fn when_square_is_outside_the_board_returns_true() {
+            let board = StandardBoard::new();
+            assert!(board.is_blocked(5, -1));
+            assert!(board.is_blocked(5, StandardBoard::COLUMNS as isize));
+            assert!(board.is_blocked(StandardBoard::ROWS, 5));
+        }
+
+        #[test]
+        fn when_square_is_far_outside_the_board_returns_true() {
+            let board = StandardBoard::new();
+            assert!(board.is_blocked(5, isize::MIN));
+            assert!(board.is_blocked(5, isize::MAX));
+            assert!(board.is_blocked(usize::MAX, 5));
+        }
+
+        #[test]
+        fn when_square_is_in_the_buffer_zone_checks_its_occupancy() {
+            let mut board = StandardBoard::new();
+            assert!(!board.is_blocked(0, 0));
+
+            board.0[0][0] = Some(Cell::Garbage);
+            assert!(board.is_blocked(0, 0));
+        }
+
+        #[test]
+        fn when_square_is_in_the_last_row_or_column_checks_its_occupancy() {
+            let mut board = StandardBoard::new();
+            let (r, c) = (StandardBoard::ROWS - 1, StandardBoard::COLUMNS - 1);
+            assert!(!board.is_blocked(r, c as isize));
+
+            board.0[r][c] = Some(Cell::Garbage);
+            assert!(board.is_blocked(r, c as isize));
+        }
+    }
+
+    mod is_garbage_row_tests {
+        use super::*;
+
+        #[test]
+        fn identifies_inserted_garbage_rows() {
+            let mut board = StandardBoard::new();
+            board.0[StandardBoard::ROWS - 1][0] = Some(Cell::Block(BlockType::T));
+
+            board.add_garbage_lines(2, 5);
+
+            assert!(board.is_garbage_row(StandardBoard::ROWS - 1));
+            assert!(board.is_garbage_row(StandardBoard::ROWS - 2));
+            assert!(!board.is_garbage_row(StandardBoard::ROWS - 3));
+        }
+
+        #[test]
+        fn when_hole_is_filled_by_player_returns_true() {
+            let mut board = StandardBoard::new();
+            board.add_garbage_lines(1, 5);
+            board.0[StandardBoard::ROWS - 1][5] = Some(Cell::Block(BlockType::I));
+
+            assert!(board.is_garbage_row(StandardBoard::ROWS - 1));
+        }
+
+        #[test]
+        fn when_row_is_empty_returns_false() {
+            assert!(!StandardBoard::new().is_garbage_row(StandardBoard::ROWS - 1));
+        }
+
+        #[test]
+        fn when_index_is_out_of_range_returns_false() {
+            let mut board = StandardBoard::new();
+            board.add_garbage_lines(StandardBoard::PLAYABLE_ROWS, 5);
+
+            assert!(!board.is_garbage_row(StandardBoard::ROWS));
+            assert!(!board.is_garbage_row(usize::MAX));
+        }
+    }
+
+    mod buffer_zone_occupied_tests {
+        use super::*;
+
+        #[test]
+        fn when_buffer_zone_row_1_is_occupied_returns_true() {
+            let mut board = StandardBoard::new();
+            board.0[1][0] = Some(Cell::Block(BlockType::I));
+            assert!(board.buffer_zone_occupied());
         }
 
         #[test]
         fn when_buffer_zone_is_empty_returns_false() {
-            let board = Board::new();
+            let board = StandardBoard::new();
             assert!(!board.buffer_zone_occupied());
         }
     }
+
+    mod flip_vertical_tests {
+        use super::*;
+
+        #[test]
+        fn reverses_row_order() {
+            let mut board = StandardBoard::new();
+            board.0[0][0] = Some(Cell::Block(BlockType::I));
+            board.0[StandardBoard::ROWS - 1][9] = Some(Cell::Block(BlockType::O));
+
+            let flipped = board.flip_vertical();
+
+            assert_eq!(
+                flipped.0[StandardBoard::ROWS - 1][0],
+                Some(Cell::Block(BlockType::I))
+            );
+            assert_eq!(flipped.0[0][9], Some(Cell::Block(BlockType::O)));
+            assert!(flipped.0[0][0].is_none());
+        }
+
+        #[test]
+        fn when_flipped_twice_returns_original() {
+            let mut board = StandardBoard::new();
+            board.add_garbage_lines(3, 2);
+            board.0[5][4] = Some(Cell::Block(BlockType::T));
+
+            assert_eq!(board.flip_vertical().flip_vertical(), board);
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    mod serde_tests {
+        use serde::Deserialize;
+        use serde::de::value::{Error, SeqDeserializer};
+
+        use super::*;
+
+        fn deserialize(rows: Vec<String>) -> Result<Board<3, 4>, Error> {
+            Board::deserialize(SeqDeserializer::new(rows.into_iter()))
+        }
+
+        #[test]
+        fn every_kind_of_square_round_trips_through_its_encoding() {
+            let squares = [
+                None,
+                Some(Cell::Garbage),
+                Some(Cell::Block(BlockType::I)),
+                Some(Cell::Block(BlockType::Z)),
+                Some(Cell::Block(BlockType::Custom(0))),
+                Some(Cell::Block(BlockType::Custom(BlockType::MAX_CUSTOM - 1))),
+            ];
+            for square in squares {
+                assert_eq!(decode_square(encode_square(&square)), Ok(square));
+            }
+        }
+
+        #[test]
+        fn deserializes_one_string_per_row() {
+            let rows = vec!["....".to_string(), ".T..".to_string(), "▒▒.▒".to_string()];
+
+            let board = deserialize(rows).unwrap();
+
+            assert_eq!(board.0[1][1], Some(Cell::Block(BlockType::T)));
+            assert_eq!(
+                board.0[2],
+                [
+                    Some(Cell::Garbage),
+                    Some(Cell::Garbage),
+                    None,
+                    Some(Cell::Garbage)
+                ]
+            );
+        }
+
+        #[test]
+        fn when_row_count_is_wrong_returns_error() {
+            assert!(deserialize(vec!["....".to_string(); 2]).is_err());
+        }
+
+        #[test]
+        fn when_row_width_is_wrong_returns_error() {
+            assert!(deserialize(vec!["...".to_string(); 3]).is_err());
+        }
+
+        #[test]
+        fn when_square_is_unrecognized_returns_error() {
+            assert!(deserialize(vec!["..x.".to_string(); 3]).is_err());
+        }
+
+        #[test]
+        fn board_round_trips_through_json() {
+            let mut board = StandardBoard::new();
+            board.0[StandardBoard::ROWS - 1][..4].fill(Some(Cell::Block(BlockType::I)));
+
+            let json = serde_json::to_string(&board).unwrap();
+
+            assert_eq!(serde_json::from_str::<StandardBoard>(&json).unwrap(), board);
+        }
+
+        #[test]
+        fn board_with_garbage_and_custom_cells_round_trips_through_json() {
+            let mut board = StandardBoard::new();
+            board.add_garbage_lines(2, 3);
+            board.0[StandardBoard::ROWS - 3][0] = Some(Cell::Block(BlockType::Custom(0)));
+
+            let json = serde_json::to_string(&board).unwrap();
+
+            assert_eq!(serde_json::from_str::<StandardBoard>(&json).unwrap(), board);
+        }
+    }
+
+    mod sized_board_tests {
+        use super::*;
+
+        type SmallBoard = Board<6, 4>;
+
+        #[test]
+        fn small_board_consolidates_rows_above_cleared_line() {
+            let i = Some(Cell::Block(BlockType::I));
+            let mut board = SmallBoard::from([
+                [None; 4],
+                [None; 4],
+                [None; 4],
+                [i, None, None, None],
+                [i; 4],
+                [None, i, None, i],
+            ]);
+            let expected_board = SmallBoard::from([
+                [None; 4],
+                [None; 4],
+                [None; 4],
+                [None; 4],
+                [i, None, None, None],
+                [None, i, None, i],
+            ]);
+
+            let lines_cleared = board.clear_lines();
+
+            assert_eq!(lines_cleared, 1);
+            assert_eq!(
+                expected_board, board,
+                "Cleared board did not match expected board:\nExpected:\n{}\nActual:\n{}",
+                expected_board, board
+            )
+        }
+
+        #[test]
+        fn small_board_dimensions_follow_const_parameters() {
+            assert_eq!(SmallBoard::ROWS, 6);
+            assert_eq!(SmallBoard::COLUMNS, 4);
+            assert_eq!(SmallBoard::PLAYABLE_ROWS, 4);
+        }
+
+        #[test]
+        fn small_board_display_renders_playable_rows_at_its_width() {
+            let mut board = SmallBoard::new();
+            board.0[SmallBoard::ROWS - 1][0] = Some(Cell::Garbage);
+
+            let rendered = board.to_string();
+
+            let lines: Vec<&str> = rendered.lines().collect();
+            assert_eq!(lines.len(), SmallBoard::PLAYABLE_ROWS + 2);
+            assert_eq!(lines[0], "*————*");
+            assert_eq!(lines[SmallBoard::PLAYABLE_ROWS], "|▒   |");
+        }
+
+        #[test]
+        fn standard_board_keeps_twenty_by_ten_playfield() {
+            assert_eq!(StandardBoard::PLAYABLE_ROWS, 20);
+            assert_eq!(StandardBoard::COLUMNS, 10);
+            assert_eq!(StandardBoard::ROWS, 22);
+        }
+    }
 }
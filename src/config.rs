@@ -1,5 +1,10 @@
+use std::collections::HashMap;
 use std::time::Duration;
 
+use crate::block::BlockType;
+use crate::board::StandardBoard;
+use crate::gravity;
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 /// Gravity configuration.
 pub struct Gravity {
@@ -7,8 +12,10 @@ pub struct Gravity {
     initial_ticks: u64,
     /// The minimum allowable ticks between applications of gravity.
     min_ticks: u64,
-    /// The amount by which gravity is reduced when the associated score threshold is passed.
+    /// The amount by which the ticks between applications of gravity are reduced with each level.
     acceleration: u64,
+    /// When true, the ticks between applications of gravity follow the classic curve instead.
+    classic: bool,
 }
 
 impl Gravity {
@@ -29,9 +36,22 @@ impl Gravity {
             initial_ticks,
             min_ticks,
             acceleration,
+            classic: false,
         })
     }
 
+    /// Gravity that follows the NES gravity curve of [gravity::gravity_ticks_for_level], which
+    /// assumes a frame interval of 1/60 of a second. The curve has no constant acceleration, so
+    /// [Self::acceleration] is zero.
+    pub fn classic() -> Self {
+        Self {
+            initial_ticks: gravity::gravity_ticks_for_level(0),
+            min_ticks: gravity::gravity_ticks_for_level(gravity::MAX_LEVEL),
+            acceleration: 0,
+            classic: true,
+        }
+    }
+
     pub fn initial_ticks(&self) -> u64 {
         self.initial_ticks
     }
@@ -43,6 +63,81 @@ impl Gravity {
     pub fn acceleration(&self) -> u64 {
         self.acceleration
     }
+
+    /// Returns the number of ticks between applications of gravity at `level`: the initial ticks
+    /// reduced by the acceleration for each level, down to the minimum, or the classic curve's
+    /// ticks for the level.
+    pub fn ticks_for_level(&self, level: u32) -> u64 {
+        if self.classic {
+            return gravity::gravity_ticks_for_level(level);
+        }
+        self.initial_ticks
+            .saturating_sub(self.acceleration.saturating_mul(level as u64))
+            .max(self.min_ticks)
+    }
+}
+
+/// Garbage that fills the bottom of the board before play begins.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct GarbageStart {
+    /// The number of rows of garbage.
+    lines: usize,
+    /// The seed used to place the hole in each row, so that the same seed always produces the
+    /// same board.
+    seed: u64,
+}
+
+impl GarbageStart {
+    /// The most rows of garbage a game can start with. More rows are left clear than the tallest
+    /// block, a vertical I, needs, so the first blocks can be placed below the spawn rows without
+    /// topping out.
+    pub const MAX_LINES: usize = StandardBoard::PLAYABLE_ROWS - 5;
+
+    pub fn new(lines: usize, seed: u64) -> Result<Self, String> {
+        if lines > Self::MAX_LINES {
+            return Err(format!(
+                "lines cannot be greater than {}: lines={lines}",
+                Self::MAX_LINES
+            ));
+        }
+
+        Ok(Self { lines, seed })
+    }
+
+    pub fn lines(&self) -> usize {
+        self.lines
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+}
+
+/// How cells above a cleared line fall once the line is removed.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum GravityMode {
+    /// Every row above a cleared line shifts down by one row per line cleared, even if that
+    /// leaves cells floating.
+    #[default]
+    Naive,
+
+    /// Each connected group of cells falls until it comes to rest, which may complete further
+    /// lines in a chain.
+    Cascade,
+}
+
+/// The condition under which a game is won, in addition to the usual loss by topping out.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum GameMode {
+    /// Play continues until the stack tops out.
+    #[default]
+    Marathon,
+
+    /// The game is complete once the given number of lines have been cleared.
+    Sprint { lines: u32 },
+
+    /// The game is complete once the given duration of play has elapsed.
+    Ultra { duration: Duration },
 }
 
 /// Game configuration.
@@ -54,11 +149,147 @@ pub struct Config {
     /// Gravity config.
     pub gravity: Gravity,
 
-    /// The number of points that must accumulate before gravity is increased.
-    pub accelerate_every_n_points: u32,
+    /// How the stack settles after lines are cleared.
+    pub gravity_mode: GravityMode,
+
+    /// When true, each application of gravity drops the active block as far as it will go (20G)
+    /// rather than moving it down one row, and the block's lock delay begins on the tick it lands.
+    /// Can be changed during play with [Game::set_instant_gravity](crate::Game::set_instant_gravity).
+    pub instant_gravity: bool,
 
     /// The number of game ticks that must elapse between input reads.
     pub input_ticks: u64,
+
+    /// The number of game ticks that must elapse between applications of gravity while soft drop
+    /// is held. Typically much smaller than the gravity ticks.
+    pub soft_drop_ticks: u64,
+
+    /// The number of game ticks a block may rest on the stack before it locks, giving the player
+    /// a window to slide or rotate it. Zero locks a block on the gravity tick that grounds it.
+    pub lock_delay_ticks: u64,
+
+    /// Garbage to fill the bottom of the board with at the start of each round, if any.
+    pub initial_garbage_lines: Option<GarbageStart>,
+
+    /// The number of upcoming blocks generated ahead of the active block and shown in the queue.
+    pub preview_count: usize,
+
+    /// When true, anything that would top out the stack is undone instead of ending the game: a
+    /// fatal landing is removed from the board and the block respawned so the player can retry it,
+    /// a hold that would spawn into the stack is refused, and garbage that would top out is
+    /// discarded.
+    pub practice_mode: bool,
+
+    /// The condition that completes the game, such as a line goal or a time limit.
+    pub mode: GameMode,
+
+    /// The column of the top-left corner of each block type's bounding box when it spawns,
+    /// overriding the default of roughly centering the block. [Game](crate::Game) refuses to
+    /// start with a column that places any of the block's cells outside the board.
+    pub spawn_columns: HashMap<BlockType, isize>,
+
+    /// The block in the hold slot at the start of each round, such as for puzzles that begin with
+    /// a piece held. A custom piece must be registered with the game's
+    /// [BlockGenerator](crate::BlockGenerator).
+    pub initial_held_block: Option<BlockType>,
+
+    /// The points awarded per combo step at level 0, multiplied by the combo count and by one
+    /// more than the level. Zero disables the combo bonus.
+    pub combo_points: u32,
+}
+
+impl Config {
+    /// Returns an error if the fields are inconsistent, such as a soft drop that never applies
+    /// gravity. [Game](crate::Game) checks its config when it's constructed.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.soft_drop_ticks == 0 {
+            return Err("soft_drop_ticks must be greater than zero".to_string());
+        }
+        Ok(())
+    }
+}
+
+impl Default for Config {
+    /// 60 ticks per second with gravity speeding up from every 48 ticks to every 12, a
+    /// half-second lock delay and three blocks of preview.
+    fn default() -> Self {
+        Self {
+            frame_interval: Duration::from_secs_f32(1.0 / 60.0),
+            gravity: Gravity {
+                initial_ticks: 48,
+                min_ticks: 12,
+                acceleration: 4,
+                classic: false,
+            },
+            gravity_mode: GravityMode::Naive,
+            instant_gravity: false,
+            input_ticks: 1,
+            soft_drop_ticks: 1,
+            lock_delay_ticks: 30,
+            initial_garbage_lines: None,
+            preview_count: 3,
+            practice_mode: false,
+            mode: GameMode::Marathon,
+            spawn_columns: HashMap::new(),
+            initial_held_block: None,
+            combo_points: 50,
+        }
+    }
+}
+
+#[cfg(test)]
+mod config_tests {
+    use super::*;
+
+    mod validate_tests {
+        use super::*;
+
+        #[test]
+        fn when_soft_drop_ticks_is_zero_returns_err() {
+            let config = Config {
+                soft_drop_ticks: 0,
+                ..Config::default()
+            };
+            assert!(config.validate().is_err())
+        }
+
+        #[test]
+        fn when_config_is_default_returns_ok() {
+            assert_eq!(Config::default().validate(), Ok(()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod garbage_start_tests {
+    use super::*;
+
+    mod new_tests {
+        use super::*;
+
+        #[test]
+        fn when_lines_fill_playable_rows_returns_err() {
+            let res = GarbageStart::new(StandardBoard::PLAYABLE_ROWS, 0);
+            assert!(res.is_err())
+        }
+
+        #[test]
+        fn when_lines_leave_only_four_playable_rows_clear_returns_err() {
+            let res = GarbageStart::new(StandardBoard::PLAYABLE_ROWS - 4, 0);
+            assert!(res.is_err())
+        }
+
+        #[test]
+        fn when_lines_leave_five_playable_rows_clear_returns_ok() {
+            let res = GarbageStart::new(StandardBoard::PLAYABLE_ROWS - 5, 7);
+            let expected = Ok(GarbageStart {
+                lines: StandardBoard::PLAYABLE_ROWS - 5,
+                seed: 7,
+            });
+
+            assert_eq!(res, expected)
+        }
+    }
 }
 
 #[cfg(test)]
@@ -87,9 +318,33 @@ mod gravity_tests {
                 initial_ticks: 1,
                 min_ticks: 1,
                 acceleration: 1,
+                classic: false,
             });
 
             assert_eq!(res, expected)
         }
     }
+    mod ticks_for_level_tests {
+        use super::*;
+
+        #[test]
+        fn when_gravity_is_linear_reduces_ticks_per_level_until_min_ticks() {
+            let gravity = Gravity::new(10, 4, 3).unwrap();
+            assert_eq!(gravity.ticks_for_level(0), 10);
+            assert_eq!(gravity.ticks_for_level(1), 7);
+            assert_eq!(gravity.ticks_for_level(2), 4);
+            assert_eq!(gravity.ticks_for_level(3), 4);
+        }
+
+        #[test]
+        fn when_gravity_is_classic_follows_the_curve() {
+            let gravity = Gravity::classic();
+            for level in [0, 9, 19, gravity::MAX_LEVEL] {
+                assert_eq!(
+                    gravity.ticks_for_level(level),
+                    gravity::gravity_ticks_for_level(level)
+                );
+            }
+        }
+    }
 }
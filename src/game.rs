@@ -1,20 +1,39 @@
-use std::collections::VecDeque;
-use std::io;
+use std::collections::{HashMap, VecDeque, hash_map::Entry};
+use std::iter;
 use std::time::Duration;
+use std::{fmt, io};
+#[cfg(feature = "serde")]
+use std::{fs, path::Path};
 
+use rand::{SeedableRng, rngs::StdRng};
 use rand_distr::{Distribution, Uniform};
 
 use crate::block_generator::BlockGenerator;
-use crate::config::Config;
+use crate::config::{Config, GameMode};
 use crate::input::{Input, PollInput};
+use crate::placement::{self, Placement};
 use crate::timer::{Clock, GameTimer, SystemClock, Tick};
 use crate::{
-    block::{ActiveBlock, BlockType},
-    board::Board,
+    block::{ActiveBlock, BlockType, CustomPieces, KickClass, Position, RotationIndex, TopLeft},
+    board::{Cell, StandardBoard},
 };
 
-/// The maxiumum number of blocks that may be queued.
-const QUEUE_LEN: usize = 3;
+/// The number of lines that must be cleared to advance a level.
+const LINES_PER_LEVEL: u32 = 10;
+
+/// The number of times moving or rotating a grounded block may restart its lock delay before the
+/// block locks regardless.
+const MAX_LOCK_DELAY_RESETS: u32 = 15;
+
+/// The percentage of the usual line clear points awarded for a difficult clear that immediately
+/// follows another.
+const BACK_TO_BACK_MULTIPLIER_PERCENT: u32 = 150;
+
+/// The points awarded for each row a block is soft dropped.
+const SOFT_DROP_POINTS_PER_ROW: u32 = 1;
+
+/// The points awarded for each row a block falls when hard dropped.
+const HARD_DROP_POINTS_PER_ROW: u32 = 2;
 
 /// A direction of movement or rotation.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
@@ -23,19 +42,359 @@ enum Direction {
     Right,
 }
 
+/// A callback invoked with the top-left coordinates of the [ActiveBlock] before and after each
+/// application of gravity.
+struct GravityListener(Box<dyn FnMut(TopLeft, TopLeft)>);
+
+impl fmt::Debug for GravityListener {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("GravityListener")
+    }
+}
+
+/// A callback invoked with the tick number and the input each time an input is polled from the
+/// game's input source.
+struct InputListener(Box<dyn FnMut(u64, Input)>);
+
+impl fmt::Debug for InputListener {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("InputListener")
+    }
+}
+
 /// A game of Tetrust.
 #[derive(Debug)]
 pub struct Game<I, C = SystemClock, S = Uniform<u8>> {
     clock: C,
     config: Config,
     score: u32,
-    board: Board,
+    level: u32,
+    lines_cleared_total: u32,
+
+    // The number of consecutive line-clearing locks after the first, or -1 if the last lock
+    // cleared nothing.
+    combo: i32,
+
+    // True if the last line clear was difficult, so the next difficult clear earns a bonus.
+    back_to_back: bool,
+    board: StandardBoard,
     block_generator: BlockGenerator<S>,
     active_block: ActiveBlock,
     queue: VecDeque<BlockType>,
     game_over: bool,
+
+    // The block stashed by the player, if any.
+    held_block: Option<BlockType>,
+
+    // True once the player has held a block, until the active block locks.
+    hold_used: bool,
+
+    // The ticks remaining before the grounded active block locks, or None if it isn't grounded.
+    lock_delay: Option<u64>,
+
+    // The number of times the active block's lock delay has been restarted by the player.
+    lock_delay_resets: u32,
+
     timer: GameTimer<C>,
+
+    // The time played since the game began or was last reset, summed over the intervals of the
+    // ticks that drove it.
+    elapsed: Duration,
+
     input: I,
+    gravity_listener: Option<GravityListener>,
+    input_listener: Option<InputListener>,
+
+    // Garbage lines earned by clearing lines, which have yet to be sent to an opponent.
+    outgoing_garbage: u32,
+
+    // The outcome of the most recent block landing.
+    last_settle_report: Option<SettleReport>,
+
+    // Counts of the blocks placed and lines cleared this game.
+    statistics: Statistics,
+
+    // The (old, new) positions of cells that changed rows when lines were last cleared.
+    last_consolidation_moves: Vec<(Position, Position)>,
+
+    // The last action that successfully changed the position or orientation of the active block.
+    last_action: LastAction,
+
+    // The wall kick offset applied by the last successful rotation of the active block.
+    last_kick: Option<(isize, isize)>,
+}
+
+/// The kinds of action that can successfully change the [ActiveBlock]. Spins are only awarded
+/// when a block locks straight after a rotation.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum LastAction {
+    /// The block hasn't moved since it spawned.
+    #[default]
+    None,
+
+    /// The block was shifted sideways or moved down, by gravity or the player.
+    Move,
+
+    /// The block was rotated, with or without a wall kick.
+    Rotate,
+}
+
+/// Whether a T block was spun into place as it locked, judged by the four corners of its 3x3
+/// bounding box.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum TSpin {
+    /// The block wasn't a T, its last action wasn't a rotation, or fewer than three corners were
+    /// blocked.
+    #[default]
+    None,
+
+    /// Three corners were blocked, but not both corners beside the T's point.
+    Mini,
+
+    /// Three corners were blocked, including both corners beside the T's point.
+    Full,
+}
+
+/// The outcome of fixing a block to the board, for frontends to report to the player.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SettleReport {
+    /// The number of lines cleared by the block.
+    pub lines_cleared: u8,
+
+    /// True if the clear left the board empty.
+    pub perfect_clear: bool,
+
+    /// The number of points added to the score.
+    pub points_awarded: u32,
+
+    /// The combo count after the lock, or -1 if the block cleared no lines.
+    pub combo: i32,
+
+    /// True if the clear continued a back-to-back chain and earned its bonus.
+    pub back_to_back: bool,
+
+    /// The last successful action before the block locked, which determines whether the landing
+    /// counts as a spin.
+    pub last_action: LastAction,
+
+    /// The kind of T-spin the block locked with, if any.
+    pub t_spin: TSpin,
+}
+
+/// Counts of the blocks placed and lines cleared over a game, for players and bots to review.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(from = "SavedStatistics", into = "SavedStatistics")
+)]
+pub struct Statistics {
+    pieces: HashMap<BlockType, u32>,
+    lines: u32,
+}
+
+/// The serialized form of [Statistics]. Custom block types can't be used as keys in formats such
+/// as JSON, so the piece counts are stored as (block type, count) pairs.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct SavedStatistics {
+    pieces: Vec<(BlockType, u32)>,
+    lines: u32,
+}
+
+#[cfg(feature = "serde")]
+impl From<Statistics> for SavedStatistics {
+    fn from(statistics: Statistics) -> Self {
+        Self {
+            pieces: statistics.pieces.into_iter().collect(),
+            lines: statistics.lines,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<SavedStatistics> for Statistics {
+    fn from(saved: SavedStatistics) -> Self {
+        Self {
+            pieces: saved.pieces.into_iter().collect(),
+            lines: saved.lines,
+        }
+    }
+}
+
+impl Statistics {
+    /// Returns the number of blocks of the given type locked to the board.
+    pub fn pieces_of(&self, block_type: BlockType) -> u32 {
+        self.pieces.get(&block_type).copied().unwrap_or(0)
+    }
+
+    /// Returns the total number of blocks locked to the board.
+    pub fn total_pieces(&self) -> u32 {
+        self.pieces.values().sum()
+    }
+
+    /// Returns the total number of lines cleared.
+    pub fn lines(&self) -> u32 {
+        self.lines
+    }
+
+    fn record_lock(&mut self, block_type: BlockType, lines_cleared: u8) {
+        *self.pieces.entry(block_type).or_default() += 1;
+        self.lines += u32::from(lines_cleared);
+    }
+}
+
+/// The outcome of applying a batch of inputs with [Game::step].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct StepReport {
+    /// The number of lines cleared by the inputs.
+    pub lines_cleared: u32,
+
+    /// True if the game was over after the last input.
+    pub game_over: bool,
+}
+
+/// The compact subset of a [Game]'s state shown in the HUD, for syncing to remote displays.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HudState {
+    /// The upcoming blocks, in the order they will be played.
+    pub queue: Vec<BlockType>,
+
+    /// The block stashed by the player, if any.
+    pub hold: Option<BlockType>,
+
+    /// The current score.
+    pub score: u32,
+
+    /// The current level.
+    pub level: u32,
+
+    /// The total number of lines cleared this game.
+    pub lines: u32,
+
+    /// The checksum of the board, for detecting when remote copies of the game have diverged.
+    pub board_checksum: u64,
+}
+
+/// A copy of the parts of a [Game]'s state that determine how play continues, for checking that
+/// replays and remote copies of a game haven't diverged.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameSnapshot {
+    score: u32,
+    combo: i32,
+    back_to_back: bool,
+    game_over: bool,
+    board: StandardBoard,
+    active_positions: Vec<Position>,
+    queue: Vec<BlockType>,
+}
+
+impl GameSnapshot {
+    /// Describes each difference between this snapshot and `other`, one per line, in the form
+    /// `field: this -> other`. Returns an empty string if the snapshots are equal.
+    pub fn diff(&self, other: &GameSnapshot) -> String {
+        let mut lines = Vec::new();
+        if self.score != other.score {
+            lines.push(format!("score: {} -> {}", self.score, other.score));
+        }
+        if self.combo != other.combo {
+            lines.push(format!("combo: {} -> {}", self.combo, other.combo));
+        }
+        if self.back_to_back != other.back_to_back {
+            lines.push(format!(
+                "back-to-back: {} -> {}",
+                self.back_to_back, other.back_to_back
+            ));
+        }
+        if self.game_over != other.game_over {
+            lines.push(format!(
+                "game over: {} -> {}",
+                self.game_over, other.game_over
+            ));
+        }
+
+        let describe = |cell: Option<Cell>| cell.map_or("empty".to_string(), |c| format!("{c:?}"));
+        for (r, (row, other_row)) in self.board.iter().zip(other.board.iter()).enumerate() {
+            for (c, (cell, other_cell)) in row.iter().zip(other_row).enumerate() {
+                if cell != other_cell {
+                    lines.push(format!(
+                        "board cell ({r}, {c}): {} -> {}",
+                        describe(*cell),
+                        describe(*other_cell)
+                    ));
+                }
+            }
+        }
+
+        if self.active_positions != other.active_positions {
+            lines.push(format!(
+                "active block: {:?} -> {:?}",
+                self.active_positions, other.active_positions
+            ));
+        }
+        if self.queue != other.queue {
+            lines.push(format!("queue: {:?} -> {:?}", self.queue, other.queue));
+        }
+        lines.join("\n")
+    }
+}
+
+/// The parts of a [Game]'s state needed to resume it later, such as from a saved game. The input
+/// source, clock and [BlockGenerator] aren't included and must be supplied when resuming, so a
+/// resumed game deals different blocks after its saved queue than the original would have.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SavedGame {
+    score: u32,
+    level: u32,
+    lines_cleared_total: u32,
+    combo: i32,
+    back_to_back: bool,
+    statistics: Statistics,
+    ticks: u64,
+    elapsed: Duration,
+    board: StandardBoard,
+    active_block: ActiveBlock,
+    queue: Vec<BlockType>,
+    held_block: Option<BlockType>,
+    hold_used: bool,
+    game_over: bool,
+}
+
+/// The reasons a [Game] may fail to start.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StartError {
+    /// The [Config] failed [Config::validate], named an unregistered custom piece or placed a
+    /// block outside the board, for the given reason.
+    InvalidConfig(String),
+
+    /// The first block could not be placed in its spawn position.
+    GameOverAtStart,
+}
+
+impl fmt::Display for StartError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StartError::InvalidConfig(reason) => write!(f, "invalid config: {reason}"),
+            StartError::GameOverAtStart => write!(f, "the first block could not be spawned"),
+        }
+    }
+}
+
+impl<I, C, S> fmt::Display for Game<I, C, S> {
+    /// Renders the visible rows of the board with the active block's cells drawn as `@`, followed
+    /// by the score and the upcoming blocks.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.board.write_highlighted(f, &self.active_positions())?;
+        writeln!(f, "Score: {}", self.score)?;
+        let queue: Vec<String> = self
+            .queue()
+            .iter()
+            .map(|b| b.symbol().to_string())
+            .collect();
+        write!(f, "Next: {}", queue.join(" "))
+    }
 }
 
 pub enum UpdateOutcome {
@@ -50,286 +409,5040 @@ impl<I, C, S> Game<I, C, S> {
         self.score
     }
 
-    /// Returns true is the game is over, at which point no further events will be handled.
-    pub fn game_over(&self) -> bool {
-        self.game_over
-    }
+    /// Returns the current level, starting from 0. Points for clearing lines are multiplied by
+    /// one more than the level.
+    pub fn level(&self) -> u32 {
+        self.level
+    }
+
+    /// Returns the total number of lines cleared this game.
+    pub fn lines_cleared_total(&self) -> u32 {
+        self.lines_cleared_total
+    }
+
+    /// Returns the counts of blocks placed and lines cleared this game.
+    pub fn statistics(&self) -> &Statistics {
+        &self.statistics
+    }
+
+    /// Returns true if the last line clear was a difficult one, such as a Tetris, in which case
+    /// the next difficult clear scores a back-to-back bonus.
+    pub fn back_to_back(&self) -> bool {
+        self.back_to_back
+    }
+
+    /// Returns the number of consecutive line-clearing locks after the first, or -1 if the last
+    /// lock cleared no lines. Each combo step is worth a bonus.
+    pub fn combo(&self) -> i32 {
+        self.combo
+    }
+
+    /// Returns true is the game is over, at which point no further events will be handled.
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use tetrust::block_generator::BlockGenerator;
+    /// use tetrust::config::{Config, Gravity};
+    /// use tetrust::game::{Game, UpdateOutcome};
+    /// use tetrust::input::{Input, ScriptedInput};
+    ///
+    /// let config = Config {
+    ///     frame_interval: Duration::from_millis(1),
+    ///     gravity: Gravity::new(1, 1, 0)?,
+    ///     ..Config::default()
+    /// };
+    /// // Hard drop every block until the stack tops out.
+    /// let input = ScriptedInput::new([Input::HardDrop; 20]);
+    /// let mut game = Game::new(BlockGenerator::seeded(7), input, config);
+    ///
+    /// while !game.game_over() {
+    ///     if let UpdateOutcome::Quit = game.update().map_err(|e| e.to_string())? {
+    ///         break;
+    ///     }
+    /// }
+    /// println!("final score: {}", game.score());
+    /// # Ok::<(), String>(())
+    /// ```
+    pub fn game_over(&self) -> bool {
+        self.game_over
+    }
+
+    /// Returns the current block queue as a contiguous slice.
+    pub fn queue(&self) -> &[BlockType] {
+        let (front, back) = self.queue.as_slices();
+        debug_assert_eq!(
+            back.len(),
+            0,
+            "Back slice of block queue was non-empty. This indicates that the queue wasn't made contiguous after inserting a new block.",
+        );
+        front
+    }
+
+    /// Returns up to the next `n` blocks in the queue, in the order they will be played, without
+    /// drawing any new blocks.
+    pub fn peek_next(&self, n: usize) -> &[BlockType] {
+        let queue = self.queue();
+        &queue[..n.min(queue.len())]
+    }
+
+    /// Captures the current state of the game for later comparison.
+    pub fn snapshot(&self) -> GameSnapshot {
+        GameSnapshot {
+            score: self.score,
+            combo: self.combo,
+            back_to_back: self.back_to_back,
+            game_over: self.game_over,
+            board: self.board.clone(),
+            active_positions: self.active_positions(),
+            queue: self.queue().to_vec(),
+        }
+    }
+
+    /// Captures the state needed to resume the game later with [Game::resume].
+    pub fn save_state(&self) -> SavedGame {
+        SavedGame {
+            score: self.score,
+            level: self.level,
+            lines_cleared_total: self.lines_cleared_total,
+            combo: self.combo,
+            back_to_back: self.back_to_back,
+            statistics: self.statistics.clone(),
+            ticks: self.timer.tick_count(),
+            elapsed: self.elapsed,
+            board: self.board.clone(),
+            active_block: self.active_block.clone(),
+            queue: self.queue().to_vec(),
+            held_block: self.held_block,
+            hold_used: self.hold_used,
+            game_over: self.game_over,
+        }
+    }
+
+    /// Writes the game's [SavedGame] state to the file at `path` as JSON, replacing any existing
+    /// file.
+    #[cfg(feature = "serde")]
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let json = serde_json::to_string_pretty(&self.save_state()).map_err(io::Error::other)?;
+        fs::write(path, json)
+    }
+
+    /// Returns the block stashed by the player, if any.
+    pub fn held_block(&self) -> Option<BlockType> {
+        self.held_block
+    }
+
+    /// Returns the upcoming and held blocks, score, level, lines and board checksum for syncing
+    /// the HUD.
+    pub fn hud_state(&self) -> HudState {
+        HudState {
+            queue: self.queue().to_vec(),
+            hold: self.held_block,
+            score: self.score,
+            level: self.level,
+            lines: self.lines_cleared_total,
+            board_checksum: self.board.checksum(),
+        }
+    }
+
+    /// Returns the block currently controlled by the player, for drawing the current frame.
+    pub fn active_block(&self) -> &ActiveBlock {
+        &self.active_block
+    }
+
+    /// Returns the board of blocks fixed so far, excluding the [ActiveBlock], for drawing the
+    /// current frame.
+    pub fn board(&self) -> &StandardBoard {
+        &self.board
+    }
+
+    /// Returns the custom pieces registered with the game's [BlockGenerator].
+    pub(crate) fn custom_pieces(&self) -> &CustomPieces {
+        self.block_generator.custom_pieces()
+    }
+
+    /// Returns a block of the given type in its spawn position and rotation. See [spawn_block].
+    fn spawn(&self, block_type: BlockType) -> ActiveBlock {
+        spawn_block(block_type, self.custom_pieces(), &self.config)
+    }
+
+    /// Returns the block that will spawn when the [ActiveBlock] locks, without dealing it.
+    fn next_block(&self) -> Option<BlockType> {
+        self.queue.front().copied()
+    }
+
+    /// Returns true if the active block can be held: hold can only be used once per block, and in
+    /// practice mode, a hold that would spawn a block into the stack is refused rather than ending
+    /// the game.
+    fn can_hold(&self) -> bool {
+        if self.hold_used {
+            return false;
+        }
+        let incoming = self.held_block.or_else(|| self.next_block());
+        !(self.config.practice_mode
+            && incoming.is_some_and(|block_type| self.board.collides(&self.spawn(block_type))))
+    }
+
+    /// Returns the RGB colour of the block type, including the colour of a custom piece registered
+    /// with the game's [BlockGenerator].
+    pub fn block_color(&self, block_type: BlockType) -> (u8, u8, u8) {
+        self.custom_pieces().color(block_type)
+    }
+
+    /// Returns the board-space positions of the [ActiveBlock]'s cells in order of increasing row
+    /// then column.
+    pub fn active_positions(&self) -> Vec<Position> {
+        self.active_block.board_positions().collect()
+    }
+
+    /// Returns a score in the range 0..=1 describing how messy the stack is, where 0 is perfectly
+    /// flat with no holes. Useful for adapting difficulty to the state of the board.
+    pub fn board_roughness(&self) -> f32 {
+        self.board.roughness()
+    }
+
+    /// Returns the column the stack currently leaves open as its deepest well, which an AI should
+    /// keep clear for I blocks. Ties are broken in favour of the rightmost column.
+    pub fn preferred_well_column(&self) -> usize {
+        self.board.deepest_well_column()
+    }
+
+    /// Returns the population variance of the stack's column heights in the half-open range
+    /// `start_col..end_col`, so that an AI can target the flattest landing zone. Returns [None] if
+    /// the range is empty or extends beyond the board's columns.
+    pub fn surface_variance(&self, start_col: usize, end_col: usize) -> Option<f32> {
+        self.board.surface_variance(start_col, end_col)
+    }
+
+    /// Returns the number of rows on the board that contain garbage, e.g. to track progress in
+    /// modes where the goal is to dig through garbage.
+    pub fn garbage_lines_remaining(&self) -> usize {
+        (0..StandardBoard::ROWS)
+            .filter(|&r| self.board.is_garbage_row(r))
+            .count()
+    }
+
+    /// Returns the number of empty cells sealed off from the top of the board by the stack, which
+    /// no block can be dropped into.
+    pub fn sealed_cell_count(&self) -> usize {
+        let reachable = self.board.flood_fill_reachable_from_top();
+        self.board
+            .iter()
+            .flatten()
+            .zip(reachable.iter().flatten())
+            .filter(|(cell, reachable)| cell.is_none() && !**reachable)
+            .count()
+    }
+
+    /// Enables or disables instant (20G) gravity, overriding [Config::instant_gravity] for the
+    /// rest of the game, such as for modes that switch to 20G partway through. Persists across
+    /// restarts.
+    pub fn set_instant_gravity(&mut self, enabled: bool) {
+        self.config.instant_gravity = enabled;
+    }
+
+    /// Returns the top-left coordinates the [ActiveBlock] would occupy after one application of
+    /// gravity, without mutating the game. A grounded block returns its current position, and
+    /// under instant gravity the block's resting position is returned.
+    ///
+    /// Useful for interpolating the block's movement between gravity ticks.
+    pub fn projected_position_after_gravity(&self) -> TopLeft {
+        if self.config.instant_gravity {
+            return self.ghost_position();
+        }
+        let mut projected = self.active_block.clone();
+        projected.move_down();
+        if self.board.collides(&projected) {
+            self.active_block.top_left()
+        } else {
+            projected.top_left()
+        }
+    }
+
+    /// Returns the top-left coordinates the [ActiveBlock] would come to rest at if it were hard
+    /// dropped, without mutating the game. Useful for drawing a ghost piece.
+    pub fn ghost_position(&self) -> TopLeft {
+        let mut ghost = self.active_block.clone();
+        self.board.drop_block(&mut ghost);
+        ghost.top_left()
+    }
+
+    /// Registers a listener to be called whenever gravity is applied to the [ActiveBlock], with
+    /// the block's top-left coordinates before and after. The coordinates are equal when the
+    /// block was grounded, in which case its lock delay begins, or it's fixed to the board if
+    /// there's no delay. Under instant gravity, the block is grounded as soon as it lands.
+    ///
+    /// Replaces any previously registered listener.
+    pub fn set_gravity_listener(&mut self, listener: impl FnMut(TopLeft, TopLeft) + 'static) {
+        self.gravity_listener = Some(GravityListener(Box::new(listener)));
+    }
+
+    /// Registers a listener to be called with the tick number and the input whenever
+    /// [Self::update] polls an input other than [Input::None], such as for recording a
+    /// [Replay](crate::replay::Replay).
+    ///
+    /// Replaces any previously registered listener.
+    pub fn set_input_listener(&mut self, listener: impl FnMut(u64, Input) + 'static) {
+        self.input_listener = Some(InputListener(Box::new(listener)));
+    }
+
+    /// Returns the outcome of the most recent block landing, or None if no block has landed since
+    /// the game started.
+    pub fn last_settle_report(&self) -> Option<&SettleReport> {
+        self.last_settle_report.as_ref()
+    }
+
+    /// Returns the (old, new) position of each cell that slid down when lines were last cleared,
+    /// for animating the stack settling.
+    pub fn last_consolidation_moves(&self) -> Vec<(Position, Position)> {
+        self.last_consolidation_moves.clone()
+    }
+
+    /// Returns the last action that successfully moved or rotated the active block.
+    pub fn last_action(&self) -> LastAction {
+        self.last_action
+    }
+
+    /// Returns the (row, column) wall kick offset applied by the last successful rotation of the
+    /// active block, or None if it hasn't been rotated since it spawned. An unkicked rotation
+    /// reports (0, 0).
+    pub fn last_kick(&self) -> Option<(isize, isize)> {
+        self.last_kick
+    }
+
+    /// Returns the time left before the grounded [ActiveBlock] locks, counted in ticks of the
+    /// frame interval, or None while the block is airborne. Useful for drawing a lock delay
+    /// indicator.
+    pub fn lock_delay_remaining(&self) -> Option<Duration> {
+        self.lock_delay.map(|ticks| {
+            self.config
+                .frame_interval
+                .saturating_mul(ticks.try_into().unwrap_or(u32::MAX))
+        })
+    }
+
+    /// Returns the number of garbage lines earned by clearing lines since the last call, for
+    /// sending to an opponent.
+    pub(crate) fn take_outgoing_garbage(&mut self) -> u32 {
+        std::mem::take(&mut self.outgoing_garbage)
+    }
+
+    /// Returns a random column for the hole in incoming garbage. See
+    /// [BlockGenerator::random_column].
+    pub(crate) fn garbage_hole_column(&mut self) -> usize {
+        self.block_generator.random_column(StandardBoard::COLUMNS)
+    }
+
+    /// Adds garbage lines to the bottom of the board, pushing the stack upwards. The
+    /// [ActiveBlock] is pushed up with it if necessary, and the game ends if there's no room left.
+    /// In practice mode, garbage that would top out the stack is discarded instead.
+    pub(crate) fn receive_garbage(&mut self, count: usize, hole_column: usize) {
+        let before = self
+            .config
+            .practice_mode
+            .then(|| (self.board.clone(), self.active_block.clone()));
+        self.board.add_garbage_lines(count, hole_column);
+
+        let mut topped_out = false;
+        while self.board.collides(&self.active_block) {
+            if self.active_block.top_left().0 == 0 {
+                topped_out = true;
+                break;
+            }
+            self.active_block.move_up();
+        }
+
+        if topped_out || self.board.buffer_zone_occupied() {
+            match before {
+                Some((board, active_block)) => {
+                    self.board = board;
+                    self.active_block = active_block;
+                }
+                None => self.game_over = true,
+            }
+        }
+    }
+
+    /// Searches for a sequence of placements of the [ActiveBlock] followed by the queued blocks,
+    /// using at most `max_pieces` blocks, that leaves the board empty. Returns the placements in
+    /// the order they should be played, or None if no perfect clear is possible. An empty board
+    /// needs no placements.
+    ///
+    /// Searches are repeated with one more piece at a time, so the solution found uses as few
+    /// blocks as possible. Among solutions of that length, placements are searched depth-first in
+    /// the order of [Self::reachable_placements], so the solution is deterministic.
+    pub fn solve_perfect_clear(&self, max_pieces: usize) -> Option<Vec<Placement>> {
+        let pieces: Vec<ActiveBlock> = iter::once(self.active_block.clone())
+            .chain(self.queue.iter().map(|&block_type| self.spawn(block_type)))
+            .take(max_pieces)
+            .collect();
+        (0..=pieces.len()).find_map(|depth| {
+            let mut path = Vec::new();
+            placement::solve_perfect_clear(
+                &self.board,
+                &pieces[..depth],
+                self.config.gravity_mode,
+                &mut path,
+            )
+            .then_some(path)
+        })
+    }
+
+    /// Returns the greatest number of lines that could be cleared by placing the [ActiveBlock] in
+    /// any of its reachable resting positions. The game's state is not modified.
+    pub fn best_clears_for_active(&self) -> u8 {
+        self.placement_iter()
+            .map(|placement| {
+                let mut board = self.board.clone();
+                board.fix_active_block(placement.block());
+                board.clear_lines_with(self.config.gravity_mode)
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Returns the inputs that would currently have an effect on the [ActiveBlock], for displaying
+    /// control hints. Soft drop only has an effect while the block can fall, since a blocked soft
+    /// drop doesn't lock the block, whereas a hard drop always locks it. Hold is included until
+    /// it's been used for the current block. Returns an empty list when the game is over.
+    pub fn legal_moves(&self) -> Vec<Input> {
+        if self.game_over {
+            return Vec::new();
+        }
+
+        [
+            Input::Left,
+            Input::Right,
+            Input::Down,
+            Input::RotateLeft,
+            Input::RotateRight,
+            Input::HardDrop,
+            Input::Hold,
+        ]
+        .into_iter()
+        .filter(|&input| self.can_apply(input))
+        .collect()
+    }
+
+    /// Returns true if the given input would change the state of the [ActiveBlock].
+    fn can_apply(&self, input: Input) -> bool {
+        let mut moved = self.active_block.clone();
+        match input {
+            Input::Left => moved.move_left(),
+            Input::Right => moved.move_right(),
+            Input::Down => return !self.is_grounded(),
+            // Rotating an O block succeeds but changes nothing.
+            Input::RotateLeft | Input::RotateRight
+                if self.active_block.block_type().kick_class() == KickClass::O =>
+            {
+                return false;
+            }
+            Input::RotateLeft => return self.kicked_rotation(Direction::Left).is_some(),
+            Input::RotateRight => return self.kicked_rotation(Direction::Right).is_some(),
+            Input::HardDrop => return true,
+            Input::Hold => return self.can_hold(),
+            _ => return false,
+        }
+        !self.board.collides(&moved)
+    }
+
+    /// Returns true if the [ActiveBlock] is resting on the stack or the floor.
+    fn is_grounded(&self) -> bool {
+        self.active_block
+            .translated((1, 0))
+            .is_none_or(|below| self.board.collides(&below))
+    }
+
+    /// Returns the [ActiveBlock] rotated in the given direction, as by [kicked_rotation].
+    fn kicked_rotation(&self, direction: Direction) -> Option<(ActiveBlock, (isize, isize))> {
+        kicked_rotation(&self.board, &self.active_block, direction)
+    }
+
+    /// Returns the shortest sequence of inputs that locks the [ActiveBlock] in `rotation` with the
+    /// left edge of its bounding box in `column`, ending with a hard drop, for teaching finesse
+    /// in training modes. Returns None if the game is over or no sequence of moves, rotations and
+    /// soft drops can bring the block there, such as when the column is cut off by an overhang.
+    ///
+    /// The search is breadth-first over the positions the block can reach, trying left, right,
+    /// down, rotate left and rotate right from each, so of the equally short sequences, the first
+    /// in that order is returned.
+    pub fn finesse(&self, column: isize, rotation: RotationIndex) -> Option<Vec<Input>> {
+        if self.game_over {
+            return None;
+        }
+
+        type State = (TopLeft, usize);
+        let state = |block: &ActiveBlock| (block.top_left(), usize::from(block.rotation_index()));
+        // The state each visited state was first reached from, and the input that reached it.
+        let mut parents: HashMap<State, Option<(State, Input)>> = HashMap::new();
+        let mut frontier = VecDeque::new();
+        parents.insert(state(&self.active_block), None);
+        frontier.push_back(self.active_block.clone());
+
+        while let Some(block) = frontier.pop_front() {
+            if block.top_left().1 == column && block.rotation_index() == rotation {
+                let mut inputs = vec![Input::HardDrop];
+                let mut current = state(&block);
+                while let Some((parent, input)) = parents[&current] {
+                    inputs.push(input);
+                    current = parent;
+                }
+                inputs.reverse();
+                return Some(inputs);
+            }
+
+            for input in [
+                Input::Left,
+                Input::Right,
+                Input::Down,
+                Input::RotateLeft,
+                Input::RotateRight,
+            ] {
+                let Some(next) = moved(&self.board, &block, input) else {
+                    continue;
+                };
+                if let Entry::Vacant(entry) = parents.entry(state(&next)) {
+                    entry.insert(Some((state(&block), input)));
+                    frontier.push_back(next);
+                }
+            }
+        }
+        None
+    }
+
+    /// Sets the [ActiveBlock]'s rotation to `rotation` clockwise quarter turns from its spawn
+    /// rotation, wrapping values outside the range 0..4 so that rotation deltas of either sign can
+    /// be applied directly. No wall kicks are attempted. Returns false, leaving the block unchanged,
+    /// if the rotated block would collide.
+    pub fn set_active_rotation(&mut self, rotation: isize) -> bool {
+        let mut rotated = self.active_block.clone();
+        rotated.set_rotation(rotation);
+        if self.board.collides(&rotated) {
+            return false;
+        }
+        self.active_block = rotated;
+        true
+    }
+
+    /// Returns the resting positions the [ActiveBlock] can reach by rotating in place, shifting
+    /// horizontally, then dropping straight down.
+    ///
+    /// Placements are ordered by the number of clockwise turns, then by column, so searches that
+    /// break ties by order are deterministic.
+    pub fn reachable_placements(&self) -> Vec<Placement> {
+        placement::reachable_placements(&self.board, &self.active_block)
+    }
+
+    /// Lazily yields the same placements as [Self::reachable_placements], in the same order, for
+    /// searches that don't need to hold every placement at once.
+    pub fn placement_iter(&self) -> impl Iterator<Item = Placement> + '_ {
+        placement::placement_iter(&self.board, &self.active_block)
+    }
+}
+
+impl<I, C: Clock, S> Game<I, C, S> {
+    pub fn time_until_next_tick(&self) -> Duration {
+        self.timer.time_until_next_tick()
+    }
+
+    /// Returns the number of ticks elapsed since the game was created. The count isn't reset when
+    /// the game restarts, so it orders every event in the game.
+    pub fn tick_count(&self) -> u64 {
+        self.timer.tick_count()
+    }
+
+    /// Sets how many missed ticks the game catches up on, one per update, after a stall spanning
+    /// several ticks, or None to catch up on all of them. By default, only one tick is applied
+    /// after a stall. Persists across restarts.
+    pub fn set_max_catch_up_ticks(&mut self, max: Option<u32>) {
+        self.timer.set_max_catch_up_ticks(max);
+    }
+
+    /// Returns the time played since the game began or was last reset, summed over the interval of
+    /// each tick played so that it's unaffected by stalls. Ticks driven by a timer shared with
+    /// other games, as in a [MultiGame](crate::versus::MultiGame), count the shared interval
+    /// rather than the game's own frame interval.
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// Returns true if the goal of the configured [GameMode] has been met, such as a sprint's
+    /// line count or an ultra's time limit, at which point no further events will be handled
+    /// besides restarting and quitting. A marathon is never complete.
+    pub fn is_complete(&self) -> bool {
+        match self.config.mode {
+            GameMode::Marathon => false,
+            GameMode::Sprint { lines } => self.lines_cleared_total >= lines,
+            GameMode::Ultra { duration } => self.elapsed() >= duration,
+        }
+    }
+
+    /// Returns the speed at which gravity moves the [ActiveBlock] down the board, in cells per
+    /// second. Returns [f32::INFINITY] under instant gravity, or if no time passes between
+    /// applications of gravity, such as with a zero frame interval.
+    pub fn drop_speed_cps(&self) -> f32 {
+        if self.config.instant_gravity {
+            return f32::INFINITY;
+        }
+        let gravity_ticks = self.timer.effective_gravity_ticks();
+        let seconds_per_cell = gravity_ticks as f32 * self.config.frame_interval.as_secs_f32();
+        if seconds_per_cell == 0.0 {
+            return f32::INFINITY;
+        }
+        1.0 / seconds_per_cell
+    }
+}
+
+impl<I: PollInput, S: Distribution<u8>> Game<I, SystemClock, S> {
+    /// Instantiate a new game using the given [BlockGenerator] as its source of [Block]s, such as
+    /// [BlockGenerator::new] for uniformly random blocks or [BlockGenerator::new_bag] for a 7-bag.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `config` fails [Config::validate]. Use [Game::new_with_board] to handle an
+    /// invalid config as an error instead.
+    pub fn new(block_generator: BlockGenerator<S>, input: I, config: Config) -> Self {
+        Self::new_with_clock(block_generator, input, config, SystemClock)
+    }
+
+    /// Resumes a game from its [SavedGame] state, drawing blocks beyond the saved queue from
+    /// `block_generator`. Returns an error if the saved state is inconsistent with itself or
+    /// with `config`.
+    pub fn resume(
+        block_generator: BlockGenerator<S>,
+        input: I,
+        config: Config,
+        saved: SavedGame,
+    ) -> Result<Self, String> {
+        Self::resume_with_clock(block_generator, input, config, SystemClock, saved)
+    }
+
+    /// Resumes a game saved to the file at `path` by [Game::save]. As with [Game::resume], the
+    /// saved queue is restored but the generator's state isn't, so later blocks are drawn from
+    /// `block_generator`. Malformed or inconsistent saves are reported as
+    /// [io::ErrorKind::InvalidData].
+    #[cfg(feature = "serde")]
+    pub fn load(
+        path: &Path,
+        block_generator: BlockGenerator<S>,
+        input: I,
+        config: Config,
+    ) -> io::Result<Self> {
+        let invalid = |e: String| io::Error::new(io::ErrorKind::InvalidData, e);
+        let saved =
+            serde_json::from_str(&fs::read_to_string(path)?).map_err(|e| invalid(e.to_string()))?;
+        Self::resume(block_generator, input, config, saved).map_err(invalid)
+    }
+}
+
+impl<I: PollInput, C: Clock + Clone, S: Distribution<u8>> Game<I, C, S> {
+    /// Builds a timer that ticks at this game's frame interval against its clock, for pacing
+    /// several games in lockstep with [Self::update_on_shared_tick].
+    pub(crate) fn new_shared_timer(&self) -> GameTimer<C> {
+        GameTimer::new_with_clock(self.config.frame_interval, 1, 1, self.clock.clone())
+    }
+
+    pub(crate) fn new_with_clock(
+        block_generator: BlockGenerator<S>,
+        input: I,
+        config: Config,
+        clock: C,
+    ) -> Self {
+        let board = initial_board(&config);
+        match Self::new_with_board(block_generator, input, config, clock, board) {
+            Ok(game) => game,
+            Err(StartError::InvalidConfig(reason)) => panic!("invalid config: {reason}"),
+            Err(StartError::GameOverAtStart) => {
+                panic!("the first block should always spawn above the initial garbage")
+            }
+        }
+    }
+
+    /// Instantiates a game starting from a pre-filled [StandardBoard] and paced by `clock`, such
+    /// as [SystemClock]. Returns [StartError::InvalidConfig] if `config` fails
+    /// [Config::validate], names an unregistered custom piece or has a spawn column outside the
+    /// board, or [StartError::GameOverAtStart] if the first block would spawn overlapping the
+    /// board's contents.
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use std::time::Duration;
+    ///
+    /// use tetrust::game::StartError;
+    /// use tetrust::{
+    ///     BlockGenerator, Cell, Config, Game, GameMode, Gravity, GravityMode, ScriptedInput,
+    ///     StandardBoard, SystemClock,
+    /// };
+    ///
+    /// let config = Config {
+    ///     frame_interval: Duration::from_millis(1),
+    ///     gravity: Gravity::new(1, 1, 0)?,
+    ///     gravity_mode: GravityMode::Naive,
+    ///     instant_gravity: false,
+    ///     input_ticks: 1,
+    ///     soft_drop_ticks: 1,
+    ///     lock_delay_ticks: 0,
+    ///     initial_garbage_lines: None,
+    ///     preview_count: 3,
+    ///     practice_mode: false,
+    ///     mode: GameMode::Marathon,
+    ///     spawn_columns: HashMap::new(),
+    ///     initial_held_block: None,
+    ///     combo_points: 50,
+    /// };
+    /// // Fill the buffer zone, where every block spawns.
+    /// let mut cells = [[None; StandardBoard::COLUMNS]; StandardBoard::ROWS];
+    /// cells[..StandardBoard::BUFFER_ZONE_ROWS].fill([Some(Cell::Garbage); StandardBoard::COLUMNS]);
+    ///
+    /// let result = Game::new_with_board(
+    ///     BlockGenerator::seeded(7),
+    ///     ScriptedInput::new([]),
+    ///     config,
+    ///     SystemClock,
+    ///     StandardBoard::from(cells),
+    /// );
+    /// assert_eq!(result.err(), Some(StartError::GameOverAtStart));
+    /// # Ok::<(), String>(())
+    /// ```
+    pub fn new_with_board(
+        mut block_generator: BlockGenerator<S>,
+        input: I,
+        config: Config,
+        clock: C,
+        board: StandardBoard,
+    ) -> Result<Self, StartError> {
+        config.validate().map_err(StartError::InvalidConfig)?;
+        check_config_blocks(&config, block_generator.custom_pieces())
+            .map_err(StartError::InvalidConfig)?;
+        let first_block = block_generator.block();
+        let active_block = spawn_block(first_block, block_generator.custom_pieces(), &config);
+        if board.collides(&active_block) {
+            return Err(StartError::GameOverAtStart);
+        }
+
+        // Populate the queue with random blocks.
+        let mut queue: VecDeque<BlockType> = (0..config.preview_count)
+            .map(|_| block_generator.block())
+            .collect();
+        queue.make_contiguous(); // simplifies returning the queue to the game loop
+
+        let timer = new_timer(&config, clock.clone());
+
+        Ok(Game {
+            clock,
+            timer,
+            score: 0,
+            level: 0,
+            lines_cleared_total: 0,
+            combo: -1,
+            back_to_back: false,
+            board,
+            block_generator,
+            active_block,
+            queue,
+            game_over: false,
+            held_block: config.initial_held_block,
+            hold_used: false,
+            config,
+            lock_delay: None,
+            lock_delay_resets: 0,
+            last_action: LastAction::None,
+            last_kick: None,
+            last_consolidation_moves: Vec::new(),
+            elapsed: Duration::ZERO,
+            input,
+            gravity_listener: None,
+            input_listener: None,
+            outgoing_garbage: 0,
+            last_settle_report: None,
+            statistics: Statistics::default(),
+        })
+    }
+
+    /// Resumes a game from its [SavedGame] state using the given [Clock].
+    pub(crate) fn resume_with_clock(
+        block_generator: BlockGenerator<S>,
+        input: I,
+        config: Config,
+        clock: C,
+        saved: SavedGame,
+    ) -> Result<Self, String> {
+        config.validate()?;
+        check_config_blocks(&config, block_generator.custom_pieces())?;
+        if saved.queue.len() != config.preview_count {
+            return Err(format!(
+                "saved queue length must match the preview count: queue={}, preview_count={}",
+                saved.queue.len(),
+                config.preview_count
+            ));
+        }
+        let custom_pieces = block_generator.custom_pieces();
+        let board_blocks = saved.board.iter().flatten().filter_map(|cell| match cell {
+            Some(Cell::Block(block_type)) => Some(*block_type),
+            _ => None,
+        });
+        saved
+            .held_block
+            .into_iter()
+            .chain(saved.queue.iter().copied())
+            .chain(board_blocks)
+            .try_for_each(|block_type| custom_pieces.check(block_type).map(|_| ()))?;
+        let active_block = saved.active_block.registered_in(custom_pieces)?;
+        if !saved.game_over && saved.board.collides(&active_block) {
+            return Err("saved active block overlaps the board or is out of bounds".to_string());
+        }
+
+        let mut timer = new_timer(&config, clock.clone());
+        timer.set_tick_count(saved.ticks);
+
+        let mut game = Game {
+            clock,
+            config,
+            timer,
+            score: saved.score,
+            level: saved.level,
+            lines_cleared_total: saved.lines_cleared_total,
+            combo: saved.combo,
+            back_to_back: saved.back_to_back,
+            board: saved.board,
+            block_generator,
+            active_block,
+            queue: saved.queue.into(),
+            game_over: saved.game_over,
+            held_block: saved.held_block,
+            hold_used: saved.hold_used,
+            lock_delay: None,
+            lock_delay_resets: 0,
+            last_action: LastAction::None,
+            last_kick: None,
+            last_consolidation_moves: Vec::new(),
+            elapsed: saved.elapsed,
+            input,
+            gravity_listener: None,
+            input_listener: None,
+            outgoing_garbage: 0,
+            last_settle_report: None,
+            statistics: saved.statistics,
+        };
+        game.apply_level_gravity();
+        Ok(game)
+    }
+
+    /// Begins a new game, clearing the board and resetting the score, level, lines and combo. The
+    /// block generator isn't reset, so the new game deals different blocks unless the generator is
+    /// reseeded.
+    pub fn reset(&mut self) {
+        let max_catch_up_ticks = self.timer.max_catch_up_ticks();
+        let tick_count = self.timer.tick_count();
+        self.timer = new_timer(&self.config, self.clock.clone());
+        self.timer.set_max_catch_up_ticks(max_catch_up_ticks);
+        self.timer.set_tick_count(tick_count);
+        self.elapsed = Duration::ZERO;
+        self.score = 0;
+        self.level = 0;
+        self.lines_cleared_total = 0;
+        self.combo = -1;
+        self.back_to_back = false;
+        self.outgoing_garbage = 0;
+        self.last_settle_report = None;
+        self.statistics = Statistics::default();
+        self.last_consolidation_moves.clear();
+        self.new_round();
+    }
+
+    /// Clears the board and deals a fresh active block and queue, while keeping the score and the
+    /// current speed of gravity. Useful for modes played over several rounds.
+    pub fn new_round(&mut self) {
+        self.board = initial_board(&self.config);
+
+        let first_block = self.block_generator.block();
+        self.active_block = self.spawn(first_block);
+        self.last_action = LastAction::None;
+        self.last_kick = None;
+
+        self.queue.clear();
+        (0..self.config.preview_count)
+            .for_each(|_| self.queue.push_back(self.block_generator.block()));
+        self.queue.make_contiguous();
+
+        self.held_block = self.config.initial_held_block;
+        self.hold_used = false;
+        self.clear_lock_delay();
+        self.game_over = false
+    }
+
+    /// Runs the game loop until the player quits, calling `on_update` with the game whenever its
+    /// state changes, e.g. to redraw it. Sleeps on the game's [Clock] between ticks.
+    pub fn run(&mut self, mut on_update: impl FnMut(&Self) -> io::Result<()>) -> io::Result<()> {
+        loop {
+            match self.update()? {
+                UpdateOutcome::Updated => on_update(self)?,
+                UpdateOutcome::Quit => return Ok(()),
+                UpdateOutcome::Unchanged => (),
+            }
+            self.clock.sleep(self.time_until_next_tick());
+        }
+    }
+
+    /// Drives the game loop at a maxmimum rate determined by the [GameTimer]'s tick interval.
+    pub fn update(&mut self) -> io::Result<UpdateOutcome> {
+        match self.timer.update() {
+            Some(tick) => self.apply_tick(&tick, self.config.frame_interval),
+            None => Ok(UpdateOutcome::Unchanged),
+        }
+    }
+
+    /// Advances the game by one tick whose timing is decided by a timer shared with other games,
+    /// such as in a [MultiGame](crate::versus::MultiGame), ticking every `interval`. The game's
+    /// own timer only decides which events fall on the tick, so gravity still follows the game's
+    /// level.
+    pub(crate) fn update_on_shared_tick(
+        &mut self,
+        interval: Duration,
+    ) -> io::Result<UpdateOutcome> {
+        let tick = self.timer.advance();
+        self.apply_tick(&tick, interval)
+    }
+
+    /// Applies the events scheduled for a tick that took `interval` to play, polling the input
+    /// source if it's due.
+    fn apply_tick(&mut self, tick: &Tick, interval: Duration) -> io::Result<UpdateOutcome> {
+        self.elapsed = self.elapsed.saturating_add(interval);
+        let outcome = if self.game_over() || self.is_complete() {
+            self.update_game_over(tick)
+        } else {
+            self.update_game_in_progress(tick)
+        };
+        if cfg!(debug_assertions) {
+            self.assert_invariants();
+        }
+        outcome
+    }
+
+    /// Panics if, while the game is in progress, any cell of the [ActiveBlock] is out of bounds or
+    /// overlaps an occupied cell of the board. Checked after every update in debug builds.
+    pub fn assert_invariants(&self) {
+        if self.game_over {
+            // The block that topped out is left overlapping the board.
+            return;
+        }
+
+        assert!(
+            !self.board.collides(&self.active_block),
+            "active block cells {:?} overlap the board or are out of bounds:{:?}",
+            self.active_positions(),
+            self.board,
+        );
+    }
+
+    /// Manages updates that are valid in the game over state.
+    fn update_game_over(&mut self, tick: &Tick) -> io::Result<UpdateOutcome> {
+        if tick.input {
+            match self.poll_input()? {
+                Input::Quit => return Ok(UpdateOutcome::Quit),
+                Input::Restart => {
+                    self.reset();
+                    return Ok(UpdateOutcome::Updated);
+                }
+                _ => (),
+            }
+        }
+        Ok(UpdateOutcome::Unchanged)
+    }
+
+    /// Manages updates that are valid while the game is in progress.
+    fn update_game_in_progress(&mut self, tick: &Tick) -> io::Result<UpdateOutcome> {
+        self.apply_tick_gravity(tick);
+
+        if tick.input {
+            let input = self.poll_input()?;
+            if let Some(outcome) = self.handle_input(input) {
+                return Ok(outcome);
+            }
+        }
+
+        if tick.any() {
+            Ok(UpdateOutcome::Updated)
+        } else {
+            Ok(UpdateOutcome::Unchanged)
+        }
+    }
+
+    /// Counts down any lock delay and applies gravity if it's due on the tick, as happens at the
+    /// start of every tick while the game is in progress.
+    fn apply_tick_gravity(&mut self, tick: &Tick) {
+        self.count_down_lock_delay();
+        if tick.gravity {
+            self.handle_gravity();
+        }
+    }
+
+    /// Polls the input source for the next input, reporting it to the input listener.
+    fn poll_input(&mut self) -> io::Result<Input> {
+        let input = self.input.poll_input(self.timer.time_until_next_tick())?;
+        if input != Input::None
+            && let Some(InputListener(listener)) = &mut self.input_listener
+        {
+            listener(self.timer.tick_count(), input);
+        }
+        Ok(input)
+    }
+
+    /// Applies a player input to the game in progress. Returns the outcome of the update if the
+    /// input ends it early, as restarting and quitting do.
+    fn handle_input(&mut self, input: Input) -> Option<UpdateOutcome> {
+        match input {
+            Input::Down => self.soft_drop(),
+            Input::HardDrop => self.hard_drop(),
+            Input::Hold => self.handle_hold(),
+            Input::Left => self.handle_move(Direction::Left),
+            Input::Right => self.handle_move(Direction::Right),
+            Input::RotateLeft => _ = self.try_rotate(Direction::Left),
+            Input::RotateRight => _ = self.try_rotate(Direction::Right),
+            Input::SoftDropStart => self.timer.set_soft_drop(true),
+            Input::SoftDropEnd => self.timer.set_soft_drop(false),
+            Input::Restart => {
+                self.reset();
+                return Some(UpdateOutcome::Updated);
+            }
+            Input::Quit => return Some(UpdateOutcome::Quit),
+            _ => (),
+        }
+        None
+    }
+
+    /// Applies `inputs` in order without consulting the clock or the input source, for bots and
+    /// deterministic replays. Gravity doesn't apply between inputs, so blocks lock only when hard
+    /// dropped. Once the game is over or complete, every input but [Input::Restart] is ignored,
+    /// and [Input::Quit] is always ignored.
+    pub fn step(&mut self, inputs: &[Input]) -> StepReport {
+        let mut lines_cleared = 0;
+        for &input in inputs {
+            if self.game_over || self.is_complete() {
+                if input == Input::Restart {
+                    self.reset();
+                }
+                continue;
+            }
+
+            let lines_before = self.lines_cleared_total;
+            self.handle_input(input);
+            lines_cleared += self.lines_cleared_total.saturating_sub(lines_before);
+        }
+
+        StepReport {
+            lines_cleared,
+            game_over: self.game_over,
+        }
+    }
+
+    /// Advances the game by one tick without consulting the clock or the input source, counting
+    /// down lock delay and applying gravity as [Self::update] would before applying `inputs` with
+    /// [Self::step]. Used to play back games recorded tick by tick, such as a
+    /// [Replay](crate::replay::Replay).
+    pub fn advance_tick(&mut self, inputs: &[Input]) -> StepReport {
+        let tick = self.timer.advance();
+        self.elapsed = self.elapsed.saturating_add(self.config.frame_interval);
+        let lines_before = self.lines_cleared_total;
+        if !self.game_over && !self.is_complete() {
+            self.apply_tick_gravity(&tick);
+        }
+        let gravity_lines = self.lines_cleared_total.saturating_sub(lines_before);
+
+        let report = self.step(inputs);
+        StepReport {
+            lines_cleared: report.lines_cleared + gravity_lines,
+            ..report
+        }
+    }
+
+    /// Attempts to move the current [ActiveBlock] one row downwards, or as far as it will go under
+    /// instant gravity, and handles the resulting collision if movement is impossible.
+    fn handle_gravity(&mut self) {
+        let before = self.active_block.top_left();
+        let (moved, grounded) = if self.config.instant_gravity {
+            // The block falls as far as it will go, so it lands on this tick.
+            (self.board.drop_block(&mut self.active_block) > 0, true)
+        } else {
+            self.active_block.move_down();
+            let grounded = self.board.collides(&self.active_block);
+            if grounded {
+                self.active_block.move_up();
+            }
+            (!grounded, grounded)
+        };
+        if moved {
+            self.last_action = LastAction::Move;
+        }
+
+        if let Some(GravityListener(listener)) = &mut self.gravity_listener {
+            listener(before, self.active_block.top_left());
+        }
+
+        if grounded {
+            if self.config.lock_delay_ticks == 0 {
+                self.lock_active_block();
+            } else if self.lock_delay.is_none() {
+                self.lock_delay = Some(self.config.lock_delay_ticks);
+            }
+        }
+    }
+
+    /// Counts down the lock delay of a grounded [ActiveBlock] by one tick, locking it when the
+    /// delay expires.
+    fn count_down_lock_delay(&mut self) {
+        let Some(remaining) = self.lock_delay else {
+            return;
+        };
+        if remaining <= 1 {
+            self.lock_active_block();
+        } else {
+            self.lock_delay = Some(remaining - 1);
+        }
+    }
+
+    /// Updates the lock delay after the player successfully moves or rotates the [ActiveBlock].
+    /// A block that is still grounded restarts its delay, up to [MAX_LOCK_DELAY_RESETS] times,
+    /// while a block that has moved off the stack stops counting down until it lands again.
+    fn reset_lock_delay(&mut self) {
+        if self.lock_delay.is_none() {
+            return;
+        }
+        if !self.is_grounded() {
+            self.lock_delay = None;
+        } else if self.lock_delay_resets < MAX_LOCK_DELAY_RESETS {
+            self.lock_delay = Some(self.config.lock_delay_ticks);
+            self.lock_delay_resets += 1;
+        }
+    }
+
+    fn clear_lock_delay(&mut self) {
+        self.lock_delay = None;
+        self.lock_delay_resets = 0;
+    }
+
+    /// Swaps the [ActiveBlock] with the held block, or stashes it and spawns the next queued block
+    /// if nothing is held. The swapped-in block respawns in its spawn position and rotation. Only
+    /// one hold is allowed per block, until the active block locks.
+    fn handle_hold(&mut self) {
+        if !self.can_hold() {
+            return;
+        }
+
+        match self.held_block.replace(self.active_block.block_type()) {
+            Some(held) => {
+                self.active_block = self.spawn(held);
+                self.last_action = LastAction::None;
+                self.last_kick = None;
+            }
+            None => self.load_next_active_block(),
+        }
+        self.hold_used = true;
+        self.clear_lock_delay();
+        self.end_game_if_blocked_out();
+    }
+
+    /// Moves the [ActiveBlock] down one row at the player's request, awarding
+    /// [SOFT_DROP_POINTS_PER_ROW]. Unlike gravity, a blocked soft drop does nothing rather than
+    /// locking the block.
+    fn soft_drop(&mut self) {
+        self.active_block.move_down();
+        if self.board.collides(&self.active_block) {
+            self.active_block.move_up();
+            return;
+        }
+        self.last_action = LastAction::Move;
+        self.score += SOFT_DROP_POINTS_PER_ROW;
+    }
+
+    /// Drops the [ActiveBlock] straight onto the stack and locks it immediately, awarding
+    /// [HARD_DROP_POINTS_PER_ROW] for each row it fell.
+    fn hard_drop(&mut self) {
+        let rows = self.board.drop_block(&mut self.active_block);
+        if rows > 0 {
+            self.last_action = LastAction::Move;
+        }
+        self.score += HARD_DROP_POINTS_PER_ROW * rows as u32;
+        self.lock_active_block();
+    }
+
+    /// Fixes the [ActiveBlock] to the board where it stands, clears lines and spawns the next
+    /// block, returning the number of lines cleared. Shared by every way a block can land.
+    fn lock_active_block(&mut self) -> u8 {
+        self.clear_lock_delay();
+        let t_spin = self.t_spin();
+        let board_before_landing = self.config.practice_mode.then(|| self.board.clone());
+
+        // Add the active block to the board.
+        self.board.fix_active_block(&self.active_block);
+
+        // Clear lines and update the score.
+        let moves = self.board.consolidation_moves(self.config.gravity_mode);
+        let lines_cleared = self.board.clear_lines_with(self.config.gravity_mode);
+        if let Some(board) = board_before_landing
+            && (self.board.buffer_zone_occupied() || self.next_block_is_blocked_out())
+        {
+            // Rewind the fatal landing and let the player retry the block from its spawn position.
+            self.board = board;
+            self.active_block = self.spawn(self.active_block.block_type());
+            self.last_action = LastAction::None;
+            self.last_kick = None;
+            return 0;
+        }
+        if lines_cleared > 0 {
+            self.last_consolidation_moves = moves;
+        }
+        self.statistics
+            .record_lock(self.active_block.block_type(), lines_cleared);
+
+        self.combo = if lines_cleared > 0 {
+            self.combo + 1
+        } else {
+            -1
+        };
+        let combo_bonus = self.config.combo_points * self.combo.max(0) as u32;
+        let mut clear_points = points_for_clear(lines_cleared, t_spin);
+        let mut back_to_back = false;
+        if lines_cleared > 0 {
+            let difficult = is_difficult_clear(lines_cleared, t_spin);
+            back_to_back = difficult && self.back_to_back;
+            if back_to_back {
+                clear_points = clear_points * BACK_TO_BACK_MULTIPLIER_PERCENT / 100;
+            }
+            self.back_to_back = difficult;
+        }
+        let perfect_clear = lines_cleared > 0 && self.board.is_empty();
+        if perfect_clear {
+            clear_points += perfect_clear_points(lines_cleared);
+        }
+        let points_awarded = (clear_points + combo_bonus) * (self.level + 1);
+        self.score += points_awarded;
+        self.last_settle_report = Some(SettleReport {
+            lines_cleared,
+            perfect_clear,
+            points_awarded,
+            combo: self.combo,
+            back_to_back,
+            last_action: self.last_action,
+            t_spin,
+        });
+        self.outgoing_garbage += garbage_lines_for_clear(lines_cleared);
+        self.record_cleared_lines(lines_cleared as u32);
+
+        // Handle game over or set up the next block. The game ends if the stack reaches the buffer
+        // zone (lock out) or the next block spawns overlapping the stack (block out).
+        self.hold_used = false;
+        if self.board.buffer_zone_occupied() {
+            self.game_over = true
+        } else {
+            self.load_next_active_block();
+            self.end_game_if_blocked_out();
+        }
+
+        lines_cleared
+    }
+
+    /// Classifies the [ActiveBlock]'s landing as a T-spin if it's a T block that was last rotated
+    /// and at least three corners of its bounding box are blocked. The spin is full if both
+    /// corners beside the T's point are among them, and mini otherwise.
+    fn t_spin(&self) -> TSpin {
+        if self.active_block.block_type() != BlockType::T || self.last_action != LastAction::Rotate
+        {
+            return TSpin::None;
+        }
+
+        let (r, c) = self.active_block.top_left();
+        let blocked = |dr: usize, dc: isize| self.board.is_blocked(r + dr, c + dc);
+        // Corners in clockwise order from the top left, so the two corners beside the point of a
+        // T with rotation index i are corners i and i + 1.
+        let corners = [blocked(0, 0), blocked(0, 2), blocked(2, 2), blocked(2, 0)];
+        if corners.iter().filter(|&&b| b).count() < 3 {
+            return TSpin::None;
+        }
+
+        let front = usize::from(self.active_block.rotation_index());
+        if corners[front] && corners[(front + 1) % 4] {
+            TSpin::Full
+        } else {
+            TSpin::Mini
+        }
+    }
+
+    /// Adds to the total lines cleared, advancing the level every [LINES_PER_LEVEL] lines and
+    /// speeding up gravity to match. A level set above the one earned by the lines cleared is
+    /// kept until the lines catch up with it.
+    fn record_cleared_lines(&mut self, lines: u32) {
+        self.lines_cleared_total += lines;
+        let level = self.lines_cleared_total / LINES_PER_LEVEL;
+        if level > self.level {
+            self.set_level(level);
+        }
+    }
+
+    /// Sets the current level and immediately changes the speed of gravity to match, such as for
+    /// challenges that start at a high level. Clearing lines advances the level from here once
+    /// the total lines cleared earn a higher level.
+    pub fn set_level(&mut self, level: u32) {
+        self.level = level;
+        self.apply_level_gravity();
+    }
+
+    /// Sets the number of game ticks between applications of gravity for the current level, as
+    /// configured by [Gravity::ticks_for_level](crate::config::Gravity::ticks_for_level).
+    fn apply_level_gravity(&mut self) {
+        let gravity_ticks = self.config.gravity.ticks_for_level(self.level);
+        self.timer.set_gravity_ticks(gravity_ticks);
+    }
+
+    /// Pulls the next block off the queue and sets it as the game's active block.
+    ///
+    /// The replacement block is generated before the next one is taken, so the queue is never
+    /// empty, even when no blocks are previewed.
+    fn load_next_active_block(&mut self) {
+        self.queue.push_back(self.block_generator.block());
+        let next_block = self
+            .queue
+            .pop_front()
+            .expect("Block queue should never be empty");
+        self.queue.make_contiguous();
+        self.active_block = self.spawn(next_block);
+        self.last_action = LastAction::None;
+        self.last_kick = None;
+    }
+
+    /// Returns true if the next block would overlap the stack if it spawned now.
+    fn next_block_is_blocked_out(&self) -> bool {
+        self.next_block()
+            .is_some_and(|block_type| self.board.collides(&self.spawn(block_type)))
+    }
+
+    /// Ends the game if the newly spawned [ActiveBlock] overlaps the stack.
+    fn end_game_if_blocked_out(&mut self) {
+        if self.board.collides(&self.active_block) {
+            self.game_over = true
+        }
+    }
+
+    fn handle_move(&mut self, direction: Direction) {
+        let undo = if direction == Direction::Left {
+            self.active_block.move_left();
+            ActiveBlock::move_right
+        } else {
+            self.active_block.move_right();
+            ActiveBlock::move_left
+        };
+
+        if self.board.collides(&self.active_block) {
+            undo(&mut self.active_block)
+        } else {
+            self.last_action = LastAction::Move;
+            self.reset_lock_delay();
+        }
+    }
+
+    /// Attempts to rotate the [ActiveBlock], trying each wall kick in turn until the rotated block
+    /// fits. Returns the (row, column) kick offset applied, or None if the rotation was rejected.
+    fn try_rotate(&mut self, direction: Direction) -> Option<(isize, isize)> {
+        let (kicked, offset) = self.kicked_rotation(direction)?;
+        self.active_block = kicked;
+        self.last_action = LastAction::Rotate;
+        self.last_kick = Some(offset);
+        self.reset_lock_delay();
+        Some(offset)
+    }
+}
+
+/// Returns `block` rotated in the given direction on `board`, using the first wall kick that fits,
+/// along with the (row, column) kick offset applied. Returns None if no kick fits.
+///
+/// The buffer zone rows are free space like any other, but there is nothing above them: kicks
+/// that would lift the block above row 0 are skipped rather than wrapping.
+fn kicked_rotation(
+    board: &StandardBoard,
+    block: &ActiveBlock,
+    direction: Direction,
+) -> Option<(ActiveBlock, (isize, isize))> {
+    // Every rotation of the O block is identical, so there's nothing to rotate or collide.
+    if block.block_type().kick_class() == KickClass::O {
+        return Some((block.clone(), (0, 0)));
+    }
+
+    let mut rotated = block.clone();
+    let kicks = if direction == Direction::Left {
+        let kicks = rotated.counter_clockwise_kicks();
+        rotated.rotate_counter_clockwise();
+        kicks
+    } else {
+        let kicks = rotated.clockwise_kicks();
+        rotated.rotate_clockwise();
+        kicks
+    };
+
+    kicks.into_iter().find_map(|offset| {
+        rotated
+            .translated(offset)
+            .filter(|kicked| !board.collides(kicked))
+            .map(|kicked| (kicked, offset))
+    })
+}
+
+/// Returns `block` after applying a movement or rotation input on `board`, or None if the input
+/// is blocked or doesn't move the block.
+fn moved(board: &StandardBoard, block: &ActiveBlock, input: Input) -> Option<ActiveBlock> {
+    let next = match input {
+        Input::Left => block.translated((0, -1))?,
+        Input::Right => block.translated((0, 1))?,
+        Input::Down => block.translated((1, 0))?,
+        Input::RotateLeft => kicked_rotation(board, block, Direction::Left)?.0,
+        Input::RotateRight => kicked_rotation(board, block, Direction::Right)?.0,
+        _ => return None,
+    };
+    (!board.collides(&next)).then_some(next)
+}
+
+/// Returns a block of the given type in its spawn rotation, in the column configured for it if
+/// any, and otherwise in its default spawn position.
+fn spawn_block(
+    block_type: BlockType,
+    custom_pieces: &CustomPieces,
+    config: &Config,
+) -> ActiveBlock {
+    let block = ActiveBlock::new_in(block_type, custom_pieces);
+    match config.spawn_columns.get(&block_type) {
+        Some(&column) => block
+            .translated((0, column - block.top_left().1))
+            .expect("a block moved sideways should stay on the board"),
+        None => block,
+    }
+}
+
+/// Returns an error if the initial held block or a spawn column names an unregistered custom
+/// piece, or if a spawn column places any of its block's cells outside the board.
+fn check_config_blocks(config: &Config, custom_pieces: &CustomPieces) -> Result<(), String> {
+    if let Some(block_type) = config.initial_held_block {
+        custom_pieces.check(block_type)?;
+    }
+    for (&block_type, &column) in &config.spawn_columns {
+        custom_pieces.check(block_type)?;
+        let block = spawn_block(block_type, custom_pieces, config);
+        if block
+            .board_positions()
+            .any(|(_, c)| c >= StandardBoard::COLUMNS)
+        {
+            return Err(format!(
+                "spawn column {column} places {block_type:?} outside the board"
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Returns the board each round begins with: empty, or filled with the configured garbage.
+fn initial_board(config: &Config) -> StandardBoard {
+    let mut board = StandardBoard::new();
+    if let Some(garbage) = config.initial_garbage_lines {
+        let mut rng = StdRng::seed_from_u64(garbage.seed());
+        board.add_random_garbage_lines(garbage.lines(), &mut rng);
+    }
+    board
+}
+
+/// Returns the points awarded at level 0 for clearing the given number of lines at once, with or
+/// without a T-spin. T-spins score even when they clear nothing.
+fn points_for_clear(lines_cleared: u8, t_spin: TSpin) -> u32 {
+    match (t_spin, lines_cleared) {
+        (TSpin::None, 0) => 0,
+        (TSpin::None, 1) => 100,
+        (TSpin::None, 2) => 300,
+        (TSpin::None, 3) => 500,
+        (TSpin::None, _) => 800,
+        (TSpin::Mini, 0) => 100,
+        (TSpin::Mini, 1) => 200,
+        (TSpin::Mini, _) => 400,
+        (TSpin::Full, 0) => 400,
+        (TSpin::Full, 1) => 800,
+        (TSpin::Full, 2) => 1200,
+        (TSpin::Full, _) => 1600,
+    }
+}
+
+/// Returns the bonus points awarded at level 0 for a clear of the given number of lines that
+/// leaves the board empty.
+fn perfect_clear_points(lines_cleared: u8) -> u32 {
+    match lines_cleared {
+        0 => 0,
+        1 => 800,
+        2 => 1200,
+        3 => 1800,
+        _ => 2000,
+    }
+}
+
+/// Builds the timer that paces a game with the given config.
+fn new_timer<C: Clock>(config: &Config, clock: C) -> GameTimer<C> {
+    let mut timer = GameTimer::new_with_clock(
+        config.frame_interval,
+        config.gravity.initial_ticks(),
+        config.input_ticks,
+        clock,
+    );
+    timer.set_soft_drop_ticks(config.soft_drop_ticks);
+    timer
+}
+
+/// Returns true if clearing the given number of lines at once, with or without a T-spin, is
+/// difficult enough to continue a back-to-back chain.
+fn is_difficult_clear(lines_cleared: u8, t_spin: TSpin) -> bool {
+    lines_cleared >= 4 || t_spin != TSpin::None
+}
+
+/// Returns the number of garbage lines sent to an opponent for clearing the given number of lines
+/// at once.
+fn garbage_lines_for_clear(lines_cleared: u8) -> u32 {
+    match lines_cleared {
+        0 | 1 => 0,
+        2 => 1,
+        3 => 2,
+        _ => 4,
+    }
+}
+
+#[cfg(test)]
+mod game_tests {
+    use std::time::Instant;
+
+    use crate::board::Cell;
+    use crate::config::{Config, Gravity};
+    use crate::timer::test_helpers::MockClock;
+
+    use super::test_helpers::{
+        FRAME_INTERVAL, MockGame, MockInput, make_game, test_config as config,
+    };
+    use super::*;
+
+    /// Returns a game whose I block clears the bottom `lines` rows when locked. See
+    /// [set_up_i_well].
+    fn game_with_i_well(lines: usize) -> MockGame {
+        let clock = MockClock::new(Instant::now());
+        let mut game = make_game(clock, MockInput::new([]), config(), 1);
+        set_up_i_well(&mut game, lines);
+        game
+    }
+
+    /// Fills the bottom `lines` rows except for the rightmost column, then drops the active I
+    /// block vertically into the gap without locking it. A cell is left above the well so that
+    /// no clear is a perfect clear.
+    fn set_up_i_well(game: &mut MockGame, lines: usize) {
+        let mut cells = [[None; StandardBoard::COLUMNS]; StandardBoard::ROWS];
+        for row in &mut cells[StandardBoard::ROWS - lines..] {
+            row[..StandardBoard::COLUMNS - 1].fill(Some(Cell::Block(BlockType::O)));
+        }
+        cells[StandardBoard::ROWS - lines - 1][0] = Some(Cell::Garbage);
+        game.set_board(StandardBoard::from(cells));
+        game.try_rotate(Direction::Right);
+        (0..StandardBoard::COLUMNS).for_each(|_| game.handle_move(Direction::Right));
+        game.drop_active_block();
+    }
+
+    /// Returns the inputs for five O block drops that land side by side across the board, filling
+    /// the bottom two rows so that the last drop clears them. Each script ends with a hard drop.
+    fn o_block_drops_filling_two_rows() -> [Vec<Input>; 5] {
+        [
+            (Input::Left, 4),
+            (Input::Left, 2),
+            (Input::None, 0),
+            (Input::Right, 2),
+            (Input::Right, 4),
+        ]
+        .map(|(direction, moves)| {
+            let mut inputs = vec![direction; moves];
+            inputs.push(Input::HardDrop);
+            inputs
+        })
+    }
+
+    mod drop_speed_cps_tests {
+        use super::*;
+
+        #[test]
+        fn when_soft_dropping_returns_one_cell_per_tick() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 1);
+            game.timer.set_soft_drop(true);
+            assert_eq!(game.drop_speed_cps(), 10.0);
+        }
+
+        #[test]
+        fn returns_cells_per_second_for_current_gravity() {
+            let clock = MockClock::new(Instant::now());
+            let game = make_game(clock, MockInput::new([]), config(), 1);
+            // 2 gravity ticks at 100ms per tick.
+            assert_eq!(game.drop_speed_cps(), 5.0);
+        }
+
+        #[test]
+        fn when_gravity_accelerates_speed_increases() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 1);
+            let initial_speed = game.drop_speed_cps();
+
+            game.level = 1;
+            game.apply_level_gravity();
+
+            assert!(game.drop_speed_cps() > initial_speed);
+            assert_eq!(game.drop_speed_cps(), 10.0);
+        }
+
+        #[test]
+        fn when_frame_interval_is_zero_returns_infinity() {
+            let clock = MockClock::new(Instant::now());
+            let config = Config {
+                frame_interval: Duration::ZERO,
+                ..config()
+            };
+            let game = make_game(clock, MockInput::new([]), config, 1);
+            assert_eq!(game.drop_speed_cps(), f32::INFINITY);
+        }
+
+        #[test]
+        fn when_gravity_is_instant_returns_infinity() {
+            let clock = MockClock::new(Instant::now());
+            let config = Config {
+                instant_gravity: true,
+                ..config()
+            };
+            let game = make_game(clock, MockInput::new([]), config, 1);
+            assert_eq!(game.drop_speed_cps(), f32::INFINITY);
+        }
+    }
+
+    mod new_with_board_tests {
+        use super::*;
+
+        #[test]
+        fn when_board_blocks_spawn_area_returns_game_over_at_start() {
+            let clock = MockClock::new(Instant::now());
+            let mut cells = [[None; StandardBoard::COLUMNS]; StandardBoard::ROWS];
+            cells[0][4] = Some(Cell::Garbage);
+
+            let res = Game::new_with_board(
+                BlockGenerator::with_mock_sampler(4),
+                MockInput::new([]),
+                config(),
+                clock,
+                StandardBoard::from(cells),
+            );
+
+            assert_eq!(res.err(), Some(StartError::GameOverAtStart));
+        }
+
+        #[test]
+        fn when_soft_drop_ticks_is_zero_returns_invalid_config() {
+            let clock = MockClock::new(Instant::now());
+            let config = Config {
+                soft_drop_ticks: 0,
+                ..config()
+            };
+
+            let res = Game::new_with_board(
+                BlockGenerator::with_mock_sampler(4),
+                MockInput::new([]),
+                config,
+                clock,
+                StandardBoard::new(),
+            );
+
+            assert!(matches!(res.err(), Some(StartError::InvalidConfig(_))));
+        }
+
+        #[test]
+        fn when_spawn_column_is_out_of_bounds_returns_invalid_config() {
+            for (block_type, column) in [(BlockType::O, 9), (BlockType::I, 7), (BlockType::T, -1)] {
+                let clock = MockClock::new(Instant::now());
+                let config = Config {
+                    spawn_columns: HashMap::from([(block_type, column)]),
+                    ..config()
+                };
+
+                let res = Game::new_with_board(
+                    BlockGenerator::with_mock_sampler(4),
+                    MockInput::new([]),
+                    config,
+                    clock,
+                    StandardBoard::new(),
+                );
+
+                assert!(
+                    matches!(res.err(), Some(StartError::InvalidConfig(_))),
+                    "{block_type:?} at {column}"
+                );
+            }
+        }
+
+        #[test]
+        fn when_initial_held_block_is_an_unregistered_custom_piece_returns_invalid_config() {
+            let clock = MockClock::new(Instant::now());
+            let config = Config {
+                initial_held_block: Some(BlockType::Custom(0)),
+                ..config()
+            };
+
+            let res = Game::new_with_board(
+                BlockGenerator::with_mock_sampler(4),
+                MockInput::new([]),
+                config,
+                clock,
+                StandardBoard::new(),
+            );
+
+            assert!(matches!(res.err(), Some(StartError::InvalidConfig(_))));
+        }
+
+        #[test]
+        fn when_spawn_columns_name_an_unregistered_custom_piece_returns_invalid_config() {
+            let clock = MockClock::new(Instant::now());
+            let config = Config {
+                spawn_columns: HashMap::from([(BlockType::Custom(0), 0)]),
+                ..config()
+            };
+
+            let res = Game::new_with_board(
+                BlockGenerator::with_mock_sampler(4),
+                MockInput::new([]),
+                config,
+                clock,
+                StandardBoard::new(),
+            );
+
+            assert!(matches!(res.err(), Some(StartError::InvalidConfig(_))));
+        }
+
+        #[test]
+        fn when_spawn_area_is_clear_starts_with_board() {
+            let clock = MockClock::new(Instant::now());
+            let mut cells = [[None; StandardBoard::COLUMNS]; StandardBoard::ROWS];
+            cells[StandardBoard::ROWS - 1][0] = Some(Cell::Garbage);
+            let board = StandardBoard::from(cells);
+
+            let game = Game::new_with_board(
+                BlockGenerator::with_mock_sampler(4),
+                MockInput::new([]),
+                config(),
+                clock,
+                board.clone(),
+            )
+            .unwrap();
+
+            assert_eq!(game.board, board);
+            assert!(!game.game_over());
+        }
+    }
+
+    mod run_tests {
+        use crate::input::ScriptedInput;
+
+        use super::*;
+
+        #[test]
+        fn applies_scripted_inputs_until_script_is_exhausted() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = Game::new_with_clock(
+                BlockGenerator::with_mock_sampler(4),
+                ScriptedInput::new([Input::Left, Input::Left]),
+                config(),
+                clock,
+            );
+            let mut updates = 0;
+
+            game.run(|_| {
+                updates += 1;
+                Ok(())
+            })
+            .unwrap();
+
+            // Gravity applies on the second tick, before the second input is read.
+            assert_eq!(updates, 2);
+            assert_eq!(game.active_block().top_left(), (1, 2));
+        }
+    }
+
+    mod step_tests {
+        use indoc::indoc;
+
+        use super::*;
+
+        #[test]
+        fn when_scripted_drops_fill_two_rows_clears_them() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 4);
+            let inputs = o_block_drops_filling_two_rows().concat();
+
+            let report = game.step(&inputs);
+
+            assert_eq!(
+                report,
+                StepReport {
+                    lines_cleared: 2,
+                    game_over: false
+                }
+            );
+            assert!(game.board().is_empty());
+        }
+
+        #[test]
+        fn when_generator_is_seeded_produces_a_deterministic_board() {
+            let play = || {
+                let clock = MockClock::new(Instant::now());
+                let mut game = Game::new_with_clock(
+                    BlockGenerator::seeded(7),
+                    MockInput::new([]),
+                    config(),
+                    clock,
+                );
+                let inputs = [
+                    Input::RotateRight,
+                    Input::Left,
+                    Input::Left,
+                    Input::HardDrop,
+                    Input::Right,
+                    Input::Right,
+                    Input::Right,
+                    Input::HardDrop,
+                    Input::Hold,
+                    Input::RotateLeft,
+                    Input::HardDrop,
+                ];
+                game.step(&inputs);
+                game.snapshot()
+            };
+
+            let first = play();
+            let second = play();
+
+            assert_eq!(second, first, "{}", second.diff(&first));
+            assert_eq!(
+                first.board.to_string(),
+                indoc! {"
+                *——————————*
+                |          |
+                |          |
+                |          |
+                |          |
+                |          |
+                |          |
+                |          |
+                |          |
+                |          |
+                |          |
+                |          |
+                |          |
+                |          |
+                |          |
+                |          |
+                |          |
+                |          |
+                |   LLL    |
+                |   L L    |
+                |   LLLIIII|
+                *——————————*
+                "}
+            );
+            assert_eq!(first.score, 116);
+            assert_eq!(first.active_positions, vec![(1, 3), (1, 4), (1, 5), (1, 6)]);
+            assert_eq!(first.queue, vec![BlockType::I, BlockType::S, BlockType::O]);
+        }
+
+        #[test]
+        fn when_game_is_over_ignores_inputs_until_restart() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 4);
+            game.game_over = true;
+
+            let report = game.step(&[Input::HardDrop, Input::Left]);
+            assert!(report.game_over);
+            assert!(game.board().is_empty());
+
+            let report = game.step(&[Input::Restart, Input::HardDrop]);
+            assert!(!report.game_over);
+            assert!(!game.board().is_empty());
+        }
+    }
+
+    mod advance_tick_tests {
+        use super::*;
+
+        #[test]
+        fn applies_gravity_when_due_then_the_inputs() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 1);
+            let (r, c) = game.active_block().top_left();
+
+            // Gravity applies every second tick.
+            game.advance_tick(&[]);
+            assert_eq!(game.active_block().top_left(), (r, c));
+            game.advance_tick(&[Input::Left]);
+
+            assert_eq!(game.active_block().top_left(), (r + 1, c - 1));
+            assert_eq!(game.tick_count(), 2);
+        }
+
+        #[test]
+        fn when_game_is_over_does_not_apply_gravity() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 1);
+            game.game_over = true;
+            let before = game.active_block().clone();
+
+            game.advance_tick(&[]);
+            game.advance_tick(&[]);
+
+            assert_eq!(*game.active_block(), before);
+        }
+    }
+
+    mod statistics_tests {
+        use super::*;
+
+        #[test]
+        fn counts_each_type_of_block_placed() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = Game::new_with_clock(
+                BlockGenerator::seeded(7),
+                MockInput::new([]),
+                config(),
+                clock,
+            );
+            // The generator seeded with 7 deals L, I, I, L, I, I.
+            for _ in 0..6 {
+                game.step(&[Input::HardDrop]);
+            }
+
+            let statistics = game.statistics();
+            assert_eq!(statistics.total_pieces(), 6);
+            use BlockType::*;
+            for (block_type, expected) in [(I, 4), (J, 0), (L, 2), (O, 0), (S, 0), (T, 0), (Z, 0)] {
+                assert_eq!(statistics.pieces_of(block_type), expected, "{block_type:?}");
+            }
+        }
+
+        #[test]
+        fn counts_lines_cleared() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 4);
+            game.step(&[
+                Input::Left,
+                Input::Left,
+                Input::Left,
+                Input::Left,
+                Input::HardDrop,
+                Input::Left,
+                Input::Left,
+                Input::HardDrop,
+                Input::HardDrop,
+                Input::Right,
+                Input::Right,
+                Input::HardDrop,
+                Input::Right,
+                Input::Right,
+                Input::Right,
+                Input::Right,
+                Input::HardDrop,
+            ]);
+
+            let statistics = game.statistics();
+            assert_eq!(statistics.pieces_of(BlockType::O), 5);
+            assert_eq!(statistics.total_pieces(), 5);
+            assert_eq!(statistics.lines(), 2);
+        }
+
+        #[test]
+        fn when_game_is_reset_clears_statistics() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 4);
+            game.step(&[Input::HardDrop]);
+
+            game.reset();
+
+            assert_eq!(*game.statistics(), Statistics::default());
+        }
+    }
+
+    mod is_complete_tests {
+        use super::*;
+
+        fn config_with_mode(mode: GameMode) -> Config {
+            Config { mode, ..config() }
+        }
+
+        #[test]
+        fn when_mode_is_sprint_completes_when_target_lines_are_cleared() {
+            let clock = MockClock::new(Instant::now());
+            let config = config_with_mode(GameMode::Sprint { lines: 2 });
+            let mut game = make_game(clock, MockInput::new([]), config, 4);
+            let [drops @ .., last_drop] = o_block_drops_filling_two_rows();
+
+            game.step(&drops.concat());
+            assert!(!game.is_complete());
+            game.step(&last_drop);
+
+            assert_eq!(game.lines_cleared_total(), 2);
+            assert!(game.is_complete());
+            assert!(!game.game_over());
+        }
+
+        #[test]
+        fn when_sprint_is_complete_ignores_inputs_until_restart() {
+            let clock = MockClock::new(Instant::now());
+            let config = config_with_mode(GameMode::Sprint { lines: 2 });
+            let mut game = make_game(clock, MockInput::new([]), config, 4);
+            game.step(&o_block_drops_filling_two_rows().concat());
+
+            game.step(&[Input::HardDrop]);
+            assert!(game.board().is_empty());
+
+            game.step(&[Input::Restart]);
+            assert!(!game.is_complete());
+        }
+
+        #[test]
+        fn when_mode_is_ultra_completes_when_time_runs_out() {
+            let clock = MockClock::new(Instant::now());
+            let config = config_with_mode(GameMode::Ultra {
+                duration: FRAME_INTERVAL * 3,
+            });
+            let mut game = make_game(clock, MockInput::new([]), config, 1);
+
+            game.advance_tick(&[]);
+            game.advance_tick(&[]);
+            assert!(!game.is_complete());
+            game.advance_tick(&[]);
+
+            assert_eq!(game.elapsed(), FRAME_INTERVAL * 3);
+            assert!(game.is_complete());
+        }
+
+        #[test]
+        fn when_game_restarts_resets_elapsed_time_but_keeps_counting_ticks() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 1);
+            game.advance_tick(&[]);
+            game.advance_tick(&[]);
+
+            game.advance_tick(&[Input::Restart]);
+
+            assert_eq!(game.elapsed(), Duration::ZERO);
+            assert_eq!(game.tick_count(), 3);
+        }
+
+        #[test]
+        fn when_ultra_is_complete_stops_applying_gravity() {
+            let clock = MockClock::new(Instant::now());
+            let config = config_with_mode(GameMode::Ultra {
+                duration: FRAME_INTERVAL,
+            });
+            let mut game = make_game(clock, MockInput::new([]), config, 1);
+            game.advance_tick(&[]);
+            let before = game.active_block().clone();
+
+            game.advance_tick(&[]);
+            game.advance_tick(&[]);
+
+            assert_eq!(*game.active_block(), before);
+        }
+
+        #[test]
+        fn when_mode_is_marathon_is_never_complete() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 4);
+
+            game.step(&o_block_drops_filling_two_rows().concat());
+
+            assert!(!game.is_complete());
+        }
+    }
+
+    mod reset_tests {
+        use super::*;
+
+        #[test]
+        fn when_moves_have_been_played_clears_board_and_score() {
+            let clock = MockClock::new(Instant::now());
+            let inputs = [Input::HardDrop, Input::Left, Input::HardDrop, Input::Right];
+            let mut game = make_game(clock.clone(), MockInput::new(inputs), config(), 6);
+            for _ in 0..8 {
+                clock.advance(FRAME_INTERVAL);
+                game.update().unwrap();
+            }
+            assert!(!game.board().is_empty());
+            assert!(game.score() > 0);
+
+            game.reset();
+
+            assert!(game.board().is_empty());
+            assert_eq!(game.score(), 0);
+            assert_eq!(game.level(), 0);
+            assert_eq!(game.lines_cleared_total(), 0);
+            assert_eq!(game.combo(), -1);
+            assert!(!game.game_over());
+        }
+
+        #[test]
+        fn keeps_advancing_the_block_generator() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = Game::new_with_clock(
+                BlockGenerator::seeded(7),
+                MockInput::new([]),
+                config(),
+                clock,
+            );
+            let dealt = |game: &Game<MockInput, MockClock>| {
+                let mut blocks = vec![game.active_block().block_type()];
+                blocks.extend_from_slice(game.queue());
+                blocks
+            };
+            let first = dealt(&game);
+
+            game.reset();
+
+            assert_ne!(dealt(&game), first);
+        }
+
+        #[test]
+        fn resets_state() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 1);
+
+            // Dirty the state
+            game.score = 10;
+            game.game_over = true;
+            game.board.fix_active_block(&game.active_block.clone());
+
+            game.reset();
+
+            assert_eq!(game.score, 0);
+            assert!(!game.game_over);
+            assert_eq!(game.board, StandardBoard::new());
+            assert_eq!(*game.active_block(), ActiveBlock::new(BlockType::I));
+            assert_eq!(game.queue(), vec![BlockType::I; 3]);
+        }
+    }
+
+    mod new_round_tests {
+        use super::*;
+
+        #[test]
+        fn clears_board_and_keeps_score_and_gravity() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 1);
+            game.score = 10;
+            game.level = 1;
+            game.apply_level_gravity();
+            let gravity_ticks = game.timer.gravity_ticks();
+            game.game_over = true;
+            game.drop_active_block();
+            game.board.fix_active_block(&game.active_block.clone());
+
+            game.new_round();
+
+            assert_eq!(game.score, 10);
+            assert_eq!(game.timer.gravity_ticks(), gravity_ticks);
+            assert!(!game.game_over);
+            assert_eq!(game.board, StandardBoard::new());
+            assert_eq!(*game.active_block(), ActiveBlock::new(BlockType::I));
+            assert_eq!(game.queue(), vec![BlockType::I; 3]);
+        }
+    }
+
+    mod display_tests {
+        use super::*;
+
+        #[test]
+        fn renders_visible_board_with_active_block_score_and_queue() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 4);
+            let mut cells = [[None; StandardBoard::COLUMNS]; StandardBoard::ROWS];
+            cells[StandardBoard::ROWS - 1][..3].fill(Some(Cell::Block(BlockType::T)));
+            cells[StandardBoard::ROWS - 1][9] = Some(Cell::Garbage);
+            game.set_board(StandardBoard::from(cells));
+            game.score = 300;
+            // Lower the O block so that its top half is the first visible row.
+            game.handle_gravity();
+            game.handle_gravity();
+
+            let empty_row = "|          |\n";
+            let expected = [
+                "*——————————*\n",
+                "|    @@    |\n",
+                "|    @@    |\n",
+                &empty_row.repeat(StandardBoard::PLAYABLE_ROWS - 3),
+                "|TTT      ▒|\n",
+                "*——————————*\n",
+                "Score: 300\n",
+                "Next: O O O",
+            ]
+            .concat();
+            assert_eq!(game.to_string(), expected);
+        }
+    }
+
+    mod snapshot_diff_tests {
+        use super::*;
+
+        #[test]
+        fn when_snapshots_are_equal_returns_empty_string() {
+            let clock = MockClock::new(Instant::now());
+            let game = make_game(clock, MockInput::new([]), config(), 1);
+            assert_eq!(game.snapshot().diff(&game.snapshot()), "");
+        }
+
+        #[test]
+        fn when_score_and_board_cell_differ_names_both() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 1);
+            let before = game.snapshot();
+            game.score = 4;
+            let mut cells = [[None; StandardBoard::COLUMNS]; StandardBoard::ROWS];
+            cells[StandardBoard::ROWS - 1][2] = Some(Cell::Garbage);
+            game.set_board(StandardBoard::from(cells));
+
+            let diff = before.diff(&game.snapshot());
+
+            assert_eq!(
+                diff,
+                format!(
+                    "score: 0 -> 4\nboard cell ({}, 2): empty -> Garbage",
+                    StandardBoard::ROWS - 1
+                )
+            );
+        }
+
+        #[test]
+        fn when_active_block_and_queue_differ_names_both() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 1);
+            let before = game.snapshot();
+            game.handle_move(Direction::Left);
+            game.queue[0] = BlockType::T;
+
+            let diff = before.diff(&game.snapshot());
+
+            assert!(diff.contains("active block: "));
+            assert!(diff.contains("queue: [I, I, I] -> [T, I, I]"));
+        }
+
+        #[test]
+        fn when_combo_differs_names_it() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 1);
+            let before = game.snapshot();
+            game.combo = 1;
+
+            assert_eq!(before.diff(&game.snapshot()), "combo: -1 -> 1");
+        }
+    }
+
+    mod hud_state_tests {
+        use super::*;
+
+        #[test]
+        fn reflects_queue_hold_score_level_and_lines() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 1);
+            game.score = 3;
+            game.record_cleared_lines(12);
+            game.handle_hold();
+
+            let hud = game.hud_state();
+
+            assert_eq!(
+                hud,
+                HudState {
+                    queue: vec![BlockType::I; 3],
+                    hold: Some(BlockType::I),
+                    score: 3,
+                    level: 1,
+                    lines: 12,
+                    board_checksum: StandardBoard::new().checksum(),
+                }
+            );
+            assert_eq!(hud.queue, game.queue());
+        }
+
+        #[cfg(feature = "serde")]
+        #[test]
+        fn round_trips_through_json() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 1);
+            game.handle_hold();
+            let hud = game.hud_state();
+
+            let json = serde_json::to_string(&hud).unwrap();
+
+            assert_eq!(serde_json::from_str::<HudState>(&json).unwrap(), hud);
+        }
+    }
+
+    mod peek_next_tests {
+        use super::*;
+
+        fn game()
+        -> Game<MockInput, MockClock, crate::block_generator::test_helpers::SequenceSampler>
+        {
+            let clock = MockClock::new(Instant::now());
+            Game::new_with_clock(
+                BlockGenerator::with_sequence_sampler([1, 2, 3, 4, 5, 6, 7]),
+                MockInput::new([]),
+                config(),
+                clock,
+            )
+        }
+
+        #[test]
+        fn when_n_exceeds_queue_returns_whole_queue() {
+            let game = game();
+            assert_eq!(
+                game.peek_next(10),
+                &[BlockType::J, BlockType::L, BlockType::O]
+            );
+        }
+
+        #[test]
+        fn when_n_is_within_queue_returns_first_n_blocks() {
+            let game = game();
+            assert_eq!(game.peek_next(1), &[BlockType::J]);
+        }
+
+        #[test]
+        fn peeked_blocks_are_the_next_to_spawn() {
+            let mut game = game();
+            let peeked = game.peek_next(config().preview_count).to_vec();
+
+            let spawned: Vec<BlockType> = (0..peeked.len())
+                .map(|_| {
+                    game.load_next_active_block();
+                    game.active_block().block_type()
+                })
+                .collect();
+
+            assert_eq!(spawned, peeked);
+        }
+    }
+
+    mod preview_count_tests {
+        use super::*;
+
+        /// Returns the first `n` blocks spawned by a game with the given number of previews, fed
+        /// by the same fixed sequence of blocks.
+        fn spawn_sequence(preview_count: usize, n: usize) -> Vec<BlockType> {
+            let clock = MockClock::new(Instant::now());
+            let cfg = Config {
+                preview_count,
+                ..config()
+            };
+            let mut game = Game::new_with_clock(
+                BlockGenerator::with_sequence_sampler([1, 2, 3, 4, 5, 6, 7]),
+                MockInput::new([]),
+                cfg,
+                clock,
+            );
+            (0..n)
+                .map(|_| {
+                    let block_type = game.active_block().block_type();
+                    game.load_next_active_block();
+                    block_type
+                })
+                .collect()
+        }
+
+        #[test]
+        fn spawn_sequence_does_not_depend_on_preview_count() {
+            let n = 20;
+            assert_eq!(spawn_sequence(3, n), spawn_sequence(5, n));
+            assert_eq!(spawn_sequence(0, n), spawn_sequence(5, n));
+        }
+
+        #[test]
+        fn queue_holds_preview_count_blocks() {
+            let clock = MockClock::new(Instant::now());
+            let cfg = Config {
+                preview_count: 5,
+                ..config()
+            };
+            let game = make_game(clock, MockInput::new([]), cfg, 1);
+            assert_eq!(game.queue(), vec![BlockType::I; 5]);
+        }
+
+        #[test]
+        fn queue_length_is_stable_across_locks() {
+            for preview_count in [1, 6] {
+                let clock = MockClock::new(Instant::now());
+                let cfg = Config {
+                    preview_count,
+                    ..config()
+                };
+                let mut game = make_game(clock, MockInput::new([]), cfg, 4);
+                for _ in 0..5 {
+                    game.hard_drop();
+                    assert_eq!(game.queue().len(), preview_count);
+                }
+            }
+        }
+    }
+
+    mod initial_garbage_lines_tests {
+        use crate::config::GarbageStart;
+
+        use super::*;
+
+        fn game_with_garbage_start(lines: usize, seed: u64) -> MockGame {
+            let clock = MockClock::new(Instant::now());
+            let cfg = Config {
+                initial_garbage_lines: Some(GarbageStart::new(lines, seed).unwrap()),
+                ..config()
+            };
+            make_game(clock, MockInput::new([]), cfg, 6)
+        }
+
+        #[test]
+        fn when_seed_is_the_same_produces_the_same_spawn_safe_board() {
+            let mut a = game_with_garbage_start(GarbageStart::MAX_LINES, 9);
+            let b = game_with_garbage_start(GarbageStart::MAX_LINES, 9);
+
+            assert_eq!(a.board(), b.board());
+            assert_eq!(a.garbage_lines_remaining(), GarbageStart::MAX_LINES);
+            assert!(!a.board().buffer_zone_occupied());
+            assert!(!a.board().collides(a.active_block()));
+
+            a.set_active_rotation(1);
+            a.hard_drop();
+            assert!(!a.game_over());
+        }
+
+        #[test]
+        fn when_new_round_starts_restores_the_garbage() {
+            let mut game = game_with_garbage_start(4, 9);
+            let board = game.board().clone();
+
+            game.new_round();
+
+            assert_eq!(*game.board(), board);
+        }
+
+        #[test]
+        fn when_unset_starts_with_empty_board() {
+            let clock = MockClock::new(Instant::now());
+            let game = make_game(clock, MockInput::new([]), config(), 6);
+            assert_eq!(*game.board(), StandardBoard::new());
+        }
+    }
+
+    mod assert_invariants_tests {
+        use super::*;
+
+        #[test]
+        fn when_state_is_consistent_does_not_panic() {
+            let clock = MockClock::new(Instant::now());
+            let game = make_game(clock, MockInput::new([]), config(), 6);
+            game.assert_invariants();
+        }
+
+        #[test]
+        #[should_panic(expected = "overlap the board")]
+        fn when_active_block_overlaps_board_panics() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 6);
+            let mut cells = [[None; StandardBoard::COLUMNS]; StandardBoard::ROWS];
+            cells[StandardBoard::ROWS - 1].fill(Some(Cell::Block(BlockType::I)));
+            game.set_board(StandardBoard::from(cells));
+            let mut sunk = ActiveBlock::new(BlockType::T);
+            (0..StandardBoard::ROWS - 2).for_each(|_| sunk.move_down());
+            game.set_active_block(sunk);
+
+            game.assert_invariants();
+        }
+
+        #[test]
+        #[should_panic(expected = "out of bounds")]
+        fn when_active_block_is_out_of_bounds_panics() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 6);
+            let mut escaped = ActiveBlock::new(BlockType::T);
+            (0..StandardBoard::COLUMNS).for_each(|_| escaped.move_right());
+            game.set_active_block(escaped);
+
+            game.assert_invariants();
+        }
+
+        #[test]
+        fn when_game_is_over_does_not_panic() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 6);
+            game.board.fix_active_block(&game.active_block.clone());
+            game.game_over = true;
+
+            game.assert_invariants();
+        }
+    }
+
+    mod last_consolidation_moves_tests {
+        use super::*;
+
+        #[test]
+        fn when_clear_is_below_partial_row_reports_surviving_cells_shifting_down() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 4);
+            let mut cells = [[None; StandardBoard::COLUMNS]; StandardBoard::ROWS];
+            for (col, cell) in cells[StandardBoard::ROWS - 1].iter_mut().enumerate() {
+                if !(4..=5).contains(&col) {
+                    *cell = Some(Cell::Block(BlockType::I));
+                }
+            }
+            cells[StandardBoard::ROWS - 2][0] = Some(Cell::Block(BlockType::I));
+            game.set_board(StandardBoard::from(cells));
+            game.drop_active_block();
+
+            game.handle_gravity();
+
+            // The O block's top half and the lone cell beside it both fall one row.
+            assert_eq!(
+                game.last_consolidation_moves(),
+                vec![
+                    ((StandardBoard::ROWS - 2, 0), (StandardBoard::ROWS - 1, 0)),
+                    ((StandardBoard::ROWS - 2, 4), (StandardBoard::ROWS - 1, 4)),
+                    ((StandardBoard::ROWS - 2, 5), (StandardBoard::ROWS - 1, 5)),
+                ]
+            );
+        }
+
+        #[test]
+        fn when_no_lines_have_been_cleared_returns_no_moves() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 4);
+            game.drop_active_block();
+
+            game.handle_gravity();
+
+            assert!(game.last_consolidation_moves().is_empty());
+        }
+    }
+
+    mod last_action_tests {
+        use super::*;
+
+        #[test]
+        fn when_block_has_just_spawned_returns_none() {
+            let clock = MockClock::new(Instant::now());
+            let game = make_game(clock, MockInput::new([]), config(), 6);
+            assert_eq!(game.last_action(), LastAction::None);
+        }
+
+        #[test]
+        fn when_block_is_moved_then_locked_reports_move() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 6);
+            game.try_rotate(Direction::Right);
+            game.drop_active_block();
+            game.handle_move(Direction::Left);
+
+            game.handle_gravity();
+
+            let report = game.last_settle_report().expect("block should have locked");
+            assert_eq!(report.last_action, LastAction::Move);
+        }
+
+        #[test]
+        fn when_block_is_rotated_into_place_then_locked_reports_rotate() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 6);
+            game.handle_move(Direction::Left);
+            game.try_rotate(Direction::Right);
+            game.drop_active_block();
+
+            game.handle_gravity();
+
+            let report = game.last_settle_report().expect("block should have locked");
+            assert_eq!(report.last_action, LastAction::Rotate);
+        }
+
+        #[test]
+        fn when_move_is_blocked_keeps_previous_action() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 6);
+            (0..StandardBoard::COLUMNS).for_each(|_| game.handle_move(Direction::Left));
+            game.try_rotate(Direction::Right);
+            game.try_rotate(Direction::Left);
+
+            game.handle_move(Direction::Left);
+
+            assert_eq!(game.last_action(), LastAction::Rotate);
+        }
+
+        #[test]
+        fn when_next_block_spawns_resets_to_none() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 6);
+            game.handle_move(Direction::Left);
+            game.drop_active_block();
+
+            game.handle_gravity();
+
+            assert_eq!(game.last_action(), LastAction::None);
+        }
+    }
+
+    mod last_settle_report_tests {
+        use super::*;
+
+        /// Returns a game whose O block completes the bottom two rows when dropped.
+        fn game_with_double_setup(extra_cell: bool) -> MockGame {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 4);
+            let mut cells = [[None; StandardBoard::COLUMNS]; StandardBoard::ROWS];
+            for row in &mut cells[StandardBoard::ROWS - 2..] {
+                for (col, cell) in row.iter_mut().enumerate() {
+                    if !(4..=5).contains(&col) {
+                        *cell = Some(Cell::Block(BlockType::I));
+                    }
+                }
+            }
+            if extra_cell {
+                cells[StandardBoard::ROWS - 3][0] = Some(Cell::Block(BlockType::I));
+            }
+            game.set_board(StandardBoard::from(cells));
+            game
+        }
+
+        #[test]
+        fn when_no_block_has_landed_returns_none() {
+            let clock = MockClock::new(Instant::now());
+            let game = make_game(clock, MockInput::new([]), config(), 4);
+            assert_eq!(game.last_settle_report(), None);
+        }
+
+        #[test]
+        fn when_block_clears_two_lines_reports_double() {
+            let mut game = game_with_double_setup(true);
+            game.drop_active_block();
+
+            game.handle_gravity();
+
+            assert_eq!(
+                game.last_settle_report(),
+                Some(&SettleReport {
+                    lines_cleared: 2,
+                    perfect_clear: false,
+                    points_awarded: 300,
+                    combo: 0,
+                    back_to_back: false,
+                    last_action: LastAction::None,
+                    t_spin: TSpin::None,
+                })
+            );
+        }
+
+        #[test]
+        fn when_block_clears_the_board_reports_perfect_clear() {
+            let mut game = game_with_double_setup(false);
+            game.drop_active_block();
+
+            game.handle_gravity();
+
+            assert_eq!(
+                game.last_settle_report(),
+                Some(&SettleReport {
+                    lines_cleared: 2,
+                    perfect_clear: true,
+                    points_awarded: 300 + 1200,
+                    combo: 0,
+                    back_to_back: false,
+                    last_action: LastAction::None,
+                    t_spin: TSpin::None,
+                })
+            );
+        }
+
+        #[test]
+        fn when_block_clears_no_lines_reports_no_clear() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 4);
+            game.drop_active_block();
+
+            game.handle_gravity();
+
+            assert_eq!(
+                game.last_settle_report(),
+                Some(&SettleReport {
+                    lines_cleared: 0,
+                    perfect_clear: false,
+                    points_awarded: 0,
+                    combo: -1,
+                    back_to_back: false,
+                    last_action: LastAction::None,
+                    t_spin: TSpin::None,
+                })
+            );
+        }
+    }
+
+    mod garbage_lines_remaining_tests {
+        use super::*;
+
+        #[test]
+        fn counts_rows_containing_garbage() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 1);
+            assert_eq!(game.garbage_lines_remaining(), 0);
+
+            game.receive_garbage(3, 0);
+
+            assert_eq!(game.garbage_lines_remaining(), 3);
+        }
+    }
+
+    mod sealed_cell_count_tests {
+        use super::*;
+
+        #[test]
+        fn counts_empty_cells_beneath_a_sealed_roof() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 1);
+            let mut cells = [[None; StandardBoard::COLUMNS]; StandardBoard::ROWS];
+            cells[StandardBoard::ROWS - 2] = [Some(Cell::Garbage); StandardBoard::COLUMNS];
+            cells[StandardBoard::ROWS - 1][0] = Some(Cell::Garbage);
+            game.set_board(StandardBoard::from(cells));
+
+            assert_eq!(game.sealed_cell_count(), StandardBoard::COLUMNS - 1);
+        }
+    }
+
+    mod practice_mode_tests {
+        use super::*;
+
+        /// Returns a game whose O block tops out as soon as it lands.
+        fn game_about_to_top_out(practice_mode: bool) -> MockGame {
+            let clock = MockClock::new(Instant::now());
+            let cfg = Config {
+                practice_mode,
+                ..config()
+            };
+            let mut game = make_game(clock, MockInput::new([]), cfg, 4);
+            let mut cells = [[Some(Cell::Garbage); StandardBoard::COLUMNS]; StandardBoard::ROWS];
+            for row in &mut cells[..StandardBoard::BUFFER_ZONE_ROWS] {
+                *row = [None; StandardBoard::COLUMNS];
+            }
+            for row in &mut cells[StandardBoard::BUFFER_ZONE_ROWS..] {
+                row[0] = None;
+            }
+            game.set_board(StandardBoard::from(cells));
+            game
+        }
+
+        #[test]
+        fn when_practice_mode_is_on_top_out_rewinds_and_play_continues() {
+            let mut game = game_about_to_top_out(true);
+            let board = game.board().clone();
+
+            game.handle_gravity();
+
+            assert!(!game.game_over());
+            assert_eq!(*game.board(), board);
+            assert_eq!(*game.active_block(), ActiveBlock::new(BlockType::O));
+        }
+
+        #[test]
+        fn when_practice_mode_is_off_top_out_ends_game() {
+            let mut game = game_about_to_top_out(false);
+
+            game.handle_gravity();
+
+            assert!(game.game_over());
+        }
+
+        /// Returns an O block game whose queued I blocks spawn into a cell of the stack that juts
+        /// into the buffer zone beside the O block.
+        fn game_with_blocked_spawn(practice_mode: bool) -> MockGame {
+            let clock = MockClock::new(Instant::now());
+            let cfg = Config {
+                practice_mode,
+                ..config()
+            };
+            let mut game = make_game(clock, MockInput::new([]), cfg, 4);
+            game.queue = VecDeque::from([BlockType::I; 3]);
+            let mut cells = [[None; StandardBoard::COLUMNS]; StandardBoard::ROWS];
+            for row in &mut cells[1..] {
+                row[3] = Some(Cell::Garbage);
+            }
+            game.set_board(StandardBoard::from(cells));
+            game
+        }
+
+        #[test]
+        fn when_practice_mode_is_on_and_next_block_spawns_into_the_stack_play_continues() {
+            let mut game = game_with_blocked_spawn(true);
+            let board = game.board().clone();
+            game.drop_active_block();
+
+            game.handle_gravity();
+
+            assert!(!game.game_over());
+            assert_eq!(*game.board(), board);
+            assert_eq!(*game.active_block(), ActiveBlock::new(BlockType::O));
+        }
+
+        #[test]
+        fn when_practice_mode_is_off_and_next_block_spawns_into_the_stack_ends_game() {
+            let mut game = game_with_blocked_spawn(false);
+            game.drop_active_block();
+
+            game.handle_gravity();
+
+            assert!(game.game_over());
+        }
+
+        #[test]
+        fn when_practice_mode_is_on_and_hold_spawns_into_the_stack_refuses_the_hold() {
+            let mut game = game_with_blocked_spawn(true);
+
+            game.handle_hold();
+
+            assert!(!game.game_over());
+            assert_eq!(*game.active_block(), ActiveBlock::new(BlockType::O));
+            assert_eq!(game.held_block(), None);
+        }
+
+        #[test]
+        fn when_practice_mode_is_off_and_hold_spawns_into_the_stack_ends_game() {
+            let mut game = game_with_blocked_spawn(false);
+
+            game.handle_hold();
+
+            assert!(game.game_over());
+        }
+
+        #[test]
+        fn when_practice_mode_is_on_and_garbage_tops_out_discards_the_garbage() {
+            let clock = MockClock::new(Instant::now());
+            let cfg = Config {
+                practice_mode: true,
+                ..config()
+            };
+            let mut game = make_game(clock, MockInput::new([]), cfg, 4);
+            game.receive_garbage(StandardBoard::PLAYABLE_ROWS, 0);
+            let board = game.board().clone();
+            let active_block = game.active_block().clone();
+
+            game.receive_garbage(1, 0);
+
+            assert!(!game.game_over());
+            assert_eq!(*game.board(), board);
+            assert_eq!(*game.active_block(), active_block);
+        }
+    }
+
+    mod active_positions_tests {
+        use super::*;
+
+        #[test]
+        fn when_o_block_is_centered_returns_its_positions() {
+            let clock = MockClock::new(Instant::now());
+            let game = make_game(clock, MockInput::new([]), config(), 4);
+            assert_eq!(
+                game.active_positions(),
+                vec![(0, 4), (0, 5), (1, 4), (1, 5)]
+            );
+        }
+
+        #[test]
+        fn when_j_block_is_against_left_wall_returns_its_positions() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 2);
+            for _ in 0..StandardBoard::COLUMNS {
+                game.handle_move(Direction::Left);
+            }
+            assert_eq!(
+                game.active_positions(),
+                vec![(0, 0), (1, 0), (1, 1), (1, 2)]
+            );
+        }
+    }
+
+    mod ghost_position_tests {
+        use super::*;
+
+        #[test]
+        fn when_board_is_empty_returns_position_on_floor() {
+            let clock = MockClock::new(Instant::now());
+            let game = make_game(clock, MockInput::new([]), config(), 4);
+            let (_, col) = game.active_block().top_left();
+            assert_eq!(game.ghost_position(), (StandardBoard::ROWS - 2, col));
+            assert_eq!(game.active_block(), &ActiveBlock::new(BlockType::O));
+        }
+
+        #[test]
+        fn when_stack_is_jagged_returns_position_on_highest_cell_beneath_block() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 4);
+            let mut cells = [[None; StandardBoard::COLUMNS]; StandardBoard::ROWS];
+            for row in &mut cells[StandardBoard::ROWS - 4..] {
+                row[5] = Some(Cell::Block(BlockType::I));
+            }
+            cells[StandardBoard::ROWS - 1][4] = Some(Cell::Block(BlockType::I));
+            game.set_board(StandardBoard::from(cells));
+
+            let (_, col) = game.active_block().top_left();
+            assert_eq!(game.ghost_position(), (StandardBoard::ROWS - 6, col));
+        }
+
+        #[test]
+        fn when_block_is_resting_returns_current_position() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 4);
+            game.drop_active_block();
+            assert_eq!(game.ghost_position(), game.active_block().top_left());
+        }
+    }
+
+    mod projected_position_after_gravity_tests {
+        use super::*;
+
+        #[test]
+        fn when_block_is_floating_returns_position_one_row_lower() {
+            let clock = MockClock::new(Instant::now());
+            let game = make_game(clock, MockInput::new([]), config(), 1);
+            let (row, col) = game.active_block().top_left();
+            assert_eq!(game.projected_position_after_gravity(), (row + 1, col));
+        }
+
+        #[test]
+        fn when_block_is_grounded_returns_current_position() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 1);
+            // I at its initial position occupies row 1; move it down to rest on the floor.
+            for _ in 0..StandardBoard::ROWS - 2 {
+                game.active_block.move_down();
+            }
+            let before = game.active_block().top_left();
+            assert_eq!(game.projected_position_after_gravity(), before);
+        }
+    }
+
+    mod placement_iter_tests {
+        use super::*;
+
+        #[test]
+        fn yields_reachable_placements_in_order() {
+            let clock = MockClock::new(Instant::now());
+            let game = make_game(clock, MockInput::new([]), config(), 1);
+
+            let placements: Vec<Placement> = game.placement_iter().collect();
+
+            assert_eq!(placements.len(), 34);
+            assert_eq!(placements, game.reachable_placements());
+        }
+    }
+
+    mod solve_perfect_clear_tests {
+        use super::*;
+
+        /// Returns a game of I blocks whose bottom `rows` rows are full except for columns 0 to 3.
+        fn game_with_i_shaped_gaps(rows: usize) -> MockGame {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 1);
+            let mut cells = [[None; StandardBoard::COLUMNS]; StandardBoard::ROWS];
+            for row in &mut cells[StandardBoard::ROWS - rows..] {
+                row[4..].fill(Some(Cell::Garbage));
+            }
+            game.set_board(StandardBoard::from(cells));
+            game
+        }
+
+        #[test]
+        fn when_one_piece_clears_the_board_returns_its_placement() {
+            let game = game_with_i_shaped_gaps(1);
+
+            let solution = game.solve_perfect_clear(1).unwrap();
+
+            assert_eq!(solution.len(), 1);
+            assert_eq!(
+                solution[0].positions(),
+                vec![
+                    (StandardBoard::ROWS - 1, 0),
+                    (StandardBoard::ROWS - 1, 1),
+                    (StandardBoard::ROWS - 1, 2),
+                    (StandardBoard::ROWS - 1, 3)
+                ]
+            );
+        }
+
+        #[test]
+        fn when_two_pieces_clear_the_board_returns_both_placements() {
+            let game = game_with_i_shaped_gaps(2);
+
+            let solution = game.solve_perfect_clear(4).unwrap();
+
+            assert_eq!(solution.len(), 2);
+            let mut board = game.board().clone();
+            for placement in &solution {
+                board.fix_active_block(placement.block());
+                board.clear_lines();
+            }
+            assert_eq!(board, StandardBoard::new());
+        }
+
+        #[test]
+        fn when_a_shorter_solution_exists_returns_it() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 4);
+            game.queue.extend([BlockType::O; 2]);
+            let mut cells = [[None; StandardBoard::COLUMNS]; StandardBoard::ROWS];
+            for row in &mut cells[StandardBoard::ROWS - 2..] {
+                row[..StandardBoard::COLUMNS - 2].fill(Some(Cell::Garbage));
+            }
+            game.set_board(StandardBoard::from(cells));
+
+            // Six O blocks can also clear the board by first stacking two rows on the left, which
+            // a depth-first search reaches before the single block that fills the gap.
+            let solution = game.solve_perfect_clear(6).unwrap();
+
+            assert_eq!(solution.len(), 1);
+            assert_eq!(
+                solution[0].positions(),
+                vec![
+                    (StandardBoard::ROWS - 2, StandardBoard::COLUMNS - 2),
+                    (StandardBoard::ROWS - 2, StandardBoard::COLUMNS - 1),
+                    (StandardBoard::ROWS - 1, StandardBoard::COLUMNS - 2),
+                    (StandardBoard::ROWS - 1, StandardBoard::COLUMNS - 1)
+                ]
+            );
+        }
+
+        #[test]
+        fn when_too_few_pieces_are_allowed_returns_none() {
+            let game = game_with_i_shaped_gaps(2);
+            assert_eq!(game.solve_perfect_clear(1), None);
+        }
+
+        #[test]
+        fn when_board_is_empty_returns_no_placements() {
+            let clock = MockClock::new(Instant::now());
+            let game = make_game(clock, MockInput::new([]), config(), 1);
+            assert_eq!(game.solve_perfect_clear(4), Some(Vec::new()));
+        }
+    }
+
+    mod best_clears_for_active_tests {
+        use super::*;
+
+        #[test]
+        fn when_no_placement_completes_a_line_returns_0() {
+            let clock = MockClock::new(Instant::now());
+            let game = make_game(clock, MockInput::new([]), config(), 1);
+            assert_eq!(game.best_clears_for_active(), 0);
+        }
+
+        #[test]
+        fn when_i_block_fits_a_four_row_well_returns_4() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 1);
+            let mut cells = [[None; StandardBoard::COLUMNS]; StandardBoard::ROWS];
+            for row in &mut cells[StandardBoard::ROWS - 4..] {
+                *row = [Some(Cell::Block(BlockType::O)); StandardBoard::COLUMNS];
+                row[0] = None;
+            }
+            game.board = StandardBoard::from(cells);
+
+            assert_eq!(game.best_clears_for_active(), 4);
+            assert_eq!(
+                game.board,
+                StandardBoard::from(cells),
+                "board should not be modified"
+            );
+        }
+    }
+
+    mod receive_garbage_tests {
+        use super::*;
+
+        #[test]
+        fn when_garbage_reaches_active_block_pushes_block_upwards() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 1);
+            game.board.drop_block(&mut game.active_block);
+            let (row, col) = game.active_block().top_left();
+
+            game.receive_garbage(2, 0);
+
+            assert_eq!(game.active_block().top_left(), (row - 2, col));
+            assert!(!game.game_over());
+        }
+
+        #[test]
+        fn when_garbage_pushes_stack_into_buffer_zone_ends_game() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 1);
+            game.board
+                .add_garbage_lines(StandardBoard::PLAYABLE_ROWS, 0);
+
+            game.receive_garbage(1, 0);
+
+            assert!(game.game_over());
+        }
+    }
+
+    mod finesse_tests {
+        use super::*;
+
+        /// Returns a board with a wall hanging from the top of column 7 that leaves the bottom two
+        /// rows open beneath it, and optionally a cell blocking the gap from the left.
+        fn board_with_overhang(gap_blocked: bool) -> StandardBoard {
+            let mut cells = [[None; StandardBoard::COLUMNS]; StandardBoard::ROWS];
+            for row in &mut cells[..StandardBoard::ROWS - 2] {
+                row[7] = Some(Cell::Garbage);
+            }
+            if gap_blocked {
+                cells[StandardBoard::ROWS - 2][6] = Some(Cell::Garbage);
+            }
+            StandardBoard::from(cells)
+        }
+
+        /// Returns the bounding box column that places the active O block's left cells in
+        /// `column`.
+        fn o_column_for(game: &MockGame, column: usize) -> isize {
+            let (_, c) = game.active_block().top_left();
+            let leftmost = game
+                .active_positions()
+                .iter()
+                .map(|&(_, c)| c)
+                .min()
+                .unwrap();
+            c + column as isize - leftmost as isize
+        }
+
+        #[test]
+        fn when_target_is_spawn_position_returns_hard_drop() {
+            let clock = MockClock::new(Instant::now());
+            let game = make_game(clock, MockInput::new([]), config(), 4);
+            let (_, c) = game.active_block().top_left();
+
+            assert_eq!(
+                game.finesse(c, RotationIndex::default()),
+                Some(vec![Input::HardDrop])
+            );
+        }
+
+        #[test]
+        fn when_target_is_left_of_spawn_returns_left_moves() {
+            let clock = MockClock::new(Instant::now());
+            let game = make_game(clock, MockInput::new([]), config(), 4);
+            let (_, c) = game.active_block().top_left();
+
+            assert_eq!(
+                game.finesse(c - 2, RotationIndex::default()),
+                Some(vec![Input::Left, Input::Left, Input::HardDrop])
+            );
+        }
+
+        #[test]
+        fn when_target_is_rotated_returns_the_shortest_rotation() {
+            let clock = MockClock::new(Instant::now());
+            let game = make_game(clock, MockInput::new([]), config(), 6);
+            let (_, c) = game.active_block().top_left();
+
+            assert_eq!(
+                game.finesse(c, RotationIndex::try_from(1).unwrap()),
+                Some(vec![Input::RotateRight, Input::HardDrop])
+            );
+            assert_eq!(
+                game.finesse(c, RotationIndex::try_from(3).unwrap()),
+                Some(vec![Input::RotateLeft, Input::HardDrop])
+            );
+        }
+
+        #[test]
+        fn when_target_is_under_an_overhang_tucks_the_block_beneath_it() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 4);
+            game.set_board(board_with_overhang(false));
+            let column = o_column_for(&game, 8);
+
+            let inputs = game.finesse(column, RotationIndex::default()).unwrap();
+            assert_eq!(inputs[inputs.len() - 2..], [Input::Right, Input::HardDrop]);
+            game.step(&inputs);
+
+            let bottom = StandardBoard::ROWS - 1;
+            for (r, c) in [(bottom - 1, 8), (bottom - 1, 9), (bottom, 8), (bottom, 9)] {
+                assert!(game.board().is_blocked(r, c));
+            }
+        }
+
+        #[test]
+        fn when_target_is_cut_off_by_an_overhang_returns_none() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 4);
+            game.set_board(board_with_overhang(true));
+            let column = o_column_for(&game, 8);
+
+            assert_eq!(game.finesse(column, RotationIndex::default()), None);
+        }
+    }
+
+    mod legal_moves_tests {
+        use super::*;
+
+        #[test]
+        fn when_block_is_unobstructed_returns_all_moves() {
+            let clock = MockClock::new(Instant::now());
+            let game = make_game(clock, MockInput::new([]), config(), 6);
+            assert_eq!(
+                game.legal_moves(),
+                vec![
+                    Input::Left,
+                    Input::Right,
+                    Input::Down,
+                    Input::RotateLeft,
+                    Input::RotateRight,
+                    Input::HardDrop,
+                    Input::Hold,
+                ]
+            );
+        }
+
+        #[test]
+        fn when_block_is_pinned_to_left_wall_excludes_left() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 6);
+            for _ in 0..StandardBoard::COLUMNS {
+                game.handle_move(Direction::Left);
+            }
+            assert!(!game.legal_moves().contains(&Input::Left));
+            assert!(game.legal_moves().contains(&Input::Right));
+        }
+
+        #[test]
+        fn when_block_is_pinned_to_right_wall_excludes_right() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 6);
+            for _ in 0..StandardBoard::COLUMNS {
+                game.handle_move(Direction::Right);
+            }
+            assert!(!game.legal_moves().contains(&Input::Right));
+            assert!(game.legal_moves().contains(&Input::Left));
+        }
+
+        #[test]
+        fn when_block_is_o_excludes_rotations() {
+            let clock = MockClock::new(Instant::now());
+            let game = make_game(clock, MockInput::new([]), config(), 4);
+            assert_eq!(
+                game.legal_moves(),
+                vec![
+                    Input::Left,
+                    Input::Right,
+                    Input::Down,
+                    Input::HardDrop,
+                    Input::Hold
+                ]
+            );
+        }
+
+        #[test]
+        fn when_block_is_grounded_excludes_down() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 6);
+            game.drop_active_block();
+            assert!(!game.legal_moves().contains(&Input::Down));
+            assert!(game.legal_moves().contains(&Input::Left));
+            assert!(game.legal_moves().contains(&Input::HardDrop));
+        }
+
+        #[test]
+        fn when_hold_has_been_used_excludes_hold() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 6);
+            game.handle_hold();
+            assert!(!game.legal_moves().contains(&Input::Hold));
+            assert!(game.legal_moves().contains(&Input::HardDrop));
+        }
+
+        #[test]
+        fn when_game_is_over_returns_no_moves() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 6);
+            game.game_over = true;
+            assert!(game.legal_moves().is_empty());
+        }
+    }
+
+    mod handle_move_tests {
+        use super::*;
+
+        /// Returns a game with an O block resting on the floor, with a column of cells immediately
+        /// to either side of it.
+        fn game_with_o_between_cells() -> MockGame {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 4);
+            let mut cells = [[None; StandardBoard::COLUMNS]; StandardBoard::ROWS];
+            for row in &mut cells[StandardBoard::ROWS - 2..] {
+                row[3] = Some(Cell::Block(BlockType::I));
+                row[6] = Some(Cell::Block(BlockType::I));
+            }
+            game.set_board(StandardBoard::from(cells));
+            game.drop_active_block();
+            game
+        }
+
+        #[test]
+        fn when_block_is_against_left_wall_does_not_move() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 2);
+            (0..StandardBoard::COLUMNS).for_each(|_| game.handle_move(Direction::Left));
+            let before = game.active_block().clone();
+
+            game.handle_move(Direction::Left);
+
+            assert_eq!(*game.active_block(), before);
+            assert_eq!(before.top_left().1, 0);
+        }
+
+        #[test]
+        fn when_block_is_against_right_wall_does_not_move() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 2);
+            (0..StandardBoard::COLUMNS).for_each(|_| game.handle_move(Direction::Right));
+            let before = game.active_block().clone();
+
+            game.handle_move(Direction::Right);
+
+            assert_eq!(*game.active_block(), before);
+            assert!(
+                game.active_positions()
+                    .iter()
+                    .any(|&(_, c)| c == StandardBoard::COLUMNS - 1)
+            );
+        }
+
+        #[test]
+        fn when_cell_is_to_the_left_does_not_move() {
+            let mut game = game_with_o_between_cells();
+            let before = game.active_block().clone();
+
+            game.handle_move(Direction::Left);
+
+            assert_eq!(*game.active_block(), before);
+        }
+
+        #[test]
+        fn when_cell_is_to_the_right_does_not_move() {
+            let mut game = game_with_o_between_cells();
+            let before = game.active_block().clone();
+
+            game.handle_move(Direction::Right);
+
+            assert_eq!(*game.active_block(), before);
+        }
+    }
+
+    mod set_active_rotation_tests {
+        use super::*;
+
+        #[test]
+        fn when_rotation_is_negative_wraps_counter_clockwise() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 6);
+            let mut expected = game.active_block().clone();
+            expected.rotate_counter_clockwise();
+
+            assert!(game.set_active_rotation(-1));
+            assert_eq!(*game.active_block(), expected);
+        }
+
+        #[test]
+        fn when_rotation_exceeds_3_wraps_clockwise() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 6);
+            let mut expected = game.active_block().clone();
+            expected.rotate_clockwise();
+
+            assert!(game.set_active_rotation(5));
+            assert_eq!(*game.active_block(), expected);
+        }
+
+        #[test]
+        fn when_rotated_block_collides_leaves_block_unchanged() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 6);
+            game.try_rotate(Direction::Right);
+            for _ in 0..StandardBoard::COLUMNS {
+                game.handle_move(Direction::Left);
+            }
+            let before = game.active_block().clone();
+
+            assert!(!game.set_active_rotation(2));
+            assert_eq!(*game.active_block(), before);
+        }
+    }
+
+    mod try_rotate_tests {
+        use super::*;
+
+        #[test]
+        fn when_rotation_fits_returns_zero_offset() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 6);
+            let top_left = game.active_block().top_left();
+
+            assert_eq!(game.try_rotate(Direction::Right), Some((0, 0)));
+            assert_eq!(game.active_block().top_left(), top_left);
+        }
+
+        #[test]
+        fn when_i_block_has_just_spawned_rotates_within_buffer_zone() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 1);
+
+            assert_eq!(game.try_rotate(Direction::Right), Some((0, 0)));
+            assert_eq!(
+                game.active_positions(),
+                vec![(0, 5), (1, 5), (2, 5), (3, 5)]
+            );
+
+            assert_eq!(game.try_rotate(Direction::Left), Some((0, 0)));
+            assert_eq!(game.try_rotate(Direction::Left), Some((0, 0)));
+            assert_eq!(
+                game.active_positions(),
+                vec![(0, 4), (1, 4), (2, 4), (3, 4)]
+            );
+        }
+
+        #[test]
+        fn when_j_block_has_just_spawned_rotates_within_buffer_zone() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 2);
+
+            assert_eq!(game.try_rotate(Direction::Right), Some((0, 0)));
+            assert_eq!(
+                game.active_positions(),
+                vec![(0, 5), (0, 6), (1, 5), (2, 5)]
+            );
+
+            assert_eq!(game.try_rotate(Direction::Left), Some((0, 0)));
+            assert_eq!(game.try_rotate(Direction::Left), Some((0, 0)));
+            assert_eq!(
+                game.active_positions(),
+                vec![(0, 5), (1, 5), (2, 4), (2, 5)]
+            );
+        }
+
+        #[test]
+        fn when_only_upward_kicks_fit_in_buffer_zone_rejects_rotation() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 2);
+            // Fill everything below the buffer zone's top row, so that the rotated J could only
+            // fit by moving above row 0.
+            let mut cells = [[Some(Cell::Garbage); StandardBoard::COLUMNS]; StandardBoard::ROWS];
+            for (r, c) in game.active_positions() {
+                cells[r][c] = None;
+            }
+            cells[0] = [None; StandardBoard::COLUMNS];
+            game.set_board(StandardBoard::from(cells));
+            let before = game.active_block().clone();
+
+            assert_eq!(game.try_rotate(Direction::Right), None);
+            assert_eq!(*game.active_block(), before);
+        }
+
+        #[test]
+        fn when_rotation_overlaps_left_wall_kicks_right() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 6);
+            game.try_rotate(Direction::Right);
+            for _ in 0..StandardBoard::COLUMNS {
+                game.handle_move(Direction::Left);
+            }
+            for _ in 0..4 {
+                game.handle_gravity();
+            }
+            // The vertical T's bounding box overhangs the left wall by one column.
+            assert_eq!(game.active_block().top_left(), (4, -1));
+
+            assert_eq!(game.try_rotate(Direction::Right), Some((0, 1)));
+            assert_eq!(game.active_block().top_left(), (4, 0));
+            assert_eq!(
+                game.active_positions(),
+                vec![(5, 0), (5, 1), (5, 2), (6, 1)]
+            );
+        }
+
+        #[test]
+        fn when_rotation_input_is_kicked_reports_offset_until_next_block() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 6);
+            assert_eq!(game.last_kick(), None);
+            game.handle_input(Input::RotateRight);
+            assert_eq!(game.last_kick(), Some((0, 0)));
+            for _ in 0..StandardBoard::COLUMNS {
+                game.handle_move(Direction::Left);
+            }
+            for _ in 0..4 {
+                game.handle_gravity();
+            }
+
+            game.handle_input(Input::RotateRight);
+            assert_eq!(game.last_kick(), Some((0, 1)));
+
+            game.handle_input(Input::HardDrop);
+            assert_eq!(game.last_kick(), None);
+        }
+
+        #[test]
+        fn when_no_kick_fits_returns_none_and_leaves_block_unchanged() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 6);
+            for _ in 0..4 {
+                game.handle_gravity();
+            }
+            // Fill every cell except those occupied by the T.
+            let mut cells = [[Some(Cell::Garbage); StandardBoard::COLUMNS]; StandardBoard::ROWS];
+            for (r, c) in game.active_positions() {
+                cells[r][c] = None;
+            }
+            game.set_board(StandardBoard::from(cells));
+            let before = game.active_block().clone();
+
+            assert_eq!(game.try_rotate(Direction::Left), None);
+            assert_eq!(*game.active_block(), before);
+        }
+
+        #[test]
+        fn when_vertical_i_block_is_wedged_against_right_wall_reverts_rotation() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 1);
+            // Leave only the rightmost column free, so no kick can fit a horizontal I.
+            let mut cells = [[Some(Cell::Garbage); StandardBoard::COLUMNS]; StandardBoard::ROWS];
+            for row in &mut cells {
+                row[StandardBoard::COLUMNS - 1] = None;
+            }
+            game.try_rotate(Direction::Right);
+            (0..StandardBoard::COLUMNS).for_each(|_| game.handle_move(Direction::Right));
+            game.drop_active_block();
+            game.set_board(StandardBoard::from(cells));
+            let before = game.active_block().clone();
+
+            assert_eq!(game.try_rotate(Direction::Right), None);
+            assert_eq!(*game.active_block(), before);
+            assert_eq!(game.try_rotate(Direction::Left), None);
+            assert_eq!(*game.active_block(), before);
+            assert!(
+                game.active_positions()
+                    .iter()
+                    .all(|&(_, c)| c == StandardBoard::COLUMNS - 1)
+            );
+        }
+
+        #[test]
+        fn when_block_is_o_block_is_unchanged() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 4);
+            let before = game.active_block().clone();
+
+            for _ in 0..4 {
+                assert_eq!(game.try_rotate(Direction::Right), Some((0, 0)));
+                assert_eq!(*game.active_block(), before);
+            }
+            for _ in 0..4 {
+                assert_eq!(game.try_rotate(Direction::Left), Some((0, 0)));
+                assert_eq!(*game.active_block(), before);
+            }
+        }
+    }
+
+    mod handle_hold_tests {
+        use super::*;
+
+        fn game_with_queue(first: BlockType, queued: [BlockType; 3]) -> MockGame {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 4);
+            game.active_block = ActiveBlock::new(first);
+            game.queue = VecDeque::from(queued);
+            game
+        }
+
+        #[test]
+        fn when_hold_is_empty_stashes_block_and_spawns_next() {
+            let mut game =
+                game_with_queue(BlockType::T, [BlockType::S, BlockType::Z, BlockType::L]);
+
+            game.handle_hold();
+
+            assert_eq!(game.held_block(), Some(BlockType::T));
+            assert_eq!(*game.active_block(), ActiveBlock::new(BlockType::S));
+            assert_eq!(game.queue(), &[BlockType::Z, BlockType::L, BlockType::O]);
+        }
+
+        #[test]
+        fn when_hold_was_used_for_this_block_does_nothing() {
+            let mut game =
+                game_with_queue(BlockType::T, [BlockType::S, BlockType::Z, BlockType::L]);
+            game.handle_hold();
+            let before = game.snapshot();
+
+            game.handle_hold();
+
+            assert_eq!(game.snapshot(), before);
+            assert_eq!(game.held_block(), Some(BlockType::T));
+        }
+
+        #[test]
+        fn when_block_has_locked_since_last_hold_swaps_held_block_in() {
+            let mut game =
+                game_with_queue(BlockType::T, [BlockType::S, BlockType::Z, BlockType::L]);
+            game.handle_hold();
+            game.hard_drop();
+            assert_eq!(game.active_block().block_type(), BlockType::Z);
+
+            game.handle_hold();
+
+            assert_eq!(game.held_block(), Some(BlockType::Z));
+            assert_eq!(*game.active_block(), ActiveBlock::new(BlockType::T));
+        }
+
+        #[test]
+        fn when_held_block_is_swapped_in_resets_its_position_and_rotation() {
+            let mut game =
+                game_with_queue(BlockType::T, [BlockType::S, BlockType::Z, BlockType::L]);
+            game.try_rotate(Direction::Right);
+            game.handle_move(Direction::Left);
+            game.handle_gravity();
+            game.handle_hold();
+            game.hard_drop();
+
+            game.handle_hold();
+
+            assert_eq!(*game.active_block(), ActiveBlock::new(BlockType::T));
+        }
+
+        #[test]
+        fn when_swapped_in_block_spawns_overlapping_the_stack_ends_game() {
+            let mut game =
+                game_with_queue(BlockType::T, [BlockType::O, BlockType::Z, BlockType::L]);
+            game.set_active_block(ActiveBlock::from_parts(BlockType::T, (10, 3), 0).unwrap());
+            let mut cells = [[None; StandardBoard::COLUMNS]; StandardBoard::ROWS];
+            cells[1][4] = Some(Cell::Garbage);
+            game.set_board(StandardBoard::from(cells));
+
+            game.handle_hold();
+
+            assert!(game.game_over());
+        }
+
+        #[test]
+        fn when_swapped_in_block_spawns_clear_of_the_stack_continues() {
+            let mut game =
+                game_with_queue(BlockType::T, [BlockType::O, BlockType::Z, BlockType::L]);
+            game.set_active_block(ActiveBlock::from_parts(BlockType::T, (10, 3), 0).unwrap());
+            let mut cells = [[None; StandardBoard::COLUMNS]; StandardBoard::ROWS];
+            cells[1][0] = Some(Cell::Garbage);
+            game.set_board(StandardBoard::from(cells));
+
+            game.handle_hold();
+
+            assert!(!game.game_over());
+        }
+
+        #[test]
+        fn when_a_block_is_held_initially_hold_swaps_it_in() {
+            let clock = MockClock::new(Instant::now());
+            let config = Config {
+                initial_held_block: Some(BlockType::I),
+                ..config()
+            };
+            let mut game = make_game(clock, MockInput::new([]), config, 4);
+            assert_eq!(game.held_block(), Some(BlockType::I));
+
+            game.handle_hold();
+
+            assert_eq!(game.held_block(), Some(BlockType::O));
+            assert_eq!(*game.active_block(), ActiveBlock::new(BlockType::I));
+        }
+
+        #[test]
+        fn when_a_block_is_held_initially_new_round_restores_it() {
+            let clock = MockClock::new(Instant::now());
+            let config = Config {
+                initial_held_block: Some(BlockType::I),
+                ..config()
+            };
+            let mut game = make_game(clock, MockInput::new([]), config, 4);
+            game.handle_hold();
+
+            game.new_round();
+
+            assert_eq!(game.held_block(), Some(BlockType::I));
+            assert!(game.can_hold());
+        }
+
+        #[test]
+        fn when_new_round_starts_clears_hold() {
+            let mut game =
+                game_with_queue(BlockType::T, [BlockType::S, BlockType::Z, BlockType::L]);
+            game.handle_hold();
+
+            game.new_round();
+
+            assert_eq!(game.held_block(), None);
+        }
+    }
+
+    mod soft_drop_tests {
+        use super::*;
+
+        #[test]
+        fn when_cell_below_is_free_moves_block_down_and_awards_a_point() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 4);
+            let mut expected = game.active_block().clone();
+            expected.move_down();
+
+            game.soft_drop();
+
+            assert_eq!(*game.active_block(), expected);
+            assert_eq!(game.score(), 1);
+        }
+
+        #[test]
+        fn when_block_is_grounded_neither_moves_nor_locks_nor_scores() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 4);
+            game.drop_active_block();
+            let before = game.active_block().clone();
+
+            game.soft_drop();
+
+            assert_eq!(*game.active_block(), before);
+            assert_eq!(*game.board(), StandardBoard::new());
+            assert_eq!(game.score(), 0);
+        }
+    }
+
+    mod resume_tests {
+        use super::*;
+        use crate::block_generator::PieceDef;
+
+        fn mid_game() -> MockGame {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 4);
+            game.hard_drop();
+            game.handle_move(Direction::Left);
+            game.handle_hold();
+            game
+        }
+
+        #[cfg(feature = "serde")]
+        #[test]
+        fn saved_game_round_trips_through_json() {
+            let saved = mid_game().save_state();
+
+            let json = serde_json::to_string(&saved).unwrap();
+
+            assert_eq!(serde_json::from_str::<SavedGame>(&json).unwrap(), saved);
+        }
+
+        #[test]
+        fn when_resumed_restores_board_score_active_block_and_queue() {
+            let game = mid_game();
+            let saved = game.save_state();
+
+            let resumed = Game::resume_with_clock(
+                BlockGenerator::with_mock_sampler(1),
+                MockInput::new([]),
+                config(),
+                MockClock::new(Instant::now()),
+                saved.clone(),
+            )
+            .unwrap();
+
+            assert_eq!(resumed.snapshot(), game.snapshot());
+            assert_eq!(resumed.held_block(), Some(BlockType::O));
+            assert_eq!(resumed.save_state(), saved);
+        }
+
+        #[test]
+        fn when_resumed_mid_combo_restores_combo() {
+            let mut game = mid_game();
+            game.combo = 2;
+
+            let resumed = Game::resume_with_clock(
+                BlockGenerator::with_mock_sampler(1),
+                MockInput::new([]),
+                config(),
+                MockClock::new(Instant::now()),
+                game.save_state(),
+            )
+            .unwrap();
+
+            assert_eq!(resumed.combo(), 2);
+        }
+
+        #[test]
+        fn when_resumed_mid_back_to_back_chain_restores_it() {
+            let mut game = mid_game();
+            game.back_to_back = true;
+
+            let resumed = Game::resume_with_clock(
+                BlockGenerator::with_mock_sampler(1),
+                MockInput::new([]),
+                config(),
+                MockClock::new(Instant::now()),
+                game.save_state(),
+            )
+            .unwrap();
+
+            assert!(resumed.back_to_back());
+        }
+
+        #[test]
+        fn when_resumed_restores_statistics() {
+            let game = mid_game();
+
+            let resumed = Game::resume_with_clock(
+                BlockGenerator::with_mock_sampler(1),
+                MockInput::new([]),
+                config(),
+                MockClock::new(Instant::now()),
+                game.save_state(),
+            )
+            .unwrap();
+
+            assert_eq!(resumed.statistics().pieces_of(BlockType::O), 1);
+            assert_eq!(resumed.statistics(), game.statistics());
+        }
+
+        #[test]
+        fn when_resumed_mid_ultra_keeps_the_time_already_played() {
+            let config = Config {
+                mode: GameMode::Ultra {
+                    duration: FRAME_INTERVAL * 3,
+                },
+                ..config()
+            };
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config.clone(), 1);
+            game.advance_tick(&[]);
+            game.advance_tick(&[]);
+
+            let mut resumed = Game::resume_with_clock(
+                BlockGenerator::with_mock_sampler(1),
+                MockInput::new([]),
+                config,
+                MockClock::new(Instant::now()),
+                game.save_state(),
+            )
+            .unwrap();
+
+            assert_eq!(resumed.elapsed(), FRAME_INTERVAL * 2);
+            resumed.advance_tick(&[]);
+            assert!(resumed.is_complete());
+        }
+
+        #[cfg(feature = "serde")]
+        #[test]
+        fn statistics_of_custom_pieces_round_trip_through_json() {
+            let mut statistics = Statistics::default();
+            statistics.record_lock(BlockType::Custom(0), 2);
+            statistics.record_lock(BlockType::I, 1);
+
+            let json = serde_json::to_string(&statistics).unwrap();
+
+            assert_eq!(
+                serde_json::from_str::<Statistics>(&json).unwrap(),
+                statistics
+            );
+        }
+
+        #[test]
+        fn when_queue_length_differs_from_preview_count_returns_error() {
+            let saved = mid_game().save_state();
+            let config = Config {
+                preview_count: 1,
+                ..config()
+            };
+
+            let result = Game::resume_with_clock(
+                BlockGenerator::with_mock_sampler(1),
+                MockInput::new([]),
+                config,
+                MockClock::new(Instant::now()),
+                saved,
+            );
+
+            assert!(result.is_err());
+        }
+
+        #[test]
+        fn when_active_block_overlaps_board_returns_error() {
+            let mut game = mid_game();
+            let mut cells = [[None; StandardBoard::COLUMNS]; StandardBoard::ROWS];
+            cells[1] = [Some(Cell::Garbage); StandardBoard::COLUMNS];
+            game.set_board(StandardBoard::from(cells));
+
+            let result = Game::resume_with_clock(
+                BlockGenerator::with_mock_sampler(1),
+                MockInput::new([]),
+                config(),
+                MockClock::new(Instant::now()),
+                game.save_state(),
+            );
+
+            assert!(result.is_err());
+        }
+
+        #[test]
+        fn when_queue_holds_an_unregistered_custom_piece_returns_error() {
+            let mut saved = mid_game().save_state();
+            saved.queue[0] = BlockType::Custom(0);
+
+            let result = Game::resume_with_clock(
+                BlockGenerator::with_mock_sampler(1),
+                MockInput::new([]),
+                config(),
+                MockClock::new(Instant::now()),
+                saved,
+            );
+
+            assert!(result.is_err());
+        }
+
+        #[test]
+        fn when_board_holds_an_unregistered_custom_piece_returns_error() {
+            let mut game = mid_game();
+            let mut cells = [[None; StandardBoard::COLUMNS]; StandardBoard::ROWS];
+            cells[StandardBoard::ROWS - 1][0] = Some(Cell::Block(BlockType::Custom(0)));
+            game.set_board(StandardBoard::from(cells));
+
+            let result = Game::resume_with_clock(
+                BlockGenerator::with_mock_sampler(1),
+                MockInput::new([]),
+                config(),
+                MockClock::new(Instant::now()),
+                game.save_state(),
+            );
+
+            assert!(result.is_err());
+        }
+
+        #[test]
+        fn when_active_block_is_a_registered_custom_piece_restores_its_shape() {
+            let mut block_generator = BlockGenerator::seeded(0);
+            let block_type = block_generator
+                .register(PieceDef {
+                    rotations: [
+                        vec![(1, 0), (1, 1)],
+                        vec![(0, 1), (1, 1)],
+                        vec![(1, 0), (1, 1)],
+                        vec![(0, 1), (1, 1)],
+                    ],
+                    color: (1, 2, 3),
+                })
+                .unwrap();
+            let mut game = Game::new_with_clock(
+                block_generator.clone(),
+                MockInput::new([]),
+                config(),
+                MockClock::new(Instant::now()),
+            );
+            game.active_block = ActiveBlock::new_in(block_type, game.custom_pieces());
+
+            let resumed = Game::resume_with_clock(
+                block_generator,
+                MockInput::new([]),
+                config(),
+                MockClock::new(Instant::now()),
+                game.save_state(),
+            )
+            .unwrap();
+
+            assert_eq!(resumed.active_block(), game.active_block());
+            assert_eq!(
+                resumed.custom_pieces().grid_cell(block_type),
+                game.custom_pieces().grid_cell(block_type)
+            );
+        }
+
+        #[test]
+        fn when_active_block_is_an_unregistered_custom_piece_returns_error() {
+            let mut block_generator = BlockGenerator::seeded(0);
+            let block_type = block_generator
+                .register(PieceDef {
+                    rotations: [
+                        vec![(1, 0), (1, 1)],
+                        vec![(0, 1), (1, 1)],
+                        vec![(1, 0), (1, 1)],
+                        vec![(0, 1), (1, 1)],
+                    ],
+                    color: (1, 2, 3),
+                })
+                .unwrap();
+            let mut game = Game::new_with_clock(
+                block_generator,
+                MockInput::new([]),
+                config(),
+                MockClock::new(Instant::now()),
+            );
+            game.active_block = ActiveBlock::new_in(block_type, game.custom_pieces());
+
+            let result = Game::resume_with_clock(
+                BlockGenerator::with_mock_sampler(1),
+                MockInput::new([]),
+                config(),
+                MockClock::new(Instant::now()),
+                game.save_state(),
+            );
+
+            assert!(result.is_err());
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    mod save_tests {
+        use std::path::PathBuf;
+
+        use super::*;
+
+        fn temp_path(name: &str) -> PathBuf {
+            std::env::temp_dir().join(format!("tetrust-{}-{name}.save", std::process::id()))
+        }
+
+        fn mid_game() -> MockGame {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 6);
+            game.hard_drop();
+            game.try_rotate(Direction::Right);
+            game.handle_hold();
+            game
+        }
+
+        #[test]
+        fn when_loaded_restores_board_and_score() {
+            let game = mid_game();
+            let path = temp_path("round-trip");
+
+            game.save(&path).unwrap();
+            let loaded = Game::load(
+                &path,
+                BlockGenerator::with_mock_sampler(1),
+                MockInput::new([]),
+                config(),
+            );
+            fs::remove_file(&path).unwrap();
+
+            let loaded = loaded.unwrap();
+            assert_eq!(loaded.board(), game.board());
+            assert_eq!(loaded.score(), game.score());
+            assert_eq!(loaded.snapshot(), game.snapshot());
+        }
+
+        #[test]
+        fn when_file_is_malformed_returns_invalid_data() {
+            let path = temp_path("malformed");
+            fs::write(&path, r#"{"score": "lots"}"#).unwrap();
+
+            let loaded = Game::load(
+                &path,
+                BlockGenerator::with_mock_sampler(1),
+                MockInput::new([]),
+                config(),
+            );
+            fs::remove_file(&path).unwrap();
+
+            assert_eq!(
+                loaded.err().map(|e| e.kind()),
+                Some(io::ErrorKind::InvalidData)
+            );
+        }
+
+        #[test]
+        fn when_save_refers_to_an_unregistered_custom_piece_returns_invalid_data() {
+            let saved = mid_game().save_state();
+            let mut bad_active = saved.clone();
+            bad_active.active_block =
+                ActiveBlock::from_parts(BlockType::Custom(0), (0, 3), 0).unwrap();
+            let mut bad_queue = saved;
+            bad_queue.queue[0] = BlockType::Custom(0);
+
+            for corrupted in [bad_active, bad_queue] {
+                let path = temp_path("unregistered-custom");
+                fs::write(&path, serde_json::to_string(&corrupted).unwrap()).unwrap();
+
+                let loaded = Game::load(
+                    &path,
+                    BlockGenerator::with_mock_sampler(1),
+                    MockInput::new([]),
+                    config(),
+                );
+                fs::remove_file(&path).unwrap();
+
+                assert_eq!(
+                    loaded.err().map(|e| e.kind()),
+                    Some(io::ErrorKind::InvalidData)
+                );
+            }
+        }
+    }
+
+    mod lock_delay_tests {
+        use super::*;
+
+        fn grounded_game(lock_delay_ticks: u64, inputs: Vec<Input>) -> (MockClock, MockGame) {
+            let config = Config {
+                gravity: Gravity::new(1, 1, 0).unwrap(),
+                lock_delay_ticks,
+                ..config()
+            };
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock.clone(), MockInput::new(inputs), config, 4);
+            game.drop_active_block();
+            (clock, game)
+        }
+
+        fn tick(clock: &MockClock, game: &mut MockGame, ticks: usize) {
+            for _ in 0..ticks {
+                clock.advance(FRAME_INTERVAL);
+                game.update().unwrap();
+            }
+        }
+
+        #[test]
+        fn when_block_is_grounded_remaining_time_counts_down() {
+            let (clock, mut game) = grounded_game(3, vec![]);
+
+            tick(&clock, &mut game, 1);
+            assert_eq!(game.lock_delay_remaining(), Some(FRAME_INTERVAL * 3));
+            tick(&clock, &mut game, 1);
+
+            assert_eq!(game.lock_delay_remaining(), Some(FRAME_INTERVAL * 2));
+        }
+
+        #[test]
+        fn when_block_is_airborne_remaining_time_is_none() {
+            let clock = MockClock::new(Instant::now());
+            let config = Config {
+                lock_delay_ticks: 3,
+                ..config()
+            };
+            let mut game = make_game(clock.clone(), MockInput::new([]), config, 4);
+
+            tick(&clock, &mut game, 2);
+
+            assert!(!game.is_grounded());
+            assert_eq!(game.lock_delay_remaining(), None);
+        }
+
+        #[test]
+        fn when_block_lands_it_does_not_lock_immediately() {
+            let (clock, mut game) = grounded_game(3, vec![]);
+
+            tick(&clock, &mut game, 1);
+
+            assert!(game.board().is_empty());
+            assert_eq!(game.lock_delay, Some(3));
+        }
+
+        #[test]
+        fn when_delay_elapses_block_locks() {
+            let (clock, mut game) = grounded_game(3, vec![]);
+
+            tick(&clock, &mut game, 3);
+            assert!(game.board().is_empty());
+            tick(&clock, &mut game, 1);
+
+            assert_eq!(
+                game.board()
+                    .iter()
+                    .flatten()
+                    .filter(|cell| cell.is_some())
+                    .count(),
+                4
+            );
+            assert_eq!(game.lock_delay, None);
+        }
+
+        #[test]
+        fn when_grounded_block_moves_delay_restarts() {
+            let inputs = vec![Input::None, Input::None, Input::Left];
+            let (clock, mut game) = grounded_game(3, inputs);
+
+            tick(&clock, &mut game, 4);
+
+            // Without the move on tick 3, the block would have locked on tick 4.
+            assert!(game.board().is_empty());
+            assert_eq!(game.lock_delay, Some(2));
+            assert_eq!(game.lock_delay_resets, 1);
+        }
+
+        #[test]
+        fn when_reset_cap_is_reached_delay_is_not_restarted() {
+            let (clock, mut game) = grounded_game(3, vec![]);
+            tick(&clock, &mut game, 1);
+            game.lock_delay_resets = MAX_LOCK_DELAY_RESETS;
+            game.lock_delay = Some(1);
+
+            game.handle_move(Direction::Left);
+
+            assert_eq!(game.lock_delay, Some(1));
+        }
+
+        #[test]
+        fn when_delay_is_zero_block_locks_on_landing() {
+            let (clock, mut game) = grounded_game(0, vec![]);
+
+            tick(&clock, &mut game, 1);
+
+            assert!(game.board().iter().flatten().any(Option::is_some));
+        }
+    }
+
+    mod hard_drop_tests {
+        use super::*;
+
+        #[test]
+        fn when_board_is_empty_locks_block_on_floor_and_awards_points() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 4);
+
+            game.hard_drop();
+
+            let mut cells = [[None; StandardBoard::COLUMNS]; StandardBoard::ROWS];
+            for row in &mut cells[StandardBoard::ROWS - 2..] {
+                row[4] = Some(Cell::Block(BlockType::O));
+                row[5] = Some(Cell::Block(BlockType::O));
+            }
+            assert_eq!(*game.board(), StandardBoard::from(cells));
+            assert_eq!(game.score(), 2 * StandardBoard::PLAYABLE_ROWS as u32);
+            assert_eq!(*game.active_block(), ActiveBlock::new(BlockType::O));
+        }
+
+        #[test]
+        fn when_stack_is_uneven_locks_block_on_highest_cell_beneath_it() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 4);
+            let mut cells = [[None; StandardBoard::COLUMNS]; StandardBoard::ROWS];
+            for row in &mut cells[StandardBoard::ROWS - 3..] {
+                row[4] = Some(Cell::Block(BlockType::I));
+            }
+            cells[StandardBoard::ROWS - 1][5] = Some(Cell::Block(BlockType::I));
+            game.set_board(StandardBoard::from(cells));
+
+            game.hard_drop();
+
+            // The O block comes to rest on the taller column at col 4, three rows above the floor.
+            cells[StandardBoard::ROWS - 5][4] = Some(Cell::Block(BlockType::O));
+            cells[StandardBoard::ROWS - 5][5] = Some(Cell::Block(BlockType::O));
+            cells[StandardBoard::ROWS - 4][4] = Some(Cell::Block(BlockType::O));
+            cells[StandardBoard::ROWS - 4][5] = Some(Cell::Block(BlockType::O));
+            assert_eq!(*game.board(), StandardBoard::from(cells));
+            assert_eq!(game.score(), 2 * (StandardBoard::PLAYABLE_ROWS as u32 - 3));
+        }
+
+        #[test]
+        fn when_hard_drop_input_is_read_locks_block() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(
+                clock.clone(),
+                MockInput::new([Input::HardDrop]),
+                config(),
+                4,
+            );
+
+            clock.advance(FRAME_INTERVAL);
+            game.update().unwrap();
+
+            assert!(game.board().iter().flatten().any(Option::is_some));
+        }
+    }
+
+    mod level_tests {
+        use super::*;
+
+        fn level_after_clearing(lines: &[u32]) -> u32 {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 1);
+            lines.iter().for_each(|&n| game.record_cleared_lines(n));
+            game.level()
+        }
+
+        #[test]
+        fn advances_every_ten_lines() {
+            assert_eq!(level_after_clearing(&[4, 4, 1]), 0);
+            assert_eq!(level_after_clearing(&[4, 4, 2]), 1);
+            assert_eq!(level_after_clearing(&[4; 5]), 2);
+            assert_eq!(level_after_clearing(&[4, 4, 4, 4, 4, 4, 1]), 2);
+        }
+
+        #[test]
+        fn when_level_advances_gravity_speeds_up_until_min_ticks() {
+            let clock = MockClock::new(Instant::now());
+            let cfg = Config {
+                gravity: Gravity::new(10, 4, 3).unwrap(),
+                ..config()
+            };
+            let mut game = make_game(clock, MockInput::new([]), cfg, 1);
+
+            game.record_cleared_lines(10);
+            assert_eq!(game.timer.gravity_ticks(), 7);
+
+            game.record_cleared_lines(10);
+            assert_eq!(game.timer.gravity_ticks(), 4);
+
+            game.record_cleared_lines(10);
+            assert_eq!(game.timer.gravity_ticks(), 4);
+        }
+
+        #[test]
+        fn when_level_is_set_high_gravity_speeds_up_immediately() {
+            let clock = MockClock::new(Instant::now());
+            let cfg = Config {
+                gravity: Gravity::new(10, 4, 3).unwrap(),
+                ..config()
+            };
+            let mut game = make_game(clock, MockInput::new([]), cfg, 1);
+            assert_eq!(game.timer.gravity_ticks(), 10);
+
+            game.set_level(2);
+
+            assert_eq!(game.level(), 2);
+            assert_eq!(game.timer.gravity_ticks(), 4);
+        }
+
+        #[test]
+        fn when_level_is_set_clearing_lines_does_not_lower_it() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 1);
+            game.set_level(5);
+
+            game.record_cleared_lines(4);
+
+            assert_eq!(game.level(), 5);
+        }
+
+        #[test]
+        fn when_gravity_is_classic_level_up_follows_the_gravity_curve() {
+            let clock = MockClock::new(Instant::now());
+            let cfg = Config {
+                gravity: Gravity::classic(),
+                ..config()
+            };
+            let mut game = make_game(clock, MockInput::new([]), cfg, 1);
+            assert_eq!(game.timer.gravity_ticks(), 48);
+
+            game.record_cleared_lines(90);
+            assert_eq!(game.timer.gravity_ticks(), 6);
+
+            game.record_cleared_lines(1000);
+            assert_eq!(game.timer.gravity_ticks(), 1);
+        }
+
+        #[test]
+        fn when_game_restarts_resets_level_and_lines() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 1);
+            game.record_cleared_lines(25);
+
+            game.reset();
+
+            assert_eq!(game.level(), 0);
+            assert_eq!(game.lines_cleared_total(), 0);
+        }
+    }
+
+    mod line_clear_scoring_tests {
+        use super::*;
+
+        fn points_for_lines(lines: usize, level: u32) -> u32 {
+            let mut game = game_with_i_well(lines);
+            game.level = level;
+            game.lock_active_block();
+            game.last_settle_report().unwrap().points_awarded
+        }
+
+        #[test]
+        fn when_single_is_cleared_awards_100() {
+            assert_eq!(points_for_lines(1, 0), 100);
+        }
+
+        #[test]
+        fn when_double_is_cleared_awards_300() {
+            assert_eq!(points_for_lines(2, 0), 300);
+        }
+
+        #[test]
+        fn when_triple_is_cleared_awards_500() {
+            assert_eq!(points_for_lines(3, 0), 500);
+        }
+
+        #[test]
+        fn when_tetris_is_cleared_awards_800() {
+            assert_eq!(points_for_lines(4, 0), 800);
+        }
+
+        #[test]
+        fn points_are_multiplied_by_one_more_than_the_level() {
+            assert_eq!(points_for_lines(4, 2), 2400);
+        }
+
+        #[test]
+        fn points_are_added_to_the_score() {
+            let mut game = game_with_i_well(2);
+            game.lock_active_block();
+            assert_eq!(game.score(), 300);
+        }
+    }
+
+    mod combo_tests {
+        use super::*;
+
+        /// Locks the active I block into a one-row well, clearing one line.
+        fn lock_single(game: &mut MockGame) -> u32 {
+            set_up_i_well(game, 1);
+            game.lock_active_block();
+            game.last_settle_report().unwrap().points_awarded
+        }
+
+        #[test]
+        fn consecutive_clears_award_escalating_combo_bonus() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 1);
+
+            let points = [
+                lock_single(&mut game),
+                lock_single(&mut game),
+                lock_single(&mut game),
+            ];
+
+            assert_eq!(points, [100, 150, 200]);
+            assert_eq!(game.combo(), 2);
+        }
+
+        #[test]
+        fn reports_combo_count_in_settle_report() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 1);
+
+            lock_single(&mut game);
+            lock_single(&mut game);
+
+            assert_eq!(game.last_settle_report().unwrap().combo, 1);
+        }
+
+        #[test]
+        fn combo_bonus_scales_with_level() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 1);
+            game.level = 1;
+            game.lines_cleared_total = LINES_PER_LEVEL;
+
+            lock_single(&mut game);
+
+            assert_eq!(lock_single(&mut game), 2 * (100 + 50));
+        }
+
+        #[test]
+        fn three_clear_chain_at_level_two_awards_cumulative_combo_bonus() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 1);
+            game.level = 2;
+            game.lines_cleared_total = 2 * LINES_PER_LEVEL;
+
+            let total: u32 = (0..3).map(|_| lock_single(&mut game)).sum();
+
+            // Combo steps 0, 1 and 2 earn 0 + 50 + 100 on top of three 100-point singles, all
+            // tripled at level 2.
+            assert_eq!(total, 3 * (3 * 100 + 150));
+        }
+
+        #[test]
+        fn combo_bonus_follows_configured_points() {
+            let clock = MockClock::new(Instant::now());
+            let config = Config {
+                combo_points: 20,
+                ..config()
+            };
+            let mut game = make_game(clock, MockInput::new([]), config, 1);
+
+            let points = [
+                lock_single(&mut game),
+                lock_single(&mut game),
+                lock_single(&mut game),
+            ];
+
+            assert_eq!(points, [100, 120, 140]);
+        }
+
+        #[test]
+        fn when_lock_clears_nothing_combo_resets() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 1);
+            lock_single(&mut game);
+            lock_single(&mut game);
+
+            game.set_board(StandardBoard::new());
+            game.drop_active_block();
+            game.lock_active_block();
+
+            assert_eq!(game.combo(), -1);
+            assert_eq!(lock_single(&mut game), 100);
+        }
+    }
+
+    mod back_to_back_tests {
+        use super::*;
+
+        /// Locks the active I block into a well `lines` rows deep, clearing them.
+        fn lock_clearing(game: &mut MockGame, lines: usize) -> u32 {
+            set_up_i_well(game, lines);
+            // Break any combo so only the back-to-back bonus applies.
+            game.combo = -1;
+            game.lock_active_block();
+            game.last_settle_report().unwrap().points_awarded
+        }
+
+        #[test]
+        fn consecutive_tetrises_award_bonus_on_the_second() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 1);
+
+            let points = [lock_clearing(&mut game, 4), lock_clearing(&mut game, 4)];
+
+            assert_eq!(points, [800, 1200]);
+            assert!(game.back_to_back());
+        }
+
+        #[test]
+        fn flags_only_the_clear_that_earned_the_bonus_in_settle_report() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 1);
+
+            lock_clearing(&mut game, 4);
+            assert!(!game.last_settle_report().unwrap().back_to_back);
+
+            lock_clearing(&mut game, 4);
+            assert!(game.last_settle_report().unwrap().back_to_back);
+        }
+
+        #[test]
+        fn when_single_is_cleared_between_tetrises_chain_breaks() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 1);
+
+            lock_clearing(&mut game, 4);
+            lock_clearing(&mut game, 1);
+            assert!(!game.back_to_back());
+
+            assert_eq!(lock_clearing(&mut game, 4), 800);
+        }
+
+        #[test]
+        fn when_lock_clears_nothing_chain_continues() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 1);
+            lock_clearing(&mut game, 4);
+
+            game.set_board(StandardBoard::new());
+            game.drop_active_block();
+            game.lock_active_block();
+
+            assert!(game.back_to_back());
+        }
+    }
+
+    mod t_spin_tests {
+        use super::*;
+
+        /// Returns a game with a T-spin double slot at the bottom of the board, covered by an
+        /// overhang, and a T block pointing down into the slot.
+        fn t_spin_double_setup(last_action: LastAction) -> MockGame {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 6);
+            let garbage = Some(Cell::Garbage);
+            let bottom = StandardBoard::ROWS - 1;
+            let mut cells = [[None; StandardBoard::COLUMNS]; StandardBoard::ROWS];
+            cells[bottom] = [garbage; StandardBoard::COLUMNS];
+            cells[bottom][4] = None;
+            cells[bottom - 1] = [garbage; StandardBoard::COLUMNS];
+            cells[bottom - 1][3..6].fill(None);
+            cells[bottom - 2][..4].fill(garbage);
+            game.set_board(StandardBoard::from(cells));
+            game.set_active_block(
+                ActiveBlock::from_parts(BlockType::T, (bottom - 2, 3), 2).unwrap(),
+            );
+            game.last_action = last_action;
+            game
+        }
+
+        #[test]
+        fn when_t_is_rotated_into_covered_slot_detects_full_t_spin_double() {
+            let mut game = t_spin_double_setup(LastAction::Rotate);
+
+            game.lock_active_block();
+
+            let report = game.last_settle_report().unwrap();
+            assert_eq!(report.t_spin, TSpin::Full);
+            assert_eq!(report.lines_cleared, 2);
+            assert_eq!(report.points_awarded, 1200);
+            assert!(game.back_to_back());
+        }
+
+        #[test]
+        fn when_t_was_last_moved_rather_than_rotated_detects_no_t_spin() {
+            let mut game = t_spin_double_setup(LastAction::Move);
+
+            game.lock_active_block();
+
+            let report = game.last_settle_report().unwrap();
+            assert_eq!(report.t_spin, TSpin::None);
+            assert_eq!(report.points_awarded, 300);
+        }
+
+        #[test]
+        fn when_point_side_corner_is_open_detects_mini_t_spin() {
+            let mut game = t_spin_double_setup(LastAction::Rotate);
+            // Point the T up, so the open top-right corner is beside its point.
+            let (r, c) = game.active_block().top_left();
+            game.set_active_block(ActiveBlock::from_parts(BlockType::T, (r, c), 0).unwrap());
+
+            assert_eq!(game.t_spin(), TSpin::Mini);
+        }
+
+        #[test]
+        fn when_fewer_than_three_corners_are_blocked_detects_no_t_spin() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 6);
+            game.drop_active_block();
+            game.try_rotate(Direction::Right);
+
+            assert_eq!(game.t_spin(), TSpin::None);
+        }
+    }
+
+    mod perfect_clear_tests {
+        use super::*;
+
+        /// Returns a game whose I block clears the only occupied row when dropped, plus an extra
+        /// occupied cell above it if `extra_cell` is true.
+        fn game_with_single_setup(extra_cell: bool) -> MockGame {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 1);
+            let bottom = StandardBoard::ROWS - 1;
+            let mut cells = [[None; StandardBoard::COLUMNS]; StandardBoard::ROWS];
+            cells[bottom] = [Some(Cell::Garbage); StandardBoard::COLUMNS];
+            cells[bottom][3..7].fill(None);
+            if extra_cell {
+                cells[bottom - 1][0] = Some(Cell::Garbage);
+            }
+            game.set_board(StandardBoard::from(cells));
+            game.drop_active_block();
+            game
+        }
+
+        #[test]
+        fn when_clear_empties_the_board_awards_perfect_clear_bonus() {
+            let mut game = game_with_single_setup(false);
+
+            game.lock_active_block();
+
+            assert!(game.board().is_empty());
+            assert!(game.last_settle_report().unwrap().perfect_clear);
+            assert_eq!(game.score(), 100 + 800);
+        }
+
+        #[test]
+        fn when_clear_leaves_cells_on_the_board_awards_no_bonus() {
+            let mut game = game_with_single_setup(true);
+
+            game.lock_active_block();
+
+            assert!(!game.last_settle_report().unwrap().perfect_clear);
+            assert_eq!(game.score(), 100);
+        }
+    }
+
+    mod lock_active_block_tests {
+        use super::*;
 
-    /// Returns the current block queue as a contiguous slice.
-    pub fn queue(&self) -> &[BlockType] {
-        let (front, back) = self.queue.as_slices();
-        debug_assert_eq!(
-            back.len(),
-            0,
-            "Back slice of block queue was non-empty. This indicates that the queue wasn't made contiguous after inserting a new block.",
-        );
-        front
-    }
+        #[test]
+        fn keeps_the_queue_full() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 4);
+            for _ in 0..3 {
+                game.drop_active_block();
+                game.lock_active_block();
+                assert_eq!(game.queue().len(), config().preview_count);
+            }
+        }
 
-    pub(crate) fn active_block(&self) -> &ActiveBlock {
-        &self.active_block
-    }
+        #[test]
+        fn returns_the_number_of_lines_cleared() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 4);
+            let mut cells = [[None; StandardBoard::COLUMNS]; StandardBoard::ROWS];
+            for row in &mut cells[StandardBoard::ROWS - 2..] {
+                for (col, cell) in row.iter_mut().enumerate() {
+                    if !(4..=5).contains(&col) {
+                        *cell = Some(Cell::Block(BlockType::I));
+                    }
+                }
+            }
+            game.set_board(StandardBoard::from(cells));
+            game.drop_active_block();
 
-    pub(crate) fn board(&self) -> &Board {
-        &self.board
+            assert_eq!(game.lock_active_block(), 2);
+            assert_eq!(*game.board(), StandardBoard::new());
+            assert_eq!(*game.active_block(), ActiveBlock::new(BlockType::O));
+        }
     }
-}
 
-impl<I, C: Clock, S> Game<I, C, S> {
-    pub fn time_until_next_tick(&self) -> Duration {
-        self.timer.time_until_next_tick()
-    }
-}
+    mod handle_gravity_tests {
+        use super::*;
 
-impl<I: PollInput> Game<I, SystemClock, Uniform<u8>> {
-    /// Instantiate a new game using the given [BlockGenerator] as its source of [Block]s.
-    pub fn new(block_generator: BlockGenerator<Uniform<u8>>, input: I, config: Config) -> Self {
-        Self::new_with_clock(block_generator, input, config, SystemClock)
-    }
-}
+        #[test]
+        fn when_cell_below_is_free_moves_block_down_one_row() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 4);
+            let mut expected = game.active_block().clone();
+            expected.move_down();
 
-impl<I: PollInput, C: Clock + Clone, S: Distribution<u8>> Game<I, C, S> {
-    pub(crate) fn new_with_clock(
-        mut block_generator: BlockGenerator<S>,
-        input: I,
-        config: Config,
-        clock: C,
-    ) -> Self {
-        let first_block = block_generator.block();
-        let active_block = ActiveBlock::new(first_block);
+            game.handle_gravity();
 
-        // Populate the queue with random blocks.
-        let mut queue: VecDeque<BlockType> =
-            (0..QUEUE_LEN).map(|_| block_generator.block()).collect();
-        queue.make_contiguous(); // simplifies returning the queue to the game loop
+            assert_eq!(*game.active_block(), expected);
+            assert_eq!(*game.board(), StandardBoard::new());
+        }
 
-        let timer = GameTimer::new_with_clock(
-            config.frame_interval,
-            config.gravity.initial_ticks(),
-            config.input_ticks,
-            clock.clone(),
-        );
+        #[test]
+        fn when_block_reaches_floor_fixes_it_and_spawns_next_block() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 4);
 
-        Game {
-            clock,
-            config,
-            timer,
-            score: 0,
-            board: Board::new(),
-            block_generator,
-            active_block,
-            queue,
-            game_over: false,
-            input,
+            // The O block spawns in the two buffer zone rows and falls the full playable height.
+            for _ in 0..=StandardBoard::PLAYABLE_ROWS {
+                game.handle_gravity();
+            }
+
+            let mut cells = [[None; StandardBoard::COLUMNS]; StandardBoard::ROWS];
+            for row in &mut cells[StandardBoard::ROWS - 2..] {
+                row[4] = Some(Cell::Block(BlockType::O));
+                row[5] = Some(Cell::Block(BlockType::O));
+            }
+            assert_eq!(*game.board(), StandardBoard::from(cells));
+            assert_eq!(*game.active_block(), ActiveBlock::new(BlockType::O));
+            assert_eq!(game.queue().len(), config().preview_count);
+            assert!(!game.game_over());
+        }
+
+        #[test]
+        fn when_next_block_would_spawn_in_the_stack_ends_game() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 4);
+            let mut cells = [[None; StandardBoard::COLUMNS]; StandardBoard::ROWS];
+            for row in &mut cells[StandardBoard::BUFFER_ZONE_ROWS..] {
+                row[4] = Some(Cell::Block(BlockType::I));
+            }
+            game.set_board(StandardBoard::from(cells));
+
+            game.handle_gravity();
+
+            assert!(game.game_over());
         }
     }
 
-    /// Begins a new game.
-    fn restart(&mut self) {
-        self.timer = GameTimer::new_with_clock(
-            self.config.frame_interval,
-            self.config.gravity.initial_ticks(),
-            self.config.input_ticks,
-            self.clock.clone(),
-        );
-        self.score = 0;
-        self.board = Board::new();
+    mod spawn_columns_tests {
+        use super::*;
 
-        let first_block = self.block_generator.block();
-        self.active_block = ActiveBlock::new(first_block);
+        /// Returns the leftmost column occupied by the active block of a game dealing only blocks
+        /// from `sampler_value`.
+        fn spawn_column(config: Config, sampler_value: u8) -> usize {
+            let clock = MockClock::new(Instant::now());
+            let game = make_game(clock, MockInput::new([]), config, sampler_value);
+            game.active_positions()
+                .iter()
+                .map(|&(_, c)| c)
+                .min()
+                .unwrap()
+        }
 
-        self.queue.clear();
-        (0..QUEUE_LEN).for_each(|_| self.queue.push_back(self.block_generator.block()));
-        self.queue.make_contiguous();
+        #[test]
+        fn by_default_centers_blocks_with_a_rightward_bias() {
+            assert_eq!(spawn_column(config(), 1), 3, "I");
+            assert_eq!(spawn_column(config(), 2), 4, "J");
+            assert_eq!(spawn_column(config(), 4), 4, "O");
+        }
 
-        self.game_over = false
-    }
+        #[test]
+        fn when_block_type_has_a_spawn_column_spawns_it_there() {
+            let config = Config {
+                spawn_columns: HashMap::from([(BlockType::I, 0), (BlockType::O, 6)]),
+                ..config()
+            };
+            let top_left_column = |sampler_value| {
+                let clock = MockClock::new(Instant::now());
+                let game = make_game(clock, MockInput::new([]), config.clone(), sampler_value);
+                game.active_block().top_left().1
+            };
 
-    /// Drives the game loop at a maxmimum rate determined by the [GameTimer]'s tick interval.
-    pub fn update(&mut self) -> io::Result<UpdateOutcome> {
-        if let Some(tick) = self.timer.update() {
-            if self.game_over() {
-                return self.update_game_over(&tick);
-            } else {
-                return self.update_game_in_progress(&tick);
-            }
+            assert_eq!(top_left_column(1), 0, "I");
+            assert_eq!(top_left_column(4), 6, "O");
+            assert_eq!(spawn_column(config, 2), 4, "J");
         }
 
-        Ok(UpdateOutcome::Unchanged)
-    }
+        #[test]
+        fn when_block_type_has_a_spawn_column_later_blocks_spawn_there() {
+            let clock = MockClock::new(Instant::now());
+            let config = Config {
+                spawn_columns: HashMap::from([(BlockType::O, 0)]),
+                ..config()
+            };
+            let mut game = make_game(clock, MockInput::new([]), config, 4);
 
-    /// Manages updates that are valid in the game over state.
-    fn update_game_over(&mut self, tick: &Tick) -> io::Result<UpdateOutcome> {
-        if tick.input {
-            match self.input.poll_input(self.timer.time_until_next_tick())? {
-                Input::Quit => return Ok(UpdateOutcome::Quit),
-                Input::Restart => {
-                    self.restart();
-                    return Ok(UpdateOutcome::Updated);
-                }
-                _ => (),
-            }
+            game.step(&[Input::Right, Input::HardDrop]);
+            assert_eq!(game.active_block().top_left().1, 0);
+
+            game.step(&[Input::Right, Input::Hold]);
+            assert_eq!(game.active_block().top_left().1, 0);
+
+            game.step(&[Input::HardDrop, Input::Right, Input::Hold]);
+            assert_eq!(game.active_block().top_left().1, 0);
         }
-        Ok(UpdateOutcome::Unchanged)
     }
 
-    /// Manages updates that are valid while the game is in progress.
-    fn update_game_in_progress(&mut self, tick: &Tick) -> io::Result<UpdateOutcome> {
-        if tick.gravity {
-            self.handle_gravity();
+    mod instant_gravity_tests {
+        use super::*;
+
+        /// Returns a game whose O block spawns above a four-row column in the middle of the board.
+        fn game_above_stack(config: Config) -> MockGame {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config, 4);
+            let mut cells = [[None; StandardBoard::COLUMNS]; StandardBoard::ROWS];
+            for row in &mut cells[StandardBoard::ROWS - 4..] {
+                row[4] = Some(Cell::Garbage);
+            }
+            game.set_board(StandardBoard::from(cells));
+            game
         }
 
-        if tick.input {
-            use crate::input::Input::*;
-            match self.input.poll_input(self.timer.time_until_next_tick())? {
-                Down => self.handle_gravity(),
-                Left => self.handle_move(Direction::Left),
-                Right => self.handle_move(Direction::Right),
-                RotateLeft => self.handle_rotate(Direction::Left),
-                RotateRight => self.handle_rotate(Direction::Right),
-                Restart => {
-                    self.restart();
-                    return Ok(UpdateOutcome::Updated);
-                }
-                Quit => return Ok(UpdateOutcome::Quit),
-                _ => (),
+        fn instant_config() -> Config {
+            Config {
+                instant_gravity: true,
+                lock_delay_ticks: 3,
+                ..config()
             }
         }
 
-        if tick.any() {
-            Ok(UpdateOutcome::Updated)
-        } else {
-            Ok(UpdateOutcome::Unchanged)
+        #[test]
+        fn when_enabled_spawned_block_lands_on_the_stack_on_first_gravity_tick() {
+            let mut game = game_above_stack(instant_config());
+            let board = game.board().clone();
+            let (_, c) = game.active_block().top_left();
+
+            // Gravity applies every second tick.
+            game.advance_tick(&[]);
+            assert_eq!(game.active_block().top_left().1, c);
+            assert_ne!(game.active_block().top_left(), game.ghost_position());
+            game.advance_tick(&[]);
+
+            assert_eq!(game.active_block().top_left(), (StandardBoard::ROWS - 6, c));
+            assert_eq!(*game.board(), board);
         }
-    }
 
-    /// Attempts to move the current [ActiveBlock] one row downwards, and handles the resulting
-    /// collision if movement is impossible.
-    fn handle_gravity(&mut self) {
-        self.active_block.move_down();
-        if self.board.collides(&self.active_block) {
-            self.active_block.move_up();
-            self.handle_landing()
+        #[test]
+        fn when_block_lands_lock_delay_begins_on_the_same_tick() {
+            let mut game = game_above_stack(instant_config());
+            let board = game.board().clone();
+
+            game.handle_gravity();
+
+            assert_eq!(game.lock_delay, Some(3));
+            assert_eq!(*game.board(), board);
         }
-    }
 
-    /// Handles the case where a block can no longer move downwards under gravity.
-    fn handle_landing(&mut self) {
-        // Add the active block to the board.
-        self.board.fix_active_block(&self.active_block);
+        #[test]
+        fn when_block_lands_locks_once_the_lock_delay_has_elapsed() {
+            let mut game = game_above_stack(instant_config());
+            let board = game.board().clone();
 
-        // Clear lines and update the score.
-        let lines_cleared = self.board.clear_lines();
-        self.score += lines_cleared as u32;
-        if lines_cleared > 0
-            && self
-                .score
-                .is_multiple_of(self.config.accelerate_every_n_points)
-        {
-            self.accelerate();
+            // The block lands on tick 2, then counts down for 3 ticks.
+            (0..4).for_each(|_| _ = game.advance_tick(&[]));
+            assert_eq!(*game.board(), board);
+            game.advance_tick(&[]);
+
+            assert_eq!(game.board().iter().flatten().flatten().count(), 8);
         }
 
-        // Handle game over or set up the next block.
-        if self.board.buffer_zone_occupied() {
-            self.game_over = true
-        } else {
-            self.load_next_active_block();
+        #[test]
+        fn when_lock_delay_is_zero_locks_on_the_tick_the_block_lands() {
+            let mut game = game_above_stack(Config {
+                lock_delay_ticks: 0,
+                ..instant_config()
+            });
+
+            game.handle_gravity();
+
+            assert_eq!(game.board().iter().flatten().flatten().count(), 8);
         }
-    }
 
-    /// Increase the rate at which blocks fall under gravity by decreasing the number of game ticks
-    /// between gravity applications.
-    fn accelerate(&mut self) {
-        let current_gravity_ticks = self.timer.gravity_ticks();
-        // Gravity ticks cannot be decreased below the minimum ticks specified at the start of
-        // the game.
-        let next_gravity_ticks = current_gravity_ticks
-            .saturating_sub(self.config.gravity.acceleration())
-            .max(self.config.gravity.min_ticks());
-        self.timer.set_gravity_ticks(next_gravity_ticks);
-    }
+        #[test]
+        fn when_enabled_projects_the_resting_position() {
+            let game = game_above_stack(instant_config());
+            assert_eq!(
+                game.projected_position_after_gravity(),
+                game.ghost_position()
+            );
+        }
 
-    /// Pulls the next block off the queue and sets it as the game's active block.
-    fn load_next_active_block(&mut self) {
-        let next_block = self
-            .queue
-            .pop_front()
-            .expect("Block queue should never be empty");
-        self.active_block = ActiveBlock::new(next_block);
-        self.queue.push_back(self.block_generator.block());
-        self.queue.make_contiguous();
-    }
+        #[test]
+        fn when_enabled_mid_game_drops_the_block_on_the_next_gravity_tick() {
+            let mut game = game_above_stack(Config {
+                lock_delay_ticks: 3,
+                ..config()
+            });
+            game.handle_gravity();
 
-    fn handle_move(&mut self, direction: Direction) {
-        let undo = if direction == Direction::Left {
-            self.active_block.move_left();
-            ActiveBlock::move_right
-        } else {
-            self.active_block.move_right();
-            ActiveBlock::move_left
-        };
+            game.set_instant_gravity(true);
+            game.handle_gravity();
 
-        if self.board.collides(&self.active_block) {
-            undo(&mut self.active_block)
+            assert_eq!(game.active_block().top_left(), game.ghost_position());
+            assert_eq!(game.lock_delay, Some(3));
         }
-    }
 
-    fn handle_rotate(&mut self, direction: Direction) {
-        let undo = if direction == Direction::Left {
-            self.active_block.rotate_counter_clockwise();
-            ActiveBlock::rotate_clockwise
-        } else {
-            self.active_block.rotate_clockwise();
-            ActiveBlock::rotate_counter_clockwise
-        };
+        #[test]
+        fn when_disabled_mid_game_moves_the_block_one_row() {
+            let mut game = game_above_stack(instant_config());
+            let (r, c) = game.active_block().top_left();
 
-        if self.board.collides(&self.active_block) {
-            undo(&mut self.active_block)
+            game.set_instant_gravity(false);
+            game.handle_gravity();
+
+            assert_eq!(game.active_block().top_left(), (r + 1, c));
+        }
+
+        #[test]
+        fn when_game_is_reset_stays_enabled() {
+            let mut game = game_above_stack(Config {
+                lock_delay_ticks: 3,
+                ..config()
+            });
+            game.set_instant_gravity(true);
+
+            game.reset();
+            game.handle_gravity();
+
+            assert_eq!(game.active_block().top_left(), game.ghost_position());
         }
     }
-}
 
-#[cfg(test)]
-mod game_tests {
-    use std::time::Instant;
+    mod gravity_listener_tests {
+        use std::cell::RefCell;
+        use std::rc::Rc;
 
-    use crate::config::{Config, Gravity};
-    use crate::timer::test_helpers::MockClock;
+        use super::*;
 
-    use super::test_helpers::{MockGame, MockInput, make_game};
-    use super::*;
+        #[test]
+        fn fires_once_per_application_of_gravity_with_positions_before_and_after() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 1);
+            let calls = Rc::new(RefCell::new(Vec::new()));
+            let recorded = Rc::clone(&calls);
+            game.set_gravity_listener(move |before, after| {
+                recorded.borrow_mut().push((before, after))
+            });
 
-    const FRAME_INTERVAL: Duration = Duration::from_millis(100);
+            game.handle_gravity();
+            game.handle_gravity();
 
-    fn config() -> Config {
-        Config {
-            frame_interval: FRAME_INTERVAL,
-            gravity: Gravity::new(2, 1, 1).unwrap(),
-            accelerate_every_n_points: 5,
-            input_ticks: 1,
+            // I starts with its top-left corner at (0, 3).
+            assert_eq!(*calls.borrow(), vec![((0, 3), (1, 3)), ((1, 3), (2, 3))]);
+        }
+
+        #[test]
+        fn when_block_is_grounded_fires_with_equal_positions() {
+            let clock = MockClock::new(Instant::now());
+            let mut game = make_game(clock, MockInput::new([]), config(), 1);
+            game.board.drop_block(&mut game.active_block);
+            let resting = game.active_block().top_left();
+            let calls = Rc::new(RefCell::new(Vec::new()));
+            let recorded = Rc::clone(&calls);
+            game.set_gravity_listener(move |before, after| {
+                recorded.borrow_mut().push((before, after))
+            });
+
+            game.handle_gravity();
+
+            assert_eq!(*calls.borrow(), vec![(resting, resting)]);
         }
     }
 
-    mod restart_tests {
+    mod input_listener_tests {
+        use std::cell::RefCell;
+        use std::rc::Rc;
+
         use super::*;
 
         #[test]
-        fn resets_state() {
+        fn fires_with_each_polled_input_and_its_tick_except_none() {
             let clock = MockClock::new(Instant::now());
-            let mut game = make_game(clock, MockInput::new([]), config(), 1);
+            let inputs = MockInput::new([Input::Left, Input::None, Input::HardDrop]);
+            let mut game = make_game(clock.clone(), inputs, config(), 1);
+            let calls = Rc::new(RefCell::new(Vec::new()));
+            let recorded = Rc::clone(&calls);
+            game.set_input_listener(move |tick, input| recorded.borrow_mut().push((tick, input)));
 
-            // Dirty the state
-            game.score = 10;
-            game.game_over = true;
-            game.board.fix_active_block(&game.active_block.clone());
+            for _ in 0..3 {
+                clock.advance(FRAME_INTERVAL);
+                game.update().unwrap();
+            }
+
+            assert_eq!(
+                *calls.borrow(),
+                vec![(1, Input::Left), (3, Input::HardDrop)]
+            );
+        }
+    }
 
-            game.restart();
+    mod custom_piece_tests {
+        use super::*;
+        use crate::block_generator::PieceDef;
 
-            assert_eq!(game.score, 0);
-            assert!(!game.game_over);
-            assert_eq!(game.board, Board::new());
-            assert_eq!(*game.active_block(), ActiveBlock::new(BlockType::I));
-            assert_eq!(game.queue(), &[BlockType::I; QUEUE_LEN]);
+        #[test]
+        fn when_custom_piece_is_dropped_it_is_fixed_to_the_floor() {
+            let mut block_generator = BlockGenerator::new();
+            let block_type = block_generator
+                .register(PieceDef {
+                    rotations: [
+                        vec![(1, 0), (1, 1), (1, 2)],
+                        vec![(0, 1), (1, 1), (2, 1)],
+                        vec![(1, 0), (1, 1), (1, 2)],
+                        vec![(0, 1), (1, 1), (2, 1)],
+                    ],
+                    color: (255, 165, 0),
+                })
+                .unwrap();
+            let mut game = Game::new_with_clock(
+                block_generator,
+                MockInput::new([]),
+                config(),
+                MockClock::new(Instant::now()),
+            );
+            game.active_block = ActiveBlock::new_in(block_type, game.custom_pieces());
+
+            // Rotated upright in its 3×3 box at the top of the board, the tromino reaches the floor
+            // after 19 rows and is fixed on the 20th application of gravity.
+            game.try_rotate(Direction::Right);
+            for _ in 0..StandardBoard::ROWS - 2 {
+                game.handle_gravity();
+            }
+
+            let mut cells = [[None; StandardBoard::COLUMNS]; StandardBoard::ROWS];
+            for row in &mut cells[StandardBoard::ROWS - 3..] {
+                row[5] = Some(Cell::Block(block_type));
+            }
+            assert_eq!(game.board, StandardBoard::from(cells));
         }
     }
 
@@ -349,7 +5462,13 @@ mod game_tests {
 
             #[test]
             fn when_neither_gravity_nor_input_ticks_returns_unchanged() {
-                let cfg = Config { gravity: Gravity::new(3, 1, 1).unwrap(), input_ticks: 2, ..config() };
+                let cfg = Config {
+                    gravity: Gravity::new(3, 1, 1).unwrap(),
+                    input_ticks: 2,
+                    soft_drop_ticks: 1,
+                    lock_delay_ticks: 0,
+                    ..config()
+                };
                 let clock = MockClock::new(Instant::now());
                 let mut game = make_game(clock.clone(), MockInput::new([]), cfg, 1);
                 clock.advance(FRAME_INTERVAL);
@@ -375,16 +5494,68 @@ mod game_tests {
             #[test]
             fn when_input_tick_is_true_and_input_is_restart_returns_updated_and_resets_game_over() {
                 let clock = MockClock::new(Instant::now());
-                let mut game = make_game(clock.clone(), MockInput::new([Input::Restart]), config(), 1);
+                let mut game =
+                    make_game(clock.clone(), MockInput::new([Input::Restart]), config(), 1);
                 game.game_over = true;
                 clock.advance(FRAME_INTERVAL);
                 game.update().unwrap();
                 assert!(!game.game_over());
             }
 
+            #[test]
+            fn when_soft_drop_is_held_gravity_applies_every_tick() {
+                let cfg = Config {
+                    gravity: Gravity::new(3, 1, 1).unwrap(),
+                    ..config()
+                };
+                let clock = MockClock::new(Instant::now());
+                let mut game = make_game(
+                    clock.clone(),
+                    MockInput::new([Input::SoftDropStart]),
+                    cfg,
+                    4,
+                );
+                let (row, _) = game.active_block().top_left();
+
+                for _ in 0..4 {
+                    clock.advance(FRAME_INTERVAL);
+                    game.update().unwrap();
+                }
+
+                // Gravity applies on each of the three ticks after soft drop starts.
+                assert_eq!(game.active_block().top_left().0, row + 3);
+            }
+
+            #[test]
+            fn when_soft_drop_is_released_gravity_returns_to_normal() {
+                let cfg = Config {
+                    gravity: Gravity::new(3, 1, 1).unwrap(),
+                    ..config()
+                };
+                let clock = MockClock::new(Instant::now());
+                let inputs = [Input::SoftDropStart, Input::SoftDropEnd];
+                let mut game = make_game(clock.clone(), MockInput::new(inputs), cfg, 4);
+                let (row, _) = game.active_block().top_left();
+
+                for _ in 0..5 {
+                    clock.advance(FRAME_INTERVAL);
+                    game.update().unwrap();
+                }
+
+                // Soft drop applies gravity on tick 2, and normal gravity applies on tick 3 only.
+                assert_eq!(game.active_block().top_left().0, row + 2);
+                assert_eq!(game.drop_speed_cps(), 10.0 / 3.0);
+            }
+
             #[test]
             fn when_gravity_ticks_active_block_moves_down() {
-                let cfg = Config { gravity: Gravity::new(1, 1, 1).unwrap(), input_ticks: 2, ..config() };
+                let cfg = Config {
+                    gravity: Gravity::new(1, 1, 1).unwrap(),
+                    input_ticks: 2,
+                    soft_drop_ticks: 1,
+                    lock_delay_ticks: 0,
+                    ..config()
+                };
                 let clock = MockClock::new(Instant::now());
                 let mut game = make_game(clock.clone(), MockInput::new([]), cfg, 1);
                 let before = game.active_block().clone();
@@ -397,7 +5568,12 @@ mod game_tests {
 
             #[test]
             fn when_input_tick_is_false_input_is_not_polled() {
-                let cfg = Config { input_ticks: 2, ..config() };
+                let cfg = Config {
+                    input_ticks: 2,
+                    soft_drop_ticks: 1,
+                    lock_delay_ticks: 0,
+                    ..config()
+                };
                 let clock = MockClock::new(Instant::now());
                 let mut game = make_game(clock.clone(), MockInput::new([Input::Left]), cfg, 1);
                 let before = game.active_block().clone();
@@ -433,7 +5609,8 @@ mod game_tests {
             #[test]
             fn when_input_tick_is_true_and_input_is_right_active_block_moves_right() {
                 let clock = MockClock::new(Instant::now());
-                let mut game = make_game(clock.clone(), MockInput::new([Input::Right]), config(), 1);
+                let mut game =
+                    make_game(clock.clone(), MockInput::new([Input::Right]), config(), 1);
                 let before = game.active_block().clone();
                 clock.advance(FRAME_INTERVAL);
                 game.update().unwrap();
@@ -443,9 +5620,15 @@ mod game_tests {
             }
 
             #[test]
-            fn when_input_tick_is_true_and_input_is_rotate_left_active_block_rotates_counter_clockwise() {
+            fn when_input_tick_is_true_and_input_is_rotate_left_active_block_rotates_counter_clockwise()
+             {
                 let clock = MockClock::new(Instant::now());
-                let mut game = make_game(clock.clone(), MockInput::new([Input::RotateLeft]), config(), 1);
+                let mut game = make_game(
+                    clock.clone(),
+                    MockInput::new([Input::RotateLeft]),
+                    config(),
+                    1,
+                );
                 let before = game.active_block().clone();
                 clock.advance(FRAME_INTERVAL);
                 game.update().unwrap();
@@ -457,7 +5640,12 @@ mod game_tests {
             #[test]
             fn when_input_tick_is_true_and_input_is_rotate_right_active_block_rotates_clockwise() {
                 let clock = MockClock::new(Instant::now());
-                let mut game = make_game(clock.clone(), MockInput::new([Input::RotateRight]), config(), 1);
+                let mut game = make_game(
+                    clock.clone(),
+                    MockInput::new([Input::RotateRight]),
+                    config(),
+                    1,
+                );
                 let before = game.active_block().clone();
                 clock.advance(FRAME_INTERVAL);
                 game.update().unwrap();
@@ -488,7 +5676,12 @@ mod game_tests {
 
             #[test]
             fn when_input_tick_is_false_returns_unchanged() {
-                let cfg = Config { input_ticks: 2, ..config() };
+                let cfg = Config {
+                    input_ticks: 2,
+                    soft_drop_ticks: 1,
+                    lock_delay_ticks: 0,
+                    ..config()
+                };
                 let clock = MockClock::new(Instant::now());
                 let mut game = make_game(clock.clone(), MockInput::new([]), cfg, 1);
                 game.game_over = true;
@@ -533,21 +5726,21 @@ mod game_tests {
 }
 
 #[cfg(test)]
-mod test_helpers {
+pub(crate) mod test_helpers {
     use std::collections::VecDeque;
 
     use crate::block_generator::{BlockGenerator, test_helpers as block_generator_test_helpers};
-    use crate::config::Config;
+    use crate::config::{Config, Gravity, GravityMode};
     use crate::input::{Input, PollInput};
     use crate::timer::test_helpers::MockClock;
 
     use super::*;
 
     /// A mock input source that returns a fixed sequence of inputs, then `Input::None`.
-    pub(super) struct MockInput(VecDeque<Input>);
+    pub(crate) struct MockInput(VecDeque<Input>);
 
     impl MockInput {
-        pub(super) fn new(inputs: impl IntoIterator<Item = Input>) -> Self {
+        pub(crate) fn new(inputs: impl IntoIterator<Item = Input>) -> Self {
             Self(inputs.into_iter().collect())
         }
     }
@@ -558,9 +5751,34 @@ mod test_helpers {
         }
     }
 
-    pub(super) type MockGame = Game<MockInput, MockClock, block_generator_test_helpers::MockSampler>;
+    /// The interval between updates of games built from [test_config].
+    pub(crate) const FRAME_INTERVAL: Duration = Duration::from_millis(100);
+
+    /// Returns the config shared by the crate's tests: gravity every other tick and no lock delay.
+    /// Every field is set explicitly, so that changes to [Config::default] don't change the tests.
+    pub(crate) fn test_config() -> Config {
+        Config {
+            frame_interval: FRAME_INTERVAL,
+            gravity: Gravity::new(2, 1, 1).unwrap(),
+            gravity_mode: GravityMode::Naive,
+            instant_gravity: false,
+            input_ticks: 1,
+            soft_drop_ticks: 1,
+            lock_delay_ticks: 0,
+            initial_garbage_lines: None,
+            preview_count: 3,
+            practice_mode: false,
+            mode: GameMode::Marathon,
+            spawn_columns: HashMap::new(),
+            initial_held_block: None,
+            combo_points: 50,
+        }
+    }
+
+    pub(crate) type MockGame =
+        Game<MockInput, MockClock, block_generator_test_helpers::MockSampler>;
 
-    pub(super) fn make_game(
+    pub(crate) fn make_game(
         clock: MockClock,
         input: MockInput,
         config: Config,
@@ -573,4 +5791,19 @@ mod test_helpers {
             clock,
         )
     }
+
+    impl MockGame {
+        pub(crate) fn set_board(&mut self, board: StandardBoard) {
+            self.board = board;
+        }
+
+        pub(crate) fn set_active_block(&mut self, active_block: ActiveBlock) {
+            self.active_block = active_block;
+        }
+
+        /// Drops the active block onto the stack without fixing it to the board.
+        pub(crate) fn drop_active_block(&mut self) {
+            self.board.drop_block(&mut self.active_block);
+        }
+    }
 }
@@ -0,0 +1,59 @@
+//! The classic gravity curve, which sets the speed of gravity at each level.
+
+/// The level from which gravity is effectively instant, moving the block down a row on every
+/// frame.
+pub const MAX_LEVEL: u32 = 29;
+
+/// Returns the number of frames between applications of gravity at `level`, following the NES
+/// gravity curve at 60 frames per second. Levels beyond [MAX_LEVEL] drop a row every frame.
+pub fn gravity_ticks_for_level(level: u32) -> u64 {
+    match level {
+        0..=8 => 48 - 5 * u64::from(level),
+        9 => 6,
+        10..=12 => 5,
+        13..=15 => 4,
+        16..=18 => 3,
+        19..MAX_LEVEL => 2,
+        _ => 1,
+    }
+}
+
+#[cfg(test)]
+mod gravity_tests {
+    use super::*;
+
+    mod gravity_ticks_for_level_tests {
+        use super::*;
+
+        #[test]
+        fn when_level_is_0_returns_48() {
+            assert_eq!(gravity_ticks_for_level(0), 48);
+        }
+
+        #[test]
+        fn when_level_is_9_returns_6() {
+            assert_eq!(gravity_ticks_for_level(8), 8);
+            assert_eq!(gravity_ticks_for_level(9), 6);
+        }
+
+        #[test]
+        fn when_level_is_19_returns_2() {
+            assert_eq!(gravity_ticks_for_level(18), 3);
+            assert_eq!(gravity_ticks_for_level(19), 2);
+        }
+
+        #[test]
+        fn when_level_is_at_or_beyond_max_level_returns_1() {
+            assert_eq!(gravity_ticks_for_level(MAX_LEVEL - 1), 2);
+            assert_eq!(gravity_ticks_for_level(MAX_LEVEL), 1);
+            assert_eq!(gravity_ticks_for_level(u32::MAX), 1);
+        }
+
+        #[test]
+        fn never_speeds_down_as_level_increases() {
+            for level in 0..MAX_LEVEL {
+                assert!(gravity_ticks_for_level(level + 1) <= gravity_ticks_for_level(level));
+            }
+        }
+    }
+}
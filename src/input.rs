@@ -1,5 +1,19 @@
-use crossterm::event::{self as termevent, Event as TermEvent, KeyCode, KeyEventKind};
-use std::{io, time::Duration};
+use crossterm::event::{
+    self as termevent, Event as TermEvent, KeyCode, KeyEventKind, KeyboardEnhancementFlags,
+    PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags,
+};
+use crossterm::{execute, terminal};
+use std::{
+    collections::VecDeque,
+    io,
+    time::{Duration, Instant},
+};
+
+use crate::board::StandardBoard;
+
+/// The most repeats [InputState::advance] returns at once. A block can't move further than the
+/// board is wide, so more would have no effect.
+const MAX_REPEATS_PER_ADVANCE: usize = StandardBoard::COLUMNS;
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum Input {
@@ -9,6 +23,15 @@ pub enum Input {
     Right,
     RotateLeft,
     RotateRight,
+    /// Soft drop is held, so gravity applies on every tick until [Input::SoftDropEnd]. For
+    /// frontends that report key releases; [Input::Down] drops a single row per input.
+    SoftDropStart,
+    /// Soft drop is released, restoring normal gravity.
+    SoftDropEnd,
+    /// Drop the active block straight onto the stack and lock it.
+    HardDrop,
+    /// Stash the active block, or swap it with the stashed block.
+    Hold,
     Quit,
     Restart,
     Help,
@@ -31,21 +54,227 @@ impl PollInput for Stdin {
     }
 }
 
+/// Reads key presses and releases from the terminal, applying delayed auto-shift to held
+/// [Input::Left] and [Input::Right] keys with an [InputState] and holding soft drop with
+/// [Input::SoftDropStart] and [Input::SoftDropEnd] for as long as the down key is held. Key
+/// releases are only reported by terminals that support the keyboard enhancement protocol;
+/// elsewhere, auto-shift and held soft drop are disabled and held keys repeat at the terminal's own
+/// key repeat rate, as with [Stdin].
+#[derive(Debug)]
+pub struct Keyboard {
+    /// The auto-shift state, or None if the terminal doesn't report key releases.
+    auto_shift: Option<InputState>,
+
+    /// Repeated moves that fell due but haven't been polled yet.
+    pending: VecDeque<Input>,
+
+    /// When auto-shift was last advanced.
+    last_advanced: Instant,
+}
+
+impl Keyboard {
+    /// Enables key release reporting if the terminal supports it, which is disabled again when the
+    /// [Keyboard] is dropped.
+    pub fn new(auto_shift: InputState) -> io::Result<Self> {
+        let releases_reported = terminal::supports_keyboard_enhancement()?;
+        if releases_reported {
+            execute!(
+                io::stdout(),
+                PushKeyboardEnhancementFlags(KeyboardEnhancementFlags::REPORT_EVENT_TYPES)
+            )?;
+        }
+
+        Ok(Self {
+            auto_shift: releases_reported.then_some(auto_shift),
+            pending: VecDeque::new(),
+            last_advanced: Instant::now(),
+        })
+    }
+}
+
+impl Drop for Keyboard {
+    fn drop(&mut self) {
+        if self.auto_shift.is_some() {
+            // There's nothing useful to do if restoring the terminal fails while dropping.
+            let _ = execute!(io::stdout(), PopKeyboardEnhancementFlags);
+        }
+    }
+}
+
+impl PollInput for Keyboard {
+    fn poll_input(&mut self, duration: Duration) -> io::Result<Input> {
+        let Some(auto_shift) = &mut self.auto_shift else {
+            return Stdin.poll_input(duration);
+        };
+
+        let now = Instant::now();
+        self.pending
+            .extend(auto_shift.advance(now - self.last_advanced));
+        self.last_advanced = now;
+        if let Some(input) = self.pending.pop_front() {
+            return Ok(input);
+        }
+
+        if termevent::poll(duration)? {
+            Ok(translate_with_releases(termevent::read()?, auto_shift))
+        } else {
+            Ok(Input::None)
+        }
+    }
+}
+
+/// Replays a fixed script of inputs, one per poll, for driving games headlessly in bots and tests.
+/// Use [Input::None] to wait for a poll without acting. Once the script is exhausted, every poll
+/// returns [Input::Quit].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScriptedInput(VecDeque<Input>);
+
+impl ScriptedInput {
+    pub fn new(inputs: impl IntoIterator<Item = Input>) -> Self {
+        Self(inputs.into_iter().collect())
+    }
+}
+
+impl PollInput for ScriptedInput {
+    fn poll_input(&mut self, _duration: Duration) -> io::Result<Input> {
+        Ok(self.0.pop_front().unwrap_or(Input::Quit))
+    }
+}
+
+/// Tracks a held horizontal direction to apply delayed auto-shift (DAS) and auto-repeat (ARR):
+/// pressing [Input::Left] or [Input::Right] moves once immediately, then, if the key is still held
+/// after the DAS delay, repeats the move once every ARR interval until it's released. [Keyboard]
+/// applies it to terminal input, and the inputs produced can also be applied to a game with
+/// [crate::game::Game::step].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputState {
+    /// The delay between the initial move and the first repeat.
+    das: Duration,
+
+    /// The interval between repeats once auto-shift has started.
+    arr: Duration,
+
+    /// The direction currently held, if any.
+    held: Option<HeldDirection>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+struct HeldDirection {
+    input: Input,
+
+    /// How long the direction has been held.
+    held_for: Duration,
+
+    /// The number of repeats produced since the direction was pressed.
+    repeats: u128,
+}
+
+impl InputState {
+    pub fn new(das: Duration, arr: Duration) -> Result<Self, String> {
+        if arr.is_zero() {
+            return Err(format!("arr must be greater than zero: arr={arr:?}"));
+        }
+
+        Ok(Self {
+            das,
+            arr,
+            held: None,
+        })
+    }
+
+    pub fn das(&self) -> Duration {
+        self.das
+    }
+
+    pub fn arr(&self) -> Duration {
+        self.arr
+    }
+
+    /// Registers a key press, returning the input to apply immediately. Pressing [Input::Left] or
+    /// [Input::Right] starts holding that direction, replacing any direction already held. Other
+    /// inputs are returned unchanged without affecting the held direction.
+    pub fn press(&mut self, input: Input) -> Input {
+        if matches!(input, Input::Left | Input::Right) {
+            self.held = Some(HeldDirection {
+                input,
+                held_for: Duration::ZERO,
+                repeats: 0,
+            });
+        }
+        input
+    }
+
+    /// Registers a key release, stopping auto-shift if `input` is the direction held.
+    pub fn release(&mut self, input: Input) {
+        if self.held.is_some_and(|held| held.input == input) {
+            self.held = None;
+        }
+    }
+
+    /// Advances the time the held direction has been held by `elapsed`, returning the repeated
+    /// moves that fell due in that time, up to the width of the board.
+    pub fn advance(&mut self, elapsed: Duration) -> Vec<Input> {
+        let Some(held) = &mut self.held else {
+            return Vec::new();
+        };
+
+        held.held_for += elapsed;
+        let Some(auto_shifted_for) = held.held_for.checked_sub(self.das) else {
+            return Vec::new();
+        };
+
+        let due = 1 + auto_shifted_for.as_nanos() / self.arr.as_nanos();
+        let new_repeats = due - held.repeats;
+        held.repeats = due;
+        let new_repeats = new_repeats.min(MAX_REPEATS_PER_ADVANCE as u128) as usize;
+        vec![held.input; new_repeats]
+    }
+}
+
 fn translate(event: TermEvent) -> Input {
-    use Input::*;
     match event {
         TermEvent::Key(key_event) if key_event.kind == KeyEventKind::Press => {
-            match key_event.code {
-                KeyCode::Left => Left,
-                KeyCode::Right => Right,
-                KeyCode::Down => Down,
-                KeyCode::Char('q') | KeyCode::Char('Q') => Quit,
-                KeyCode::Char('z') | KeyCode::Char('Z') => RotateLeft,
-                KeyCode::Char('x') | KeyCode::Char('X') => RotateRight,
-                KeyCode::Char('r') | KeyCode::Char('R') => Restart,
-                _ => None,
-            }
+            translate_code(key_event.code)
+        }
+        _ => Input::None,
+    }
+}
+
+/// Translates a terminal event from a terminal that reports key releases, registering presses and
+/// releases of [Input::Left] and [Input::Right] with `auto_shift`. The down key holds soft drop
+/// until it's released, rather than dropping one row per press.
+fn translate_with_releases(event: TermEvent, auto_shift: &mut InputState) -> Input {
+    let TermEvent::Key(key_event) = event else {
+        return Input::None;
+    };
+    let input = translate_code(key_event.code);
+    match (key_event.kind, input) {
+        (KeyEventKind::Press, Input::Down) => Input::SoftDropStart,
+        (KeyEventKind::Release, Input::Down) => Input::SoftDropEnd,
+        (KeyEventKind::Press, _) => auto_shift.press(input),
+        // Auto-shift and held soft drop replace the terminal's own key repeat for the keys they
+        // hold, but other keys repeat as before.
+        (KeyEventKind::Repeat, Input::Left | Input::Right | Input::Down) => Input::None,
+        (KeyEventKind::Repeat, _) => input,
+        (KeyEventKind::Release, _) => {
+            auto_shift.release(input);
+            Input::None
         }
+    }
+}
+
+fn translate_code(code: KeyCode) -> Input {
+    use Input::*;
+    match code {
+        KeyCode::Left => Left,
+        KeyCode::Right => Right,
+        KeyCode::Down => Down,
+        KeyCode::Char(' ') => HardDrop,
+        KeyCode::Char('c') | KeyCode::Char('C') => Hold,
+        KeyCode::Char('q') | KeyCode::Char('Q') => Quit,
+        KeyCode::Char('z') | KeyCode::Char('Z') => RotateLeft,
+        KeyCode::Char('x') | KeyCode::Char('X') => RotateRight,
+        KeyCode::Char('r') | KeyCode::Char('R') => Restart,
         _ => None,
     }
 }
@@ -82,6 +311,16 @@ mod translate_tests {
         assert_eq!(translate(press(KeyCode::Down)), Input::Down);
     }
 
+    #[test]
+    fn when_space_pressed_returns_hard_drop() {
+        assert_eq!(translate(press(KeyCode::Char(' '))), Input::HardDrop);
+    }
+
+    #[test]
+    fn when_c_pressed_returns_hold() {
+        assert_eq!(translate(press(KeyCode::Char('c'))), Input::Hold);
+    }
+
     #[test]
     fn when_q_pressed_returns_quit() {
         assert_eq!(translate(press(KeyCode::Char('q'))), Input::Quit);
@@ -136,4 +375,223 @@ mod translate_tests {
     fn when_event_is_not_a_key_event_returns_none() {
         assert_eq!(translate(TermEvent::FocusGained), Input::None);
     }
+
+    mod translate_with_releases_tests {
+        use super::*;
+
+        fn auto_shift() -> InputState {
+            InputState::new(Duration::from_millis(170), Duration::from_millis(50)).unwrap()
+        }
+
+        fn repeat(code: KeyCode) -> TermEvent {
+            TermEvent::Key(KeyEvent::new_with_kind(
+                code,
+                KeyModifiers::empty(),
+                KeyEventKind::Repeat,
+            ))
+        }
+
+        #[test]
+        fn when_down_key_pressed_returns_soft_drop_start() {
+            assert_eq!(
+                translate_with_releases(press(KeyCode::Down), &mut auto_shift()),
+                Input::SoftDropStart
+            );
+        }
+
+        #[test]
+        fn when_down_key_released_returns_soft_drop_end() {
+            assert_eq!(
+                translate_with_releases(release(KeyCode::Down), &mut auto_shift()),
+                Input::SoftDropEnd
+            );
+        }
+
+        #[test]
+        fn when_down_key_repeats_returns_none() {
+            assert_eq!(
+                translate_with_releases(repeat(KeyCode::Down), &mut auto_shift()),
+                Input::None
+            );
+        }
+
+        #[test]
+        fn when_direction_is_pressed_and_released_holds_it_until_release() {
+            let mut state = auto_shift();
+
+            assert_eq!(
+                translate_with_releases(press(KeyCode::Left), &mut state),
+                Input::Left
+            );
+            assert_eq!(state.advance(Duration::from_millis(170)), vec![Input::Left]);
+            assert_eq!(
+                translate_with_releases(release(KeyCode::Left), &mut state),
+                Input::None
+            );
+            assert_eq!(state.advance(Duration::from_secs(1)), vec![]);
+        }
+
+        #[test]
+        fn when_other_key_repeats_returns_it() {
+            assert_eq!(
+                translate_with_releases(repeat(KeyCode::Char('x')), &mut auto_shift()),
+                Input::RotateRight
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod scripted_input_tests {
+    use super::*;
+
+    mod poll_input_tests {
+        use super::*;
+
+        #[test]
+        fn replays_script_in_order_then_quits() {
+            let mut input = ScriptedInput::new([Input::Left, Input::None, Input::RotateRight]);
+            let polled: Vec<Input> = (0..5)
+                .map(|_| input.poll_input(Duration::ZERO).unwrap())
+                .collect();
+            assert_eq!(
+                polled,
+                vec![
+                    Input::Left,
+                    Input::None,
+                    Input::RotateRight,
+                    Input::Quit,
+                    Input::Quit
+                ]
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod input_state_tests {
+    use super::*;
+
+    const DAS: Duration = Duration::from_millis(170);
+    const ARR: Duration = Duration::from_millis(50);
+
+    fn input_state() -> InputState {
+        InputState::new(DAS, ARR).unwrap()
+    }
+
+    mod new_tests {
+        use super::*;
+
+        #[test]
+        fn when_arr_is_zero_returns_err() {
+            assert!(InputState::new(DAS, Duration::ZERO).is_err());
+        }
+
+        #[test]
+        fn when_arr_is_positive_returns_ok() {
+            let state = input_state();
+            assert_eq!((state.das(), state.arr()), (DAS, ARR));
+        }
+    }
+
+    mod press_tests {
+        use super::*;
+
+        #[test]
+        fn when_direction_is_pressed_moves_immediately() {
+            let mut state = input_state();
+            assert_eq!(state.press(Input::Left), Input::Left);
+        }
+
+        #[test]
+        fn when_other_input_is_pressed_returns_it_without_holding() {
+            let mut state = input_state();
+            assert_eq!(state.press(Input::HardDrop), Input::HardDrop);
+            assert_eq!(state.advance(DAS * 2), vec![]);
+        }
+
+        #[test]
+        fn when_opposite_direction_is_pressed_replaces_held_direction() {
+            let mut state = input_state();
+            state.press(Input::Left);
+            state.advance(DAS);
+            state.press(Input::Right);
+            assert_eq!(state.advance(DAS), vec![Input::Right]);
+        }
+    }
+
+    mod advance_tests {
+        use super::*;
+
+        #[test]
+        fn when_nothing_is_held_returns_no_moves() {
+            let mut state = input_state();
+            assert_eq!(state.advance(DAS * 10), vec![]);
+        }
+
+        #[test]
+        fn when_das_has_not_elapsed_returns_no_moves() {
+            let mut state = input_state();
+            state.press(Input::Left);
+            assert_eq!(state.advance(DAS - Duration::from_millis(1)), vec![]);
+        }
+
+        #[test]
+        fn when_das_elapses_repeats_once() {
+            let mut state = input_state();
+            state.press(Input::Left);
+            state.advance(DAS - Duration::from_millis(1));
+            assert_eq!(state.advance(Duration::from_millis(1)), vec![Input::Left]);
+        }
+
+        #[test]
+        fn after_das_repeats_once_per_arr() {
+            let mut state = input_state();
+            state.press(Input::Right);
+            state.advance(DAS);
+
+            assert_eq!(state.advance(ARR - Duration::from_millis(1)), vec![]);
+            assert_eq!(state.advance(Duration::from_millis(1)), vec![Input::Right]);
+            assert_eq!(state.advance(ARR * 3), vec![Input::Right; 3]);
+        }
+
+        #[test]
+        fn when_several_repeats_fall_due_at_once_returns_all_of_them() {
+            let mut state = input_state();
+            state.press(Input::Right);
+            assert_eq!(state.advance(DAS + ARR * 2), vec![Input::Right; 3]);
+        }
+
+        #[test]
+        fn when_more_repeats_fall_due_than_the_board_is_wide_returns_one_per_column() {
+            let mut state = InputState::new(DAS, Duration::from_nanos(1)).unwrap();
+            state.press(Input::Left);
+
+            assert_eq!(
+                state.advance(DAS + Duration::from_secs(60)),
+                vec![Input::Left; StandardBoard::COLUMNS]
+            );
+            assert_eq!(state.advance(Duration::from_nanos(1)), vec![Input::Left]);
+        }
+    }
+
+    mod release_tests {
+        use super::*;
+
+        #[test]
+        fn when_held_direction_is_released_stops_repeating() {
+            let mut state = input_state();
+            state.press(Input::Left);
+            state.release(Input::Left);
+            assert_eq!(state.advance(DAS * 2), vec![]);
+        }
+
+        #[test]
+        fn when_other_direction_is_released_keeps_repeating() {
+            let mut state = input_state();
+            state.press(Input::Left);
+            state.release(Input::Right);
+            assert_eq!(state.advance(DAS), vec![Input::Left]);
+        }
+    }
 }
@@ -1,8 +1,39 @@
+//! A terminal Tetris engine.
+//!
+//! The types needed to configure and drive a game are re-exported from the crate root:
+//!
+//! ```
+//! use tetrust::{BlockGenerator, BlockType, Config, Game, Input, ScriptedInput};
+//!
+//! let config = Config {
+//!     preview_count: 5,
+//!     ..Config::default()
+//! };
+//! let game = Game::new(BlockGenerator::seeded(7), ScriptedInput::new([Input::HardDrop]), config);
+//!
+//! assert_eq!(game.score(), 0);
+//! assert_eq!(game.held_block(), None::<BlockType>);
+//! assert_eq!(game.queue().len(), 5);
+//! ```
+
 pub(crate) mod block;
 pub mod block_generator;
 pub(crate) mod board;
 pub mod config;
 pub mod game;
+pub mod gravity;
 pub mod input;
+pub mod placement;
 mod render;
+pub mod replay;
 pub(crate) mod timer;
+pub mod versus;
+
+pub use block::{ActiveBlock, BlockType, KickClass, Position, RotationIndex, TopLeft};
+pub use block_generator::BlockGenerator;
+pub use board::{Board, Cell, ParseBoardError, StandardBoard};
+pub use config::{Config, GameMode, Gravity, GravityMode};
+pub use game::Game;
+pub use input::{Input, InputState, Keyboard, PollInput, ScriptedInput, Stdin};
+pub use replay::Replay;
+pub use timer::{Clock, SystemClock};
@@ -1,8 +1,7 @@
-use std::{thread, time::Duration};
+use std::collections::HashMap;
+use std::time::Duration;
 
-use tetrust::{
-    block_generator::BlockGenerator, config::{Config, Gravity}, game::{Game, UpdateOutcome}, input::Stdin
-};
+use tetrust::{BlockGenerator, Config, Game, GameMode, Gravity, GravityMode, InputState, Keyboard};
 
 /// The number of ticks that must elapse between applications of gravity.
 const INITIAL_GRAVITY_TICKS: u64 = 48;
@@ -11,35 +10,55 @@ const MIN_GRAVITY_TICKS: u64 = 12;
 
 const ACCELERATION: u64 = 4;
 
-const ACCELERATE_EVERY_N_POINTS: u32 = 5;
-
 /// The number of ticks that must elapse between reads of user input.
 const INPUT_TICKS: u64 = 1;
 
+/// The number of ticks that must elapse between applications of gravity while soft dropping.
+const SOFT_DROP_TICKS: u64 = 1;
+
+/// The number of ticks a grounded block rests before locking: half a second at 60 ticks per
+/// second.
+const LOCK_DELAY_TICKS: u64 = 30;
+
+/// The number of upcoming blocks held in the queue.
+const PREVIEW_COUNT: usize = 3;
+
+/// The delay before a held direction starts to auto-repeat.
+const DAS: Duration = Duration::from_millis(170);
+
+/// The interval between auto-repeated moves.
+const ARR: Duration = Duration::from_millis(50);
+
 fn main() -> Result<(), String> {
-    let block_generator = BlockGenerator::new();
     let frame_interval = Duration::from_secs_f32(1.0 / 60.0);
     let config = Config {
         gravity: Gravity::new(INITIAL_GRAVITY_TICKS, MIN_GRAVITY_TICKS, ACCELERATION)?,
+        gravity_mode: GravityMode::Naive,
+        instant_gravity: false,
         frame_interval,
-        accelerate_every_n_points: ACCELERATE_EVERY_N_POINTS,
         input_ticks: INPUT_TICKS,
+        soft_drop_ticks: SOFT_DROP_TICKS,
+        lock_delay_ticks: LOCK_DELAY_TICKS,
+        initial_garbage_lines: None,
+        preview_count: PREVIEW_COUNT,
+        practice_mode: false,
+        mode: GameMode::Marathon,
+        spawn_columns: HashMap::new(),
+        initial_held_block: None,
+        combo_points: 50,
     };
-    let mut game = Game::new(block_generator, Stdin, config);
-
-    ratatui::run(|terminal| -> Result<(), String> {
-        loop {
-            match game.update().map_err(|e| e.to_string())? {
-                UpdateOutcome::Updated => {
-                    _ = terminal
-                        .draw(|frame| frame.render_widget(&game, frame.area()))
-                        .map_err(|e| e.to_string())?
-                }
-                UpdateOutcome::Quit => return Ok(()),
-                _ => (),
-            }
-
-            thread::sleep(game.time_until_next_tick())
-        }
+    let auto_shift = InputState::new(DAS, ARR)?;
+
+    ratatui::run(|terminal| {
+        // Key release reporting is enabled per screen, so the keyboard must be set up after
+        // switching to the alternate screen.
+        let keyboard = Keyboard::new(auto_shift)?;
+        let mut game = Game::new(BlockGenerator::new(), keyboard, config);
+        game.run(|game| {
+            terminal
+                .draw(|frame| frame.render_widget(game, frame.area()))
+                .map(drop)
+        })
     })
+    .map_err(|e| e.to_string())
 }
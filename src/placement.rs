@@ -0,0 +1,192 @@
+use std::iter;
+
+use crate::{
+    block::{ActiveBlock, Position, TopLeft},
+    board::StandardBoard,
+    config::GravityMode,
+};
+
+/// A resting position of a block on the board.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Placement {
+    clockwise_turns: usize,
+    block: ActiveBlock,
+}
+
+impl Placement {
+    /// Returns the number of clockwise quarter turns from the block's original rotation.
+    pub fn clockwise_turns(&self) -> usize {
+        self.clockwise_turns
+    }
+
+    /// Returns the board-space coordinates of the top-left corner of the block's bounding box.
+    pub fn top_left(&self) -> TopLeft {
+        self.block.top_left()
+    }
+
+    /// Returns the board-space positions of the block's cells in order of increasing row then
+    /// column.
+    pub fn positions(&self) -> Vec<Position> {
+        self.block.board_positions().collect()
+    }
+
+    /// Returns the block in its resting position.
+    pub(crate) fn block(&self) -> &ActiveBlock {
+        &self.block
+    }
+}
+
+/// Returns the resting positions the block can reach by rotating in place, shifting horizontally,
+/// then dropping straight down.
+///
+/// Placements are ordered by the number of clockwise turns, then by column, so that searches over
+/// them are deterministic.
+pub(crate) fn reachable_placements(board: &StandardBoard, block: &ActiveBlock) -> Vec<Placement> {
+    placement_iter(board, block).collect()
+}
+
+/// Lazily yields the same placements as [reachable_placements], in the same order.
+pub(crate) fn placement_iter<'a>(
+    board: &'a StandardBoard,
+    block: &ActiveBlock,
+) -> impl Iterator<Item = Placement> + 'a {
+    let block = block.clone();
+    (0..4).flat_map(move |clockwise_turns| {
+        let mut rotated = block.clone();
+        (0..clockwise_turns).for_each(|_| rotated.rotate_clockwise());
+
+        // Find the leftmost reachable column, then sweep rightwards.
+        let leftmost = (!board.collides(&rotated)).then(|| {
+            let mut shifted = rotated;
+            loop {
+                shifted.move_left();
+                if board.collides(&shifted) {
+                    shifted.move_right();
+                    return shifted;
+                }
+            }
+        });
+        let sweep = iter::successors(leftmost, move |shifted| {
+            let mut next = shifted.clone();
+            next.move_right();
+            (!board.collides(&next)).then_some(next)
+        });
+
+        sweep.map(move |mut block| {
+            board.drop_block(&mut block);
+            Placement {
+                clockwise_turns,
+                block,
+            }
+        })
+    })
+}
+
+/// Searches depth-first for placements of `pieces`, in order, that leave the board empty, pushing
+/// them onto `path`. Returns true if some prefix of the pieces clears the board, in which case
+/// `path` holds the placements; otherwise `path` is left unchanged.
+pub(crate) fn solve_perfect_clear(
+    board: &StandardBoard,
+    pieces: &[ActiveBlock],
+    gravity_mode: GravityMode,
+    path: &mut Vec<Placement>,
+) -> bool {
+    if board.is_empty() {
+        return true;
+    }
+    let filled = board.occupied_cells().count();
+
+    // Lines clear a full row of cells at a time, so the board can only be emptied after placing a
+    // number of pieces that brings the filled cell count to a multiple of the board's width.
+    let clearable = pieces
+        .iter()
+        .scan(filled, |cells, piece| {
+            *cells += piece.board_positions().count();
+            Some(*cells)
+        })
+        .any(|cells| cells.is_multiple_of(StandardBoard::COLUMNS));
+    if !clearable {
+        return false;
+    }
+
+    let Some((piece, rest)) = pieces.split_first() else {
+        return false;
+    };
+    for placement in placement_iter(board, piece) {
+        let mut next = board.clone();
+        next.fix_active_block(placement.block());
+        next.clear_lines_with(gravity_mode);
+        if next.buffer_zone_occupied() {
+            continue;
+        }
+
+        path.push(placement);
+        if solve_perfect_clear(&next, rest, gravity_mode, path) {
+            return true;
+        }
+        path.pop();
+    }
+    false
+}
+
+#[cfg(test)]
+mod placement_tests {
+    use super::*;
+    use crate::block::BlockType;
+
+    mod reachable_placements_tests {
+        use super::*;
+
+        #[test]
+        fn orders_placements_by_clockwise_turns_then_column() {
+            let placements =
+                reachable_placements(&StandardBoard::new(), &ActiveBlock::new(BlockType::J));
+
+            let keys: Vec<(usize, isize)> = placements
+                .iter()
+                .map(|p| (p.clockwise_turns(), p.top_left().1))
+                .collect();
+            let mut sorted = keys.clone();
+            sorted.sort();
+            assert_eq!(keys, sorted);
+            assert_eq!(keys.first(), Some(&(0, 0)));
+        }
+
+        #[test]
+        fn when_block_is_i_on_empty_board_yields_every_in_bounds_column_per_rotation() {
+            let placements =
+                reachable_placements(&StandardBoard::new(), &ActiveBlock::new(BlockType::I));
+
+            // Horizontal rotations span 4 columns and vertical rotations span 1.
+            let horizontal = StandardBoard::COLUMNS - 3;
+            let vertical = StandardBoard::COLUMNS;
+            assert_eq!(placements.len(), 2 * horizontal + 2 * vertical);
+            assert_eq!(placements.len(), 34);
+        }
+
+        #[test]
+        fn when_searched_twice_returns_identical_placements() {
+            let mut board = StandardBoard::new();
+            board.add_garbage_lines(3, 7);
+            let block = ActiveBlock::new(BlockType::T);
+
+            let first = reachable_placements(&board, &block);
+            let second = reachable_placements(&board, &block);
+
+            assert_eq!(first, second);
+        }
+
+        #[test]
+        fn places_every_block_at_rest() {
+            let board = StandardBoard::new();
+            for placement in reachable_placements(&board, &ActiveBlock::new(BlockType::S)) {
+                assert!(
+                    placement
+                        .positions()
+                        .iter()
+                        .any(|(r, _)| *r == StandardBoard::ROWS - 1)
+                );
+            }
+        }
+    }
+}
@@ -2,25 +2,21 @@ use indoc::indoc;
 use ratatui::{
     buffer::Buffer,
     layout::{Constraint, Layout, Rect},
-    style::Stylize,
+    style::{Style, Stylize},
     symbols::Marker,
     text::Text,
     widgets::{Block, Borders, Paragraph, Widget, canvas::Canvas},
 };
 
-use crate::{
-    block::Position,
-    board::Board,
-    game::Game,
-};
+use crate::{block::Position, board::StandardBoard, game::Game};
 
 const BORDER_THICKNESS: u16 = 1;
 
-const BOARD_HEIGHT: u16 = Board::PLAYABLE_ROWS as u16 + BORDER_THICKNESS * 2;
+const BOARD_HEIGHT: u16 = StandardBoard::PLAYABLE_ROWS as u16 + BORDER_THICKNESS * 2;
 
 /// The number of rendered columns is double the columns of the board, since square cells are
 /// rendered using two █ characters: ██.
-const BOARD_WIDTH: u16 = Board::COLUMNS as u16 * 2 + BORDER_THICKNESS * 2;
+const BOARD_WIDTH: u16 = StandardBoard::COLUMNS as u16 * 2 + BORDER_THICKNESS * 2;
 
 const BOARD_SIDEBAR_PADDING: u16 = 2;
 
@@ -30,6 +26,10 @@ const SCORE_WIDGET_HEIGHT: u16 = 3;
 
 const NEXT_BLOCK_WIDGET_HEIGHT: u16 = 4;
 
+/// The number of free rows at the top of the board below which the board's border is highlighted
+/// to warn the player that they're close to topping out.
+const DANGER_THRESHOLD_ROWS: usize = 4;
+
 impl<I, C, S> Widget for &Game<I, C, S> {
     fn render(self, area: Rect, buf: &mut Buffer)
     where
@@ -37,7 +37,7 @@ impl<I, C, S> Widget for &Game<I, C, S> {
     {
         let header = Text::from_iter([
             "TETRUST".bold(),
-            "<←|↓|→> Move | <z|x> Rotate | <r> Restart | <q> Quit".into(),
+            "<←|↓|→> Move | <space> Drop | <z|x> Rotate | <c> Hold | <r> Restart | <q> Quit".into(),
         ]);
 
         let [text_area, _, game_area] = area.layout(&Layout::vertical([
@@ -70,45 +70,60 @@ impl<I, C, S> Game<I, C, S> {
     }
 
     fn render_board(&self, board_area: Rect, buf: &mut Buffer) {
+        let border = if self.board().is_in_danger(DANGER_THRESHOLD_ROWS) {
+            Block::bordered().border_style(Style::new().red())
+        } else {
+            Block::bordered()
+        };
+
         Canvas::default()
             // Bordering the canvas adds 2 to its vertical and horizontal dimensions. The layout
             // it's rendered to must provide exactly enough room for the board and its borders to
             // avoid artifacts from the resolution mismatch.
-            .block(Block::bordered())
+            .block(border)
             // x_bounds and y_bounds define the canvas' viewport - inside its borders.
             //
             // Due to ratatui's internal rendering logic, stepping by two columns on each loop
             // iteration to render double-width blocks (██), requires a negative x-offset to avoid
             // blocks slipping behind the left border of the canvas.
-            .x_bounds([-1.0, (Board::COLUMNS * 2) as f64 - 1.0])
+            .x_bounds([-1.0, (StandardBoard::COLUMNS * 2) as f64 - 1.0])
             // The y-bounds don't require an offset, since we're stepping by one row each time.
-            .y_bounds([0.0, (Board::ROWS - Board::BUFFER_ZONE_ROWS - 1) as f64])
+            .y_bounds([
+                0.0,
+                (StandardBoard::ROWS - StandardBoard::BUFFER_ZONE_ROWS - 1) as f64,
+            ])
             .marker(Marker::HalfBlock)
             .paint(|ctx| {
                 // Iterate over all cells of the board and active block.
                 let mut active_block_positions = self.active_block().board_positions().peekable();
-                for (i_row, row) in self.board().iter().skip(Board::BUFFER_ZONE_ROWS).enumerate() {
+                for (i_row, row) in self
+                    .board()
+                    .iter()
+                    .skip(StandardBoard::BUFFER_ZONE_ROWS)
+                    .enumerate()
+                {
                     for (i_col, cell) in row.iter().enumerate() {
                         let (x, y) = to_terminal_coords((i_row, i_col));
                         match active_block_positions.peek() {
                             // If the current position is an active block position inside the
                             // buffer zone, skip the cell.
-                            Some((i_ab_row, _)) if *i_ab_row < Board::BUFFER_ZONE_ROWS => {
+                            Some((i_ab_row, _)) if *i_ab_row < StandardBoard::BUFFER_ZONE_ROWS => {
                                 active_block_positions.next();
                             }
                             // If the current position is an active block position which is on the
                             // visible board, render the current active block cell and advance the
                             // iterator to the next.
                             Some((i_ab_row, i_ab_col))
-                                if *i_ab_row == i_row + Board::BUFFER_ZONE_ROWS && *i_ab_col == i_col =>
+                                if *i_ab_row == i_row + StandardBoard::BUFFER_ZONE_ROWS
+                                    && *i_ab_col == i_col =>
                             {
                                 ctx.print(x, y, self.active_block().grid_cell());
                                 active_block_positions.next();
                             }
                             // Otherwise, render the fixed cell from the board.
                             _ => {
-                                if let Some(block_type) = cell {
-                                    ctx.print(x, y, block_type.grid_cell());
+                                if let Some(cell) = cell {
+                                    ctx.print(x, y, cell.grid_cell_in(self.custom_pieces()));
                                 }
                             }
                         }
@@ -119,15 +134,19 @@ impl<I, C, S> Game<I, C, S> {
     }
 
     fn render_sidebar(&self, sidebar_area: Rect, buf: &mut Buffer) {
-        let [score_area, _, next_block_area, _] = sidebar_area.layout(&Layout::vertical([
-            Constraint::Length(SCORE_WIDGET_HEIGHT),
-            Constraint::Length(1),
-            Constraint::Length(NEXT_BLOCK_WIDGET_HEIGHT),
-            Constraint::Fill(1),
-        ]));
+        let [score_area, _, next_block_area, _, held_block_area, _] =
+            sidebar_area.layout(&Layout::vertical([
+                Constraint::Length(SCORE_WIDGET_HEIGHT),
+                Constraint::Length(1),
+                Constraint::Length(NEXT_BLOCK_WIDGET_HEIGHT),
+                Constraint::Length(1),
+                Constraint::Length(NEXT_BLOCK_WIDGET_HEIGHT),
+                Constraint::Fill(1),
+            ]));
 
         self.render_score(score_area, buf);
         self.render_next_block(next_block_area, buf);
+        self.render_held_block(held_block_area, buf);
     }
 
     fn render_score(&self, score_area: Rect, buf: &mut Buffer) {
@@ -138,11 +157,27 @@ impl<I, C, S> Game<I, C, S> {
     }
 
     fn render_next_block(&self, next_block_area: Rect, buf: &mut Buffer) {
-        let next_block = Paragraph::new(self.queue()[0].schematic())
+        let schematic = self
+            .queue()
+            .first()
+            .map(|&block_type| self.custom_pieces().schematic(block_type))
+            .unwrap_or_default();
+        let next_block = Paragraph::new(schematic)
             .left_aligned()
             .block(Block::new().borders(Borders::ALL).title("Next"));
         next_block.render(next_block_area, buf)
     }
+
+    fn render_held_block(&self, held_block_area: Rect, buf: &mut Buffer) {
+        let schematic = self
+            .held_block()
+            .map(|block_type| self.custom_pieces().schematic(block_type))
+            .unwrap_or_default();
+        let held_block = Paragraph::new(schematic)
+            .left_aligned()
+            .block(Block::new().borders(Borders::ALL).title("Hold"));
+        held_block.render(held_block_area, buf)
+    }
 }
 
 fn render_game_over(game_rect: Rect, buf: &mut Buffer) {
@@ -171,6 +206,6 @@ fn to_terminal_coords((row, col): Position) -> (f64, f64) {
         // Widths are doubled, since square tiles are achieved using two █ characters: ██.
         (col * 2) as f64,
         // Rows are counted from the bottom of the area instead of the top.
-        (Board::PLAYABLE_ROWS - row - 1) as f64,
+        (StandardBoard::PLAYABLE_ROWS - row - 1) as f64,
     )
 }
@@ -0,0 +1,236 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::block_generator::BlockGenerator;
+use crate::config::Config;
+use crate::game::Game;
+use crate::input::{Input, ScriptedInput};
+
+/// A record of the inputs applied to a game, with the tick on which each was applied, and the seed
+/// of the game's [BlockGenerator], from which the game can be played back deterministically.
+///
+/// Playback advances a new game tick by tick with [Game::advance_tick], so gravity and lock delay
+/// play out between inputs just as they did in the recorded game. Replays reproduce games created
+/// with the same config and a generator from [BlockGenerator::seeded], recorded from their first
+/// tick, such as by [Replay::record_game].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Replay {
+    seed: u64,
+    config: Config,
+    events: Vec<(u64, Input)>,
+}
+
+impl Replay {
+    /// Begins recording a game played with `config` and blocks from [BlockGenerator::seeded] with
+    /// `seed`.
+    pub fn new(seed: u64, config: Config) -> Self {
+        Self {
+            seed,
+            config,
+            events: Vec::new(),
+        }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Returns the recorded (tick, input) pairs in the order they were applied.
+    pub fn events(&self) -> &[(u64, Input)] {
+        &self.events
+    }
+
+    /// Records that `input` was applied on `tick`. [Input::None] has no effect on the game and
+    /// isn't recorded. Returns an error if `tick` is earlier than the last recorded tick.
+    pub fn record(&mut self, tick: u64, input: Input) -> Result<(), String> {
+        if let Some(&(last_tick, _)) = self.events.last()
+            && tick < last_tick
+        {
+            return Err(format!(
+                "tick cannot be earlier than the last recorded tick: tick={tick}, last_tick={last_tick}"
+            ));
+        }
+
+        if input != Input::None {
+            self.events.push((tick, input));
+        }
+        Ok(())
+    }
+
+    /// Records every input that `game` polls from now on, on the tick it was polled, using
+    /// [Game::set_input_listener]. The game should be newly created with the replay's seed and
+    /// config, and it replaces any input listener the game already had.
+    pub fn record_game<I, C, S>(replay: &Rc<RefCell<Self>>, game: &mut Game<I, C, S>) {
+        let replay = Rc::clone(replay);
+        game.set_input_listener(move |tick, input| {
+            replay
+                .borrow_mut()
+                .record(tick, input)
+                .expect("a game's tick count should never decrease");
+        });
+    }
+
+    /// Plays the recorded inputs back in a new game, returning the game in its final state. The
+    /// game is advanced one tick at a time up to the last recorded tick, applying each input after
+    /// the gravity of the tick it was recorded on.
+    pub fn play(&self) -> Game<ScriptedInput> {
+        let mut game = Game::new(
+            BlockGenerator::seeded(self.seed),
+            ScriptedInput::new([]),
+            self.config.clone(),
+        );
+        let mut events = self.events.as_slice();
+        while !events.is_empty() {
+            let tick = game.tick_count() + 1;
+            let due = events.partition_point(|&(t, _)| t <= tick);
+            let inputs: Vec<Input> = events[..due].iter().map(|&(_, input)| input).collect();
+            game.advance_tick(&inputs);
+            events = &events[due..];
+        }
+        game
+    }
+}
+
+#[cfg(test)]
+mod replay_tests {
+    use std::time::Instant;
+
+    use super::*;
+    use crate::game::UpdateOutcome;
+    use crate::game::test_helpers::{FRAME_INTERVAL, test_config};
+    use crate::timer::test_helpers::MockClock;
+
+    const SEED: u64 = 42;
+
+    mod record_tests {
+        use super::*;
+
+        #[test]
+        fn records_inputs_in_order() {
+            let mut replay = Replay::new(SEED, test_config());
+            replay.record(1, Input::Left).unwrap();
+            replay.record(1, Input::RotateRight).unwrap();
+            replay.record(4, Input::HardDrop).unwrap();
+
+            assert_eq!(
+                replay.events(),
+                &[
+                    (1, Input::Left),
+                    (1, Input::RotateRight),
+                    (4, Input::HardDrop)
+                ]
+            );
+        }
+
+        #[test]
+        fn when_input_is_none_does_not_record_it() {
+            let mut replay = Replay::new(SEED, test_config());
+            replay.record(1, Input::None).unwrap();
+            assert_eq!(replay.events(), &[]);
+        }
+
+        #[test]
+        fn when_tick_is_earlier_than_last_tick_returns_err() {
+            let mut replay = Replay::new(SEED, test_config());
+            replay.record(5, Input::Left).unwrap();
+            assert!(replay.record(4, Input::Right).is_err());
+            assert_eq!(replay.events(), &[(5, Input::Left)]);
+        }
+    }
+
+    mod play_tests {
+        use super::*;
+
+        #[test]
+        fn reproduces_the_recorded_games_final_board_and_score() {
+            let inputs = [
+                Input::Left,
+                Input::Left,
+                Input::HardDrop,
+                Input::RotateRight,
+                Input::Right,
+                Input::Right,
+                Input::Right,
+                Input::HardDrop,
+                Input::Hold,
+                Input::HardDrop,
+                Input::RotateLeft,
+                Input::Left,
+                Input::Down,
+                Input::HardDrop,
+            ];
+            let clock = MockClock::new(Instant::now());
+            let mut game = Game::new_with_clock(
+                BlockGenerator::seeded(SEED),
+                ScriptedInput::new(inputs),
+                test_config(),
+                clock.clone(),
+            );
+            let replay = Rc::new(RefCell::new(Replay::new(SEED, test_config())));
+            Replay::record_game(&replay, &mut game);
+
+            loop {
+                clock.advance(FRAME_INTERVAL);
+                if let UpdateOutcome::Quit = game.update().unwrap() {
+                    break;
+                }
+            }
+            let played = replay.borrow().play();
+
+            assert!(game.score() > 0);
+            assert_eq!(played.board(), game.board());
+            assert_eq!(played.score(), game.score());
+            assert_eq!(played.tick_count(), game.tick_count());
+            assert_eq!(
+                played.snapshot(),
+                game.snapshot(),
+                "{}",
+                played.snapshot().diff(&game.snapshot())
+            );
+        }
+
+        #[test]
+        fn reproduces_a_recorded_game_in_which_gravity_locks_blocks() {
+            let config = Config {
+                lock_delay_ticks: 2,
+                ..test_config()
+            };
+            let clock = MockClock::new(Instant::now());
+            let mut game = Game::new_with_clock(
+                BlockGenerator::seeded(SEED),
+                ScriptedInput::new(
+                    [Input::Left, Input::RotateRight]
+                        .into_iter()
+                        .chain([Input::None; 60])
+                        .chain([Input::Right, Input::Right, Input::HardDrop])
+                        .chain([Input::None; 60])
+                        .chain([Input::Hold, Input::Left]),
+                ),
+                config.clone(),
+                clock.clone(),
+            );
+            let replay = Rc::new(RefCell::new(Replay::new(SEED, config)));
+            Replay::record_game(&replay, &mut game);
+
+            loop {
+                clock.advance(FRAME_INTERVAL);
+                if let UpdateOutcome::Quit = game.update().unwrap() {
+                    break;
+                }
+            }
+            let played = replay.borrow().play();
+
+            // Only one block was hard dropped, so the cells of any others were locked by gravity.
+            assert!(game.board().occupied_cells().count() > 4);
+            assert_eq!(played.board(), game.board());
+            assert_eq!(played.score(), game.score());
+            assert_eq!(played.tick_count(), game.tick_count());
+            assert_eq!(
+                played.snapshot(),
+                game.snapshot(),
+                "{}",
+                played.snapshot().diff(&game.snapshot())
+            );
+        }
+    }
+}
@@ -1,4 +1,7 @@
-use std::time::{Duration, Instant};
+use std::{
+    thread,
+    time::{Duration, Instant},
+};
 
 /// A game tick, where each field is a flag representing whether the correponding event
 /// should be triggered on that tick.
@@ -6,6 +9,10 @@ use std::time::{Duration, Instant};
 pub struct Tick {
     pub gravity: bool,
     pub input: bool,
+
+    /// True if soft drop was engaged on this tick, in which case gravity follows the faster soft
+    /// drop cadence.
+    pub soft_drop: bool,
 }
 
 impl Tick {
@@ -23,7 +30,8 @@ mod any_tests {
         assert!(
             !Tick {
                 gravity: false,
-                input: false
+                input: false,
+                soft_drop: false,
             }
             .any()
         );
@@ -34,7 +42,8 @@ mod any_tests {
         assert!(
             Tick {
                 gravity: true,
-                input: false
+                input: false,
+                soft_drop: false,
             }
             .any()
         );
@@ -45,7 +54,8 @@ mod any_tests {
         assert!(
             Tick {
                 gravity: false,
-                input: true
+                input: true,
+                soft_drop: false,
             }
             .any()
         );
@@ -56,7 +66,8 @@ mod any_tests {
         assert!(
             Tick {
                 gravity: true,
-                input: true
+                input: true,
+                soft_drop: false,
             }
             .any()
         );
@@ -65,6 +76,11 @@ mod any_tests {
 
 pub trait Clock {
     fn now(&self) -> Instant;
+
+    /// Blocks until the given duration has elapsed on this clock.
+    fn sleep(&self, duration: Duration) {
+        thread::sleep(duration)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -88,8 +104,20 @@ pub struct GameTimer<C = SystemClock> {
     // The number of ticks after which user input should be read.
     input_ticks: u64,
 
+    // The number of ticks after which gravity should be applied while soft drop is engaged.
+    soft_drop_ticks: u64,
+
+    // True while soft drop is engaged.
+    soft_drop: bool,
+
     // The total number of ticks elapsed.
     tick_count: u64,
+
+    // Ticks that have elapsed but have yet to be returned by update, such as after a long stall.
+    pending_ticks: u32,
+
+    // The maximum number of pending ticks to catch up on, or None to catch up on every one.
+    max_catch_up_ticks: Option<u32>,
 }
 
 impl GameTimer<SystemClock> {
@@ -101,7 +129,32 @@ impl GameTimer<SystemClock> {
     }
 }
 
+impl<C> GameTimer<C> {
+    /// Returns the interval between ticks.
+    pub fn tick_interval(&self) -> Duration {
+        self.interval_timer.tick_interval
+    }
+
+    /// Returns the number of ticks elapsed since the timer was created.
+    pub fn tick_count(&self) -> u64 {
+        self.tick_count
+    }
+
+    /// Sets the number of ticks elapsed, such as when resuming a saved game. The gravity and input
+    /// cadences carry on from the same point.
+    pub fn set_tick_count(&mut self, tick_count: u64) {
+        self.tick_count = tick_count;
+    }
+}
+
 impl<C: Clock> GameTimer<C> {
+    /// By default, ticks missed during a stall are dropped rather than replayed, so a pause never
+    /// causes a burst of gravity.
+    pub const DEFAULT_MAX_CATCH_UP_TICKS: Option<u32> = Some(1);
+
+    /// By default, gravity applies on every tick while soft drop is engaged.
+    pub const DEFAULT_SOFT_DROP_TICKS: u64 = 1;
+
     pub(crate) fn new_with_clock(
         tick_interval: Duration,
         gravity_ticks: u64,
@@ -113,6 +166,10 @@ impl<C: Clock> GameTimer<C> {
             tick_count: 0,
             gravity_ticks,
             input_ticks,
+            soft_drop_ticks: Self::DEFAULT_SOFT_DROP_TICKS,
+            soft_drop: false,
+            pending_ticks: 0,
+            max_catch_up_ticks: Self::DEFAULT_MAX_CATCH_UP_TICKS,
         }
     }
 
@@ -125,35 +182,95 @@ impl<C: Clock> GameTimer<C> {
         self.gravity_ticks = ticks;
     }
 
+    /// Returns the number of ticks between applications of gravity at the current cadence, which
+    /// is faster while soft drop is engaged.
+    pub fn effective_gravity_ticks(&self) -> u64 {
+        if self.soft_drop {
+            self.soft_drop_ticks
+        } else {
+            self.gravity_ticks()
+        }
+    }
+
+    /// Sets the number of ticks required to trigger gravity events while soft drop is engaged.
+    pub fn set_soft_drop_ticks(&mut self, ticks: u64) {
+        self.soft_drop_ticks = ticks;
+    }
+
+    /// Engages or disengages soft drop, switching gravity to the soft drop cadence from the next
+    /// tick.
+    pub fn set_soft_drop(&mut self, engaged: bool) {
+        self.soft_drop = engaged;
+    }
+
+    pub fn max_catch_up_ticks(&self) -> Option<u32> {
+        self.max_catch_up_ticks
+    }
+
+    /// Sets the maximum number of ticks the timer will catch up on when several tick intervals
+    /// elapse between updates, or None to catch up on all of them. Ticks beyond the cap are
+    /// dropped.
+    pub fn set_max_catch_up_ticks(&mut self, max: Option<u32>) {
+        self.max_catch_up_ticks = max;
+        self.cap_pending_ticks();
+    }
+
     /// Update triggers the timer to evaluate how much progress has been made towards the next tick
     /// since the last.
     ///
     /// Returns [None] if insufficient time has elapsed for the timer to tick.
     ///
     /// Returns [Some(Tick)] if the timer has ticked, with the fields of [Tick] indicating which
-    /// events are scheduled to occur on that tick.
+    /// events are scheduled to occur on that tick. If several intervals have elapsed since the
+    /// last update, the ticks that weren't returned are caught up on by the following updates, up
+    /// to [Self::max_catch_up_ticks] in total.
     pub fn update(&mut self) -> Option<Tick> {
-        if self.interval_timer.update() {
-            // Ticks at the boundary of a u64 will be imperfect... but that's not going to happen.
-            self.tick_count = self.tick_count.wrapping_add(1);
-            Some(self.last_tick())
-        } else {
-            None
+        self.pending_ticks = self
+            .pending_ticks
+            .saturating_add(self.interval_timer.update_count());
+        self.cap_pending_ticks();
+        if self.pending_ticks == 0 {
+            return None;
         }
+
+        self.pending_ticks -= 1;
+        Some(self.advance())
     }
 
-    /// Returns the remaining duration until the next tick.
+    /// Ticks once without consulting the clock, returning the events scheduled for the tick. Used
+    /// to drive games whose timing is decided elsewhere, such as by a timer shared between games or
+    /// when playing back a replay.
+    pub fn advance(&mut self) -> Tick {
+        // Ticks at the boundary of a u64 will be imperfect... but that's not going to happen.
+        self.tick_count = self.tick_count.wrapping_add(1);
+        self.last_tick()
+    }
+
+    /// Returns the remaining duration until the next tick, which is zero while the timer is
+    /// catching up on missed ticks.
     pub fn time_until_next_tick(&self) -> Duration {
+        if self.pending_ticks > 0 {
+            return Duration::ZERO;
+        }
         self.interval_timer
             .next_tick_at
             .saturating_duration_since(self.interval_timer.now())
     }
 
+    fn cap_pending_ticks(&mut self) {
+        if let Some(max) = self.max_catch_up_ticks {
+            self.pending_ticks = self.pending_ticks.min(max);
+        }
+    }
+
     /// Returns the most recent tick.
     fn last_tick(&self) -> Tick {
         Tick {
-            gravity: self.tick_count.is_multiple_of(self.gravity_ticks),
+            gravity: self
+                .tick_count
+                .is_multiple_of(self.effective_gravity_ticks()),
             input: self.tick_count.is_multiple_of(self.input_ticks),
+            soft_drop: self.soft_drop,
         }
     }
 }
@@ -190,6 +307,10 @@ pub(crate) mod test_helpers {
         fn now(&self) -> Instant {
             self.0.get()
         }
+
+        fn sleep(&self, duration: Duration) {
+            self.advance(duration);
+        }
     }
 }
 
@@ -212,7 +333,11 @@ mod game_timer_tests {
                 interval_timer: IntervalTimer::new(interval, MockClock::new(now)),
                 gravity_ticks: 5,
                 input_ticks: 1,
+                soft_drop_ticks: 1,
+                soft_drop: false,
                 tick_count: 0,
+                pending_ticks: 0,
+                max_catch_up_ticks: Some(1),
             };
 
             assert_eq!(actual, expected);
@@ -258,7 +383,11 @@ mod game_timer_tests {
                 ),
                 gravity_ticks,
                 input_ticks,
+                soft_drop_ticks: 1,
+                soft_drop: false,
                 tick_count,
+                pending_ticks: 0,
+                max_catch_up_ticks: None,
             }
         }
 
@@ -269,7 +398,8 @@ mod game_timer_tests {
                 timer.last_tick(),
                 Tick {
                     gravity: true,
-                    input: true
+                    input: true,
+                    soft_drop: false,
                 }
             );
         }
@@ -281,7 +411,8 @@ mod game_timer_tests {
                 timer.last_tick(),
                 Tick {
                     gravity: true,
-                    input: false
+                    input: false,
+                    soft_drop: false,
                 }
             );
         }
@@ -293,7 +424,8 @@ mod game_timer_tests {
                 timer.last_tick(),
                 Tick {
                     gravity: false,
-                    input: true
+                    input: true,
+                    soft_drop: false,
                 }
             );
         }
@@ -305,7 +437,8 @@ mod game_timer_tests {
                 timer.last_tick(),
                 Tick {
                     gravity: false,
-                    input: false
+                    input: false,
+                    soft_drop: false,
                 }
             );
         }
@@ -333,13 +466,155 @@ mod game_timer_tests {
                 timer.update(),
                 Some(Tick {
                     gravity: true,
-                    input: true
+                    input: true,
+                    soft_drop: false,
                 })
             );
             assert_eq!(timer.tick_count, 1);
         }
     }
 
+    mod advance_tests {
+        use super::*;
+
+        #[test]
+        fn ticks_without_the_clock_advancing() {
+            let clock = MockClock::new(Instant::now());
+            let mut timer = GameTimer::new_with_clock(Duration::from_millis(100), 2, 1, clock);
+
+            let gravity: Vec<bool> = (0..4).map(|_| timer.advance().gravity).collect();
+
+            assert_eq!(gravity, [false, true, false, true]);
+            assert_eq!(timer.tick_count, 4);
+        }
+    }
+
+    mod catch_up_tests {
+        use super::*;
+
+        const INTERVAL: Duration = Duration::from_millis(100);
+
+        fn ticks_after_stall(max_catch_up_ticks: Option<u32>) -> u32 {
+            let clock = MockClock::new(Instant::now());
+            let mut timer = GameTimer::new_with_clock(INTERVAL, 1, 1, clock.clone());
+            timer.set_max_catch_up_ticks(max_catch_up_ticks);
+            clock.advance(INTERVAL * 10);
+
+            let mut count = 0;
+            while timer.update().is_some() {
+                count += 1;
+            }
+            count
+        }
+
+        #[test]
+        fn when_uncapped_catches_up_on_every_missed_tick() {
+            assert_eq!(ticks_after_stall(None), 10);
+        }
+
+        #[test]
+        fn when_capped_catches_up_on_at_most_the_cap() {
+            assert_eq!(ticks_after_stall(Some(3)), 3);
+        }
+
+        #[test]
+        fn by_default_applies_one_tick_after_a_stall() {
+            assert_eq!(
+                ticks_after_stall(GameTimer::<MockClock>::DEFAULT_MAX_CATCH_UP_TICKS),
+                1
+            );
+        }
+
+        #[test]
+        fn while_catching_up_next_tick_is_due_immediately() {
+            let clock = MockClock::new(Instant::now());
+            let mut timer = GameTimer::new_with_clock(INTERVAL, 1, 1, clock.clone());
+            timer.set_max_catch_up_ticks(None);
+            clock.advance(INTERVAL * 2 + Duration::from_millis(30));
+
+            timer.update();
+
+            assert_eq!(timer.time_until_next_tick(), Duration::ZERO);
+            timer.update();
+            assert_eq!(timer.time_until_next_tick(), Duration::from_millis(70));
+        }
+
+        #[test]
+        fn catch_up_ticks_advance_the_tick_count() {
+            let clock = MockClock::new(Instant::now());
+            let mut timer = GameTimer::new_with_clock(INTERVAL, 2, 1, clock.clone());
+            timer.set_max_catch_up_ticks(None);
+            clock.advance(INTERVAL * 2);
+
+            let ticks = [timer.update(), timer.update(), timer.update()];
+
+            assert_eq!(
+                ticks.map(|tick| tick.map(|t| t.gravity)),
+                [Some(false), Some(true), None]
+            );
+        }
+    }
+
+    mod soft_drop_tests {
+        use super::*;
+
+        const INTERVAL: Duration = Duration::from_millis(100);
+
+        fn gravity_over(
+            timer: &mut GameTimer<MockClock>,
+            clock: &MockClock,
+            ticks: usize,
+        ) -> Vec<bool> {
+            (0..ticks)
+                .map(|_| {
+                    clock.advance(INTERVAL);
+                    timer.update().unwrap().gravity
+                })
+                .collect()
+        }
+
+        #[test]
+        fn when_engaged_gravity_follows_soft_drop_cadence() {
+            let clock = MockClock::new(Instant::now());
+            let mut timer = GameTimer::new_with_clock(INTERVAL, 4, 1, clock.clone());
+            timer.set_soft_drop_ticks(2);
+
+            timer.set_soft_drop(true);
+
+            assert_eq!(
+                gravity_over(&mut timer, &clock, 4),
+                [false, true, false, true]
+            );
+        }
+
+        #[test]
+        fn when_disengaged_gravity_reverts_to_normal_cadence() {
+            let clock = MockClock::new(Instant::now());
+            let mut timer = GameTimer::new_with_clock(INTERVAL, 4, 1, clock.clone());
+            timer.set_soft_drop_ticks(2);
+            timer.set_soft_drop(true);
+            gravity_over(&mut timer, &clock, 2);
+
+            timer.set_soft_drop(false);
+
+            assert_eq!(
+                gravity_over(&mut timer, &clock, 4),
+                [false, true, false, false]
+            );
+        }
+
+        #[test]
+        fn tick_reports_whether_soft_drop_was_engaged() {
+            let clock = MockClock::new(Instant::now());
+            let mut timer = GameTimer::new_with_clock(INTERVAL, 4, 1, clock.clone());
+            timer.set_soft_drop(true);
+            clock.advance(INTERVAL);
+
+            assert!(timer.update().unwrap().soft_drop);
+            assert_eq!(timer.effective_gravity_ticks(), 1);
+        }
+    }
+
     mod set_gravity_ticks_tests {
         use super::*;
 
@@ -387,22 +662,27 @@ impl<C: Clock> IntervalTimer<C> {
         self.clock.now()
     }
 
-    /// Updates the timer's state with the time accumulated since the last tick. Returns true if a
-    /// tick is triggered by the update.
-    fn update(&mut self) -> bool {
+    /// Updates the timer's state with the time accumulated since the last tick. Returns the number
+    /// of whole tick intervals that elapsed, saturating at [u32::MAX].
+    ///
+    /// The elapsed intervals are counted by division rather than drained one at a time, so even a
+    /// stall of several hours, such as a laptop sleeping, is accounted for in constant time.
+    fn update_count(&mut self) -> u32 {
         let now = self.clock.now();
         let delta = now - self.last_update;
         self.last_update = now;
 
         self.time_since_last_tick += delta;
-        let ticked = self.time_since_last_tick >= self.tick_interval;
-        while self.time_since_last_tick >= self.tick_interval {
-            self.time_since_last_tick -= self.tick_interval
-        }
+        let interval = self.tick_interval.as_nanos().max(1);
+        let elapsed = self.time_since_last_tick.as_nanos();
+        let count = u32::try_from(elapsed / interval).unwrap_or(u32::MAX);
+        // The remainder is always shorter than the interval, so it fits in a u64 of nanoseconds.
+        // Any intervals beyond u32::MAX are dropped.
+        self.time_since_last_tick = Duration::from_nanos((elapsed % interval) as u64);
 
         self.next_tick_at = now + self.tick_interval - self.time_since_last_tick;
 
-        ticked
+        count
     }
 }
 
@@ -447,33 +727,69 @@ mod interval_timer_tests {
         }
 
         #[test]
-        fn when_elapsed_time_is_less_than_interval_returns_false() {
+        fn when_elapsed_time_is_less_than_interval_returns_0() {
             let mut timer = timer_at(Instant::now());
             timer.clock.advance(INTERVAL - Duration::from_millis(1));
-            assert!(!timer.update());
+            assert_eq!(timer.update_count(), 0);
         }
 
         #[test]
-        fn when_elapsed_time_equals_interval_returns_true() {
+        fn when_elapsed_time_equals_interval_returns_1() {
             let mut timer = timer_at(Instant::now());
             timer.clock.advance(INTERVAL);
-            assert!(timer.update());
+            assert_eq!(timer.update_count(), 1);
         }
 
         #[test]
-        fn when_elapsed_time_exceeds_interval_returns_true() {
+        fn when_elapsed_time_exceeds_interval_returns_1() {
             let mut timer = timer_at(Instant::now());
             timer.clock.advance(INTERVAL + Duration::from_millis(1));
-            assert!(timer.update());
+            assert_eq!(timer.update_count(), 1);
         }
 
         #[test]
-        fn when_elapsed_time_spans_multiple_intervals_returns_true() {
+        fn when_elapsed_time_spans_multiple_intervals_returns_2() {
             let mut timer = timer_at(Instant::now());
             timer
                 .clock
                 .advance(INTERVAL * 2 + Duration::from_millis(30));
-            assert!(timer.update());
+            assert_eq!(timer.update_count(), 2);
+        }
+
+        #[test]
+        fn when_stalled_for_hours_counts_every_interval_and_keeps_the_remainder() {
+            let mut timer = timer_at(Instant::now());
+            let remainder = Duration::from_millis(30);
+            timer
+                .clock
+                .advance(Duration::from_secs(8 * 60 * 60) + remainder);
+            assert_eq!(timer.update_count(), 8 * 60 * 60 * 10);
+            assert_eq!(timer.time_since_last_tick, remainder);
+        }
+
+        #[test]
+        fn when_more_than_u32_max_intervals_elapse_saturates() {
+            let mut timer =
+                IntervalTimer::new(Duration::from_nanos(1), MockClock::new(Instant::now()));
+            timer.clock.advance(Duration::from_secs(10_000));
+            assert_eq!(timer.update_count(), u32::MAX);
+            assert_eq!(timer.time_since_last_tick, Duration::ZERO);
+        }
+
+        #[test]
+        fn update_count_returns_number_of_whole_intervals_elapsed() {
+            let mut timer = timer_at(Instant::now());
+            timer
+                .clock
+                .advance(INTERVAL * 10 + Duration::from_millis(30));
+            assert_eq!(timer.update_count(), 10);
+        }
+
+        #[test]
+        fn when_no_interval_has_elapsed_update_count_returns_zero() {
+            let mut timer = timer_at(Instant::now());
+            timer.clock.advance(INTERVAL - Duration::from_millis(1));
+            assert_eq!(timer.update_count(), 0);
         }
 
         #[test]
@@ -481,7 +797,7 @@ mod interval_timer_tests {
             let mut timer = timer_at(Instant::now());
             timer.clock.advance(Duration::from_millis(40));
             let now = timer.clock.now();
-            timer.update();
+            timer.update_count();
             assert_eq!(timer.last_update, now);
         }
 
@@ -490,7 +806,7 @@ mod interval_timer_tests {
             let mut timer = timer_at(Instant::now());
             let elapsed = INTERVAL - Duration::from_millis(1);
             timer.clock.advance(elapsed);
-            timer.update();
+            timer.update_count();
             assert_eq!(timer.time_since_last_tick, elapsed);
         }
 
@@ -499,7 +815,7 @@ mod interval_timer_tests {
             let mut timer = timer_at(Instant::now());
             let remainder = Duration::from_millis(30);
             timer.clock.advance(INTERVAL + remainder);
-            timer.update();
+            timer.update_count();
             assert_eq!(timer.time_since_last_tick, remainder);
         }
 
@@ -509,7 +825,7 @@ mod interval_timer_tests {
             let elapsed = Duration::from_millis(40);
             timer.clock.advance(elapsed);
             let now = timer.clock.now();
-            timer.update();
+            timer.update_count();
             assert_eq!(timer.next_tick_at, now + INTERVAL - elapsed);
         }
 
@@ -518,7 +834,7 @@ mod interval_timer_tests {
             let mut timer = timer_at(Instant::now());
             let remainder = Duration::from_millis(30);
             timer.clock.advance(INTERVAL * 2 + remainder);
-            timer.update();
+            timer.update_count();
             assert_eq!(timer.time_since_last_tick, remainder);
         }
     }
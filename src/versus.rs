@@ -0,0 +1,270 @@
+use std::io;
+
+use rand_distr::{Distribution, Uniform};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    widgets::Widget,
+};
+
+use crate::game::{Game, UpdateOutcome};
+use crate::input::PollInput;
+use crate::timer::{Clock, GameTimer, SystemClock};
+
+/// Two games played side by side, where the lines cleared by each player send garbage to the
+/// other.
+///
+/// Both games are paced by a shared [GameTimer] so that they tick in lockstep, while gravity still
+/// accelerates independently with each player's level.
+#[derive(Debug)]
+pub struct MultiGame<I, C = SystemClock, S = Uniform<u8>> {
+    players: [Game<I, C, S>; 2],
+    timer: GameTimer<C>,
+}
+
+impl<I, C, S> MultiGame<I, C, S> {
+    /// Returns the games of both players.
+    pub fn players(&self) -> &[Game<I, C, S>; 2] {
+        &self.players
+    }
+}
+
+impl<I: PollInput, C: Clock + Clone, S: Distribution<u8>> MultiGame<I, C, S> {
+    /// Pits two games against each other, ticking at player A's frame interval against player A's
+    /// clock.
+    pub fn new(player_a: Game<I, C, S>, player_b: Game<I, C, S>) -> Self {
+        let timer = player_a.new_shared_timer();
+        Self {
+            players: [player_a, player_b],
+            timer,
+        }
+    }
+
+    /// Updates both games on the same tick, then sends the garbage earned by each player's line
+    /// clears to the other player. The hole in each garbage line is drawn from the receiving
+    /// player's RNG; see `BlockGenerator::random_column`.
+    pub fn step(&mut self) -> io::Result<[UpdateOutcome; 2]> {
+        if self.timer.update().is_none() {
+            return Ok([UpdateOutcome::Unchanged, UpdateOutcome::Unchanged]);
+        }
+        let interval = self.timer.tick_interval();
+        let mut outcomes = [
+            self.players[0].update_on_shared_tick(interval)?,
+            self.players[1].update_on_shared_tick(interval)?,
+        ];
+
+        let [a, b] = &mut self.players;
+        let attacks = [b.take_outgoing_garbage(), a.take_outgoing_garbage()];
+        for ((player, outcome), garbage) in self.players.iter_mut().zip(&mut outcomes).zip(attacks)
+        {
+            if garbage > 0 && !player.game_over() {
+                let hole_column = player.garbage_hole_column();
+                player.receive_garbage(garbage as usize, hole_column);
+                if matches!(outcome, UpdateOutcome::Unchanged) {
+                    *outcome = UpdateOutcome::Updated;
+                }
+            }
+        }
+
+        Ok(outcomes)
+    }
+}
+
+impl<I, C, S> Widget for &MultiGame<I, C, S> {
+    fn render(self, area: Rect, buf: &mut Buffer)
+    where
+        Self: Sized,
+    {
+        let [a_area, b_area] = area.layout(&Layout::horizontal([
+            Constraint::Fill(1),
+            Constraint::Fill(1),
+        ]));
+        self.players[0].render(a_area, buf);
+        self.players[1].render(b_area, buf);
+    }
+}
+
+#[cfg(test)]
+mod multi_game_tests {
+    use std::collections::HashMap;
+    use std::time::Instant;
+
+    use super::*;
+    use crate::block::BlockType;
+    use crate::block_generator::test_helpers::MockSampler;
+    use crate::board::{Cell, StandardBoard};
+    use crate::config::{Config, GameMode, Gravity, GravityMode};
+    use crate::game::test_helpers::{FRAME_INTERVAL, MockInput, make_game};
+    use crate::timer::test_helpers::MockClock;
+
+    fn config() -> Config {
+        Config {
+            frame_interval: FRAME_INTERVAL,
+            gravity: Gravity::new(1, 1, 1).unwrap(),
+            gravity_mode: GravityMode::Naive,
+            instant_gravity: false,
+            input_ticks: 1,
+            soft_drop_ticks: 1,
+            lock_delay_ticks: 0,
+            initial_garbage_lines: None,
+            preview_count: 3,
+            practice_mode: false,
+            mode: GameMode::Marathon,
+            spawn_columns: HashMap::new(),
+            initial_held_block: None,
+            combo_points: 50,
+        }
+    }
+
+    mod step_tests {
+        use super::*;
+
+        /// Returns a game in which player A has just cleared two lines by dropping an O block into
+        /// a two-column well.
+        fn game_with_double_clear(
+            clock: &MockClock,
+        ) -> MultiGame<MockInput, MockClock, MockSampler> {
+            // Both players only receive O blocks, which spawn in columns 4 and 5.
+            let mut a = make_game(clock.clone(), MockInput::new([]), config(), 4);
+            let b = make_game(clock.clone(), MockInput::new([]), config(), 4);
+            let mut cells = [[None; StandardBoard::COLUMNS]; StandardBoard::ROWS];
+            for row in &mut cells[StandardBoard::ROWS - 2..] {
+                *row = [Some(Cell::Block(BlockType::I)); StandardBoard::COLUMNS];
+                row[4] = None;
+                row[5] = None;
+            }
+            a.set_board(StandardBoard::from(cells));
+            a.drop_active_block();
+            MultiGame::new(a, b)
+        }
+
+        #[test]
+        fn when_player_a_clears_two_lines_player_b_receives_one_garbage_line() {
+            let clock = MockClock::new(Instant::now());
+            let mut multi_game = game_with_double_clear(&clock);
+
+            clock.advance(FRAME_INTERVAL);
+            multi_game.step().unwrap();
+
+            let [a, b] = multi_game.players();
+            assert_eq!(*a.board(), StandardBoard::new());
+            let garbage_rows: Vec<_> = b
+                .board()
+                .iter()
+                .filter(|row| row.contains(&Some(Cell::Garbage)))
+                .collect();
+            assert_eq!(garbage_rows.len(), 1);
+            assert_eq!(
+                garbage_rows[0].iter().filter(|cell| cell.is_none()).count(),
+                1
+            );
+        }
+
+        #[test]
+        fn garbage_holes_are_drawn_from_the_seeded_rng() {
+            let boards: Vec<_> = (0..2)
+                .map(|_| {
+                    let clock = MockClock::new(Instant::now());
+                    let mut multi_game = game_with_double_clear(&clock);
+                    clock.advance(FRAME_INTERVAL);
+                    multi_game.step().unwrap();
+                    multi_game.players()[1].board().clone()
+                })
+                .collect();
+
+            assert_eq!(boards[0], boards[1]);
+        }
+
+        #[test]
+        fn when_the_shared_interval_has_not_elapsed_neither_game_updates() {
+            let clock = MockClock::new(Instant::now());
+            let mut multi_game = game_with_double_clear(&clock);
+
+            let outcomes = multi_game.step().unwrap();
+
+            assert!(matches!(
+                outcomes,
+                [UpdateOutcome::Unchanged, UpdateOutcome::Unchanged]
+            ));
+            assert!(
+                multi_game.players()[1]
+                    .board()
+                    .iter()
+                    .all(|row| !row.contains(&Some(Cell::Garbage)))
+            );
+        }
+
+        #[test]
+        fn when_player_b_has_a_slower_frame_interval_both_games_tick_together() {
+            let clock = MockClock::new(Instant::now());
+            let a = make_game(clock.clone(), MockInput::new([]), config(), 4);
+            let slower = Config {
+                frame_interval: FRAME_INTERVAL * 2,
+                ..config()
+            };
+            let b = make_game(clock.clone(), MockInput::new([]), slower, 4);
+            let mut multi_game = MultiGame::new(a, b);
+            let spawned_at = multi_game
+                .players()
+                .each_ref()
+                .map(|player| player.active_block().top_left());
+
+            clock.advance(FRAME_INTERVAL);
+            multi_game.step().unwrap();
+
+            for (player, (row, column)) in multi_game.players().iter().zip(spawned_at) {
+                assert_eq!(player.active_block().top_left(), (row + 1, column));
+            }
+        }
+
+        #[test]
+        fn when_player_b_has_a_slower_frame_interval_its_ultra_clock_follows_the_shared_ticks() {
+            let clock = MockClock::new(Instant::now());
+            let ultra = Config {
+                mode: GameMode::Ultra {
+                    duration: FRAME_INTERVAL * 3,
+                },
+                ..config()
+            };
+            let a = make_game(clock.clone(), MockInput::new([]), ultra.clone(), 4);
+            let slower = Config {
+                frame_interval: FRAME_INTERVAL * 2,
+                ..ultra
+            };
+            let b = make_game(clock.clone(), MockInput::new([]), slower, 4);
+            let mut multi_game = MultiGame::new(a, b);
+
+            for _ in 0..2 {
+                clock.advance(FRAME_INTERVAL);
+                multi_game.step().unwrap();
+            }
+            for player in multi_game.players() {
+                assert_eq!(player.elapsed(), FRAME_INTERVAL * 2);
+                assert!(!player.is_complete());
+            }
+
+            clock.advance(FRAME_INTERVAL);
+            multi_game.step().unwrap();
+
+            for player in multi_game.players() {
+                assert_eq!(player.elapsed(), FRAME_INTERVAL * 3);
+                assert!(player.is_complete());
+            }
+        }
+
+        #[test]
+        fn when_no_lines_are_cleared_no_garbage_is_sent() {
+            let clock = MockClock::new(Instant::now());
+            let a = make_game(clock.clone(), MockInput::new([]), config(), 4);
+            let b = make_game(clock.clone(), MockInput::new([]), config(), 4);
+            let mut multi_game = MultiGame::new(a, b);
+
+            clock.advance(FRAME_INTERVAL);
+            multi_game.step().unwrap();
+
+            for player in multi_game.players() {
+                assert_eq!(*player.board(), StandardBoard::new());
+            }
+        }
+    }
+}